@@ -0,0 +1,126 @@
+use crate::keystore::{Keystore, KeystoreEntry};
+use crossterm::event::{KeyCode, KeyEvent};
+use leo_bindings::leo_bindings_sdk::Account;
+use ratatui::{
+    Frame,
+    layout::{Alignment, Rect},
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Borders, Paragraph},
+};
+use snarkvm::prelude::TestnetV0;
+
+pub enum PickerOutcome {
+    Selected(Account<TestnetV0>),
+    Quit,
+}
+
+pub struct AccountPicker {
+    entries: Vec<KeystoreEntry>,
+    selected: usize,
+    passphrase: String,
+    entering_passphrase: bool,
+    error: Option<String>,
+}
+
+impl AccountPicker {
+    pub fn new(keystore: &Keystore) -> Self {
+        Self {
+            entries: keystore.accounts.clone(),
+            selected: 0,
+            passphrase: String::new(),
+            entering_passphrase: false,
+            error: None,
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> Option<PickerOutcome> {
+        if self.entering_passphrase {
+            match key.code {
+                KeyCode::Char(c) => self.passphrase.push(c),
+                KeyCode::Backspace => {
+                    self.passphrase.pop();
+                }
+                KeyCode::Esc => {
+                    self.entering_passphrase = false;
+                    self.passphrase.clear();
+                }
+                KeyCode::Enter => {
+                    let entry = &self.entries[self.selected];
+                    match entry.account(&self.passphrase) {
+                        Ok(account) => return Some(PickerOutcome::Selected(account)),
+                        Err(e) => {
+                            self.error = Some(e.to_string());
+                            self.passphrase.clear();
+                        }
+                    }
+                }
+                _ => {}
+            }
+            return None;
+        }
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Some(PickerOutcome::Quit),
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down if self.selected + 1 < self.entries.len() => self.selected += 1,
+            KeyCode::Enter if !self.entries.is_empty() => {
+                self.entering_passphrase = true;
+                self.error = None;
+            }
+            _ => {}
+        }
+        None
+    }
+
+    pub fn view(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .title("Select Account")
+            .borders(Borders::ALL);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let mut lines: Vec<Line> = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| {
+                let style = if i == self.selected {
+                    Style::default().fg(Color::Black).bg(Color::Yellow)
+                } else {
+                    Style::default().fg(Color::White)
+                };
+                Line::from(format!("{}  {}", entry.name, entry.address)).style(style)
+            })
+            .collect();
+
+        lines.push(Line::from(""));
+        if self.entering_passphrase {
+            lines.push(Line::from(format!(
+                "Passphrase: {}_",
+                "*".repeat(self.passphrase.len())
+            )));
+            lines.push(
+                Line::from("Enter to unlock, Esc to go back")
+                    .style(Style::default().fg(Color::Gray)),
+            );
+        } else {
+            lines.push(
+                Line::from("↑/↓ to choose, Enter to unlock, q to quit")
+                    .style(Style::default().fg(Color::Gray)),
+            );
+        }
+        if let Some(error) = &self.error {
+            lines.push(Line::from(error.clone()).style(Style::default().fg(Color::Red)));
+        }
+
+        let top = inner.y + inner.height.saturating_sub(lines.len() as u16) / 2;
+        let content = Rect {
+            x: inner.x,
+            y: top,
+            width: inner.width,
+            height: inner.height.saturating_sub(top - inner.y),
+        };
+        frame.render_widget(Paragraph::new(lines).alignment(Alignment::Center), content);
+    }
+}
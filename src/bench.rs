@@ -0,0 +1,143 @@
+//! `poker bench`: plays a short game on a local VM and times every
+//! transition the program has.
+
+use crate::game::GameState;
+use crate::history::Street;
+use crate::rigged::Deal;
+use crate::scenario::{Action, Step, Table};
+use crate::telemetry::{Transition, TransitionSample, format_duration};
+use leo_bindings::leo_bindings_sdk::{LocalVM, VMManager};
+use snarkvm::prelude::TestnetV0;
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+const DEAL: &str = "Ah Ad / Kc Qd / 9s 8h / 2c 7d Jh 4s 3c";
+
+/// One game covering every transition: a hand checked down to showdown, a
+/// hand folded preflop, then P1 claiming the prize.
+pub fn bench_round() -> anyhow::Result<Vec<TransitionSample>> {
+    let deal: Deal = DEAL.parse()?;
+    let mut table = Table::seat(LocalVM::new()?, &deal)?;
+
+    table.settle()?;
+    call_down(&mut table)?;
+
+    while matches!(
+        table.state()?,
+        GameState::P1NewShuffle
+            | GameState::P2NewShuffle
+            | GameState::P2Shuffle
+            | GameState::P3Shuffle
+    ) {
+        table.advance()?;
+    }
+    table.settle()?;
+    for _ in 0..2 {
+        act(&mut table, Action::Fold)?;
+        table.settle()?;
+    }
+
+    table.end_game(1)?;
+    table.advance()?;
+    Ok(table.samples)
+}
+
+/// Calls, or checks, every bet until the hand is over.
+fn call_down<M: VMManager<TestnetV0>>(table: &mut Table<M>) -> anyhow::Result<()> {
+    while table.state()?.is_betting_state() {
+        act(table, Action::Call)?;
+        table.settle()?;
+    }
+    Ok(())
+}
+
+fn act<M: VMManager<TestnetV0>>(table: &mut Table<M>, action: Action) -> anyhow::Result<()> {
+    let state = table.state()?;
+    let (Some(player), Some(street)) = (state.current_player(), Street::of_state(state.to_u8()))
+    else {
+        anyhow::bail!("No bet expected in state {:?}", state);
+    };
+    table.act(street, Step { player, action })
+}
+
+/// Per-transition runs, mean, min and max, in program order.
+pub fn summary(
+    samples: &[TransitionSample],
+) -> Vec<(Transition, usize, Duration, Duration, Duration)> {
+    let mut by_transition: BTreeMap<Transition, Vec<Duration>> = BTreeMap::new();
+    for sample in samples {
+        by_transition
+            .entry(sample.transition)
+            .or_default()
+            .push(sample.elapsed);
+    }
+    by_transition
+        .into_iter()
+        .map(|(transition, times)| {
+            let total: Duration = times.iter().sum();
+            let min = *times.iter().min().unwrap();
+            let max = *times.iter().max().unwrap();
+            (
+                transition,
+                times.len(),
+                total / times.len() as u32,
+                min,
+                max,
+            )
+        })
+        .collect()
+}
+
+pub fn print_summary(samples: &[TransitionSample]) {
+    println!(
+        "{:<20}  {:>4}  {:>8}  {:>8}  {:>8}",
+        "Transition", "Runs", "Mean", "Min", "Max"
+    );
+    for (transition, runs, mean, min, max) in summary(samples) {
+        println!(
+            "{:<20}  {:>4}  {:>8}  {:>8}  {:>8}",
+            transition.name(),
+            runs,
+            format_duration(mean),
+            format_duration(min),
+            format_duration(max)
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary() {
+        let sample = |transition, ms| TransitionSample {
+            transition,
+            elapsed: Duration::from_millis(ms),
+        };
+        let samples = [
+            sample(Transition::Showdown, 300),
+            sample(Transition::CreateGame, 900),
+            sample(Transition::Showdown, 100),
+        ];
+        assert_eq!(
+            summary(&samples),
+            vec![
+                (
+                    Transition::CreateGame,
+                    1,
+                    Duration::from_millis(900),
+                    Duration::from_millis(900),
+                    Duration::from_millis(900)
+                ),
+                (
+                    Transition::Showdown,
+                    2,
+                    Duration::from_millis(200),
+                    Duration::from_millis(100),
+                    Duration::from_millis(300)
+                ),
+            ]
+        );
+    }
+}
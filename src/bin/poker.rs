@@ -1,47 +1,150 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use crossterm::{
-    event::{self, Event},
+    event::{self, DisableBracketedPaste, EnableBracketedPaste, Event},
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
+use leo_bindings::leo_bindings_sdk::Account;
+use poker::account_picker::{AccountPicker, PickerOutcome};
+#[cfg(feature = "test-utils")]
+use poker::bench::{bench_round, print_summary};
+use poker::cards::CardStyle;
+use poker::config::Config;
 use poker::game::{
-    Game, GameMessage, handle_game_key, new_game_from_private_key, new_testnet_game,
+    Credits, Game, GameMessage, handle_game_key, new_game_from_account, new_testnet_game,
 };
 use poker::game_state::NetworkType;
+use poker::invite::InviteCode;
+use poker::keystore::{Keystore, PASSPHRASE_ENV, read_passphrase};
+use poker::leaderboard::{Leaderboard, RestGameSource};
+use poker::stats::StatsDb;
+use poker::telemetry::TransitionTimes;
+#[cfg(feature = "test-utils")]
+use poker::telemetry::{Transition, format_duration};
+use poker::tx::TxHistory;
 use ratatui::{
     Terminal,
     backend::CrosstermBackend,
     layout::{Constraint, Direction, Layout},
 };
+use snarkvm::prelude::TestnetV0;
 use std::io;
 use std::panic;
+use std::path::PathBuf;
 use std::time::Duration;
 
 #[derive(Parser)]
 #[command(name = "poker")]
 #[command(about = "Mental Poker on Aleo", long_about = None)]
 struct Cli {
+    /// Play as PRIVATE_KEY_P<index + 1>, or a dev account on a local network
     #[arg(short, long)]
     index: Option<u16>,
+    /// Profile to load from poker.toml
+    #[arg(short, long)]
+    profile: Option<String>,
+    /// Write this session's log to a file as JSON lines
+    #[arg(long)]
+    session_log: Option<PathBuf>,
+    /// Card style: unicode, ascii or four-color
+    #[arg(long)]
+    card_style: Option<CardStyle>,
+    /// Use the high-contrast, colorblind-safe card palette
+    #[arg(long)]
+    high_contrast: bool,
+    #[command(subcommand)]
+    command: Option<Commands>,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Join a game from an invite code
+    Join { code: String },
+    /// Manage accounts in the encrypted keystore
+    Account {
+        #[command(subcommand)]
+        action: AccountCommand,
+    },
+    /// Rank addresses by tournaments won on the profile's network
+    Leaderboard {
+        /// Number of addresses to show
+        #[arg(long, default_value_t = 20)]
+        top: usize,
+    },
+    /// Time every transition on a local VM
+    #[cfg(feature = "test-utils")]
+    Bench {
+        /// Number of games to play
+        #[arg(long, default_value_t = 1)]
+        rounds: usize,
+    },
+}
+
+#[derive(Subcommand)]
+enum AccountCommand {
+    /// List accounts in the keystore
+    List,
+    /// Generate a new account
+    Generate { name: String },
+    /// Import an existing private key
+    Import { name: String },
+    /// Print the private key of an account
+    Export { name: String },
+    /// Remove an account from the keystore
+    Remove { name: String },
+}
+
+enum AccountSource {
+    DevIndex(u16),
+    Resolved(Account<TestnetV0>),
+    Picker(AccountPicker),
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     dotenvy::dotenv().ok();
     let cli = Cli::parse();
 
-    let endpoint = std::env::var("ENDPOINT").expect("ENDPOINT environment variable must be set");
-    let network = std::env::var("NETWORK").expect("NETWORK environment variable must be set");
+    let invite = match &cli.command {
+        Some(Commands::Join { code }) => Some(code.parse::<InviteCode>()?),
+        Some(Commands::Account { action }) => return run_account_command(action),
+        #[cfg(feature = "test-utils")]
+        Some(Commands::Bench { rounds }) => return run_bench(*rounds),
+        Some(Commands::Leaderboard { .. }) | None => None,
+    };
+
+    let mut profile = Config::load()?.profile(cli.profile.as_deref(), "testnet")?;
+    if let Some(style) = cli.card_style {
+        profile.ui.cards.style = style;
+    }
+    if cli.high_contrast {
+        profile.ui.cards.high_contrast = true;
+    }
+    if let Some(invite) = &invite {
+        profile.network = invite.network;
+        profile.endpoint = invite.endpoint.clone();
+    }
+    if profile.network == NetworkType::Local {
+        eprintln!("Local games can only be played from poker_test");
+        std::process::exit(1);
+    }
+
+    if let Some(Commands::Leaderboard { top }) = cli.command {
+        return run_leaderboard(&profile.endpoint, profile.network, top);
+    }
 
-    let network_type = match network.to_lowercase().as_str() {
-        "testnet" => NetworkType::Testnet,
-        "mainnet" => NetworkType::Mainnet,
-        _ => {
+    let source = if let Some(index) = cli.index {
+        AccountSource::DevIndex(index)
+    } else if let Some(account) = &profile.account {
+        AccountSource::Resolved(account.resolve()?)
+    } else {
+        let keystore = Keystore::open_default()?;
+        if keystore.accounts.is_empty() {
             eprintln!(
-                "Invalid NETWORK value: '{}'. Must be 'testnet' or 'mainnet'",
-                network
+                "No account configured. Set PRIVATE_KEY, set 'account' in the profile or run `poker account generate <name>`"
             );
             std::process::exit(1);
         }
+        AccountSource::Picker(AccountPicker::new(&keystore))
     };
 
     init_file_logger(cli.index)?;
@@ -49,18 +152,38 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let original_hook = panic::take_hook();
     panic::set_hook(Box::new(move |panic_info| {
         let _ = disable_raw_mode();
-        let _ = execute!(io::stdout(), LeaveAlternateScreen);
+        let _ = execute!(io::stdout(), DisableBracketedPaste, LeaveAlternateScreen);
         original_hook(panic_info);
     }));
 
-    let handle = if let Some(index) = cli.index {
-        new_testnet_game(index, &endpoint)?
-    } else {
-        let private_key =
-            std::env::var("PRIVATE_KEY").expect("PRIVATE_KEY environment variable must be set");
-        new_game_from_private_key(&private_key, &endpoint)?
+    let handle = match source {
+        AccountSource::DevIndex(index) => {
+            new_testnet_game(index, &profile.endpoint, profile.network)?
+        }
+        AccountSource::Resolved(account) => {
+            new_game_from_account(account, &profile.endpoint, profile.network)?
+        }
+        AccountSource::Picker(picker) => match pick_account(picker)? {
+            Some(account) => new_game_from_account(account, &profile.endpoint, profile.network)?,
+            None => return Ok(()),
+        },
     };
-    let mut game = Game::new(handle, network_type);
+    let mut game = Game::new(handle, &profile);
+    // Another client on this machine may hold the database open.
+    match StatsDb::open_default() {
+        Ok(db) => game.set_stats(db),
+        Err(e) => game
+            .model
+            .log_warning(format!("Stats are not recorded: {}", e)),
+    }
+    game.set_transition_times(TransitionTimes::open_default()?);
+    game.set_tx_history(TxHistory::open_default()?);
+    if let Some(path) = &cli.session_log {
+        game.set_session_log(path)?;
+    }
+    if let Some(invite) = &invite {
+        game.join_invite(invite);
+    }
 
     let mut terminal = setup_terminal()?;
 
@@ -68,7 +191,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         terminal.draw(|f| {
             let chunks = Layout::default()
                 .direction(Direction::Vertical)
-                .constraints([Constraint::Min(10), Constraint::Length(8)])
+                .constraints([
+                    Constraint::Min(10),
+                    Constraint::Length(profile.ui.log_height),
+                ])
                 .split(f.area());
 
             game.view(f, chunks[0]);
@@ -89,6 +215,158 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+fn run_account_command(action: &AccountCommand) -> Result<(), Box<dyn std::error::Error>> {
+    let mut keystore = Keystore::open_default()?;
+    match action {
+        AccountCommand::List => {
+            if keystore.accounts.is_empty() {
+                println!("No accounts in {}", Keystore::default_path().display());
+            }
+            for entry in &keystore.accounts {
+                println!("{}  {}", entry.name, entry.address);
+            }
+        }
+        AccountCommand::Generate { name } => {
+            let passphrase = new_passphrase()?;
+            let entry = keystore.generate(name, &passphrase)?;
+            println!("Generated {}  {}", entry.name, entry.address);
+            keystore.save()?;
+        }
+        AccountCommand::Import { name } => {
+            let private_key = rpassword::prompt_password("Private key: ")?;
+            let passphrase = new_passphrase()?;
+            let entry = keystore.import(name, &private_key, &passphrase)?;
+            println!("Imported {}  {}", entry.name, entry.address);
+            keystore.save()?;
+        }
+        AccountCommand::Export { name } => {
+            let passphrase = read_passphrase(&format!("Passphrase for '{}': ", name))?;
+            println!("{}", keystore.export(name, &passphrase)?);
+        }
+        AccountCommand::Remove { name } => {
+            let passphrase = read_passphrase(&format!("Passphrase for '{}': ", name))?;
+            keystore.export(name, &passphrase)?;
+            keystore.remove(name)?;
+            keystore.save()?;
+            println!("Removed {}", name);
+        }
+    }
+    Ok(())
+}
+
+fn run_leaderboard(
+    endpoint: &str,
+    network: NetworkType,
+    top: usize,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let source = RestGameSource::new(endpoint, network);
+    let mut leaderboard = Leaderboard::open_default()?;
+    let games = leaderboard.index(source.api());
+    let completed = games.update(&source);
+    let standings = games.standings();
+    println!(
+        "{} completed games ({} new), {} still running",
+        games.completed.len(),
+        completed,
+        games.open.len()
+    );
+    leaderboard.save()?;
+
+    if standings.is_empty() {
+        return Ok(());
+    }
+    println!(
+        "{:>3}  {:<63}  {:>4}  {:>5}  {:>12}  {:>12}",
+        "#", "Address", "Wins", "Games", "Won", "Net"
+    );
+    for (rank, standing) in standings.iter().take(top).enumerate() {
+        println!(
+            "{:>3}  {:<63}  {:>4}  {:>5}  {:>12}  {:>12}",
+            rank + 1,
+            standing.address,
+            standing.wins,
+            standing.games,
+            Credits::from(standing.won).to_string(),
+            standing.net_credits()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(feature = "test-utils")]
+fn run_bench(rounds: usize) -> Result<(), Box<dyn std::error::Error>> {
+    let local = NetworkType::Local.name();
+    let mut times = TransitionTimes::open_default()?;
+    let mut samples = Vec::new();
+    for round in 1..=rounds {
+        eprintln!("Round {}/{}", round, rounds);
+        samples.extend(bench_round()?);
+    }
+    print_summary(&samples);
+    for sample in samples {
+        times.record(local, sample);
+    }
+    times.save()?;
+
+    // The bindings don't report when proving ends, so network timings are
+    // whole calls. Local proving time is the best estimate of the proving
+    // share; the rest is broadcast and confirmation.
+    for (network, timings) in times.networks.iter().filter(|(name, _)| *name != local) {
+        println!(
+            "\n{}: mean call (proving and confirmation), and time beyond local proving",
+            network
+        );
+        for transition in Transition::all() {
+            let (Some(timing), Some(proving)) =
+                (timings.get(&transition), times.timing(local, transition))
+            else {
+                continue;
+            };
+            let mean = Duration::from_millis(timing.mean_ms());
+            let overhead = mean.saturating_sub(Duration::from_millis(proving.mean_ms()));
+            println!(
+                "{:<20}  {:>8}  {:>8}",
+                transition.name(),
+                format_duration(mean),
+                format_duration(overhead)
+            );
+        }
+    }
+    Ok(())
+}
+
+fn new_passphrase() -> Result<String, Box<dyn std::error::Error>> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV) {
+        return Ok(passphrase);
+    }
+    let passphrase = rpassword::prompt_password("New passphrase: ")?;
+    let confirm = rpassword::prompt_password("Confirm passphrase: ")?;
+    if passphrase != confirm {
+        return Err("Passphrases do not match".into());
+    }
+    Ok(passphrase)
+}
+
+fn pick_account(
+    mut picker: AccountPicker,
+) -> Result<Option<Account<TestnetV0>>, Box<dyn std::error::Error>> {
+    let mut terminal = setup_terminal()?;
+    let outcome = loop {
+        terminal.draw(|f| picker.view(f, f.area()))?;
+        if event::poll(Duration::from_millis(100))?
+            && let Event::Key(key) = event::read()?
+            && let Some(outcome) = picker.handle_key(key)
+        {
+            break outcome;
+        }
+    };
+    restore_terminal(&mut terminal)?;
+    Ok(match outcome {
+        PickerOutcome::Selected(account) => Some(account),
+        PickerOutcome::Quit => None,
+    })
+}
+
 fn init_file_logger(account_index: Option<u16>) -> Result<(), Box<dyn std::error::Error>> {
     use std::fs::File;
     use std::io::Write;
@@ -106,7 +384,7 @@ fn init_file_logger(account_index: Option<u16>) -> Result<(), Box<dyn std::error
         .filter_module("leo_bindings", log::LevelFilter::Debug)
         .filter_module("credits_bindings", log::LevelFilter::Debug)
         .filter_module("mental_poker_bindings", log::LevelFilter::Debug)
-.filter(Some("ureq"), log::LevelFilter::Off)
+        .filter(Some("ureq"), log::LevelFilter::Off)
         .try_init()?;
 
     Ok(())
@@ -115,7 +393,7 @@ fn init_file_logger(account_index: Option<u16>) -> Result<(), Box<dyn std::error
 fn setup_terminal() -> Result<Terminal<CrosstermBackend<io::Stdout>>, Box<dyn std::error::Error>> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
+    execute!(stdout, EnterAlternateScreen, EnableBracketedPaste)?;
     let backend = CrosstermBackend::new(stdout);
     let terminal = Terminal::new(backend)?;
     Ok(terminal)
@@ -125,16 +403,22 @@ fn restore_terminal(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    execute!(
+        terminal.backend_mut(),
+        DisableBracketedPaste,
+        LeaveAlternateScreen
+    )?;
     terminal.show_cursor()?;
     Ok(())
 }
 
 fn handle_event() -> Result<Option<GameMessage>, Box<dyn std::error::Error>> {
-    if event::poll(Duration::from_millis(100))?
-        && let Event::Key(key) = event::read()?
-    {
-        return Ok(handle_game_key(key));
+    if event::poll(Duration::from_millis(100))? {
+        return Ok(match event::read()? {
+            Event::Key(key) => handle_game_key(key),
+            Event::Paste(text) => Some(GameMessage::Paste(text)),
+            _ => None,
+        });
     }
     Ok(None)
 }
@@ -0,0 +1,62 @@
+use clap::Parser;
+use poker::config::{AccountRef, Config};
+use poker::game::{Game, new_game_from_account, new_local_game};
+use poker::game_state::NetworkType;
+use poker::server::Server;
+
+#[derive(Parser)]
+#[command(name = "poker_server")]
+#[command(about = "Serve poker seats to external frontends over JSON-RPC/WebSocket", long_about = None)]
+struct Cli {
+    /// Profile to load from poker.toml
+    #[arg(short, long)]
+    profile: Option<String>,
+    /// Address to listen on
+    #[arg(short, long, default_value = "127.0.0.1:9944")]
+    listen: String,
+    /// Account for a seat ("dev:<index>", "env:<VAR>", "keystore:<name>" or a
+    /// private key). Repeat for several seats; defaults to the profile account.
+    #[arg(short, long = "account")]
+    accounts: Vec<AccountRef>,
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    dotenvy::dotenv().ok();
+    let cli = Cli::parse();
+    env_logger::Builder::from_env(env_logger::Env::default().filter_or("RUST_LOG", "info"))
+        .filter(Some("ureq"), log::LevelFilter::Off)
+        .try_init()?;
+
+    let profile = Config::load()?.profile(cli.profile.as_deref(), "local")?;
+    let games: Vec<Game> = if profile.network == NetworkType::Local {
+        if !cli.accounts.is_empty() {
+            return Err("Local seats always use the three dev accounts".into());
+        }
+        new_local_game()?
+            .into_iter()
+            .map(|handle| Game::new(handle, &profile))
+            .collect()
+    } else {
+        let accounts = if cli.accounts.is_empty() {
+            profile.account.iter().cloned().collect()
+        } else {
+            cli.accounts
+        };
+        if accounts.is_empty() {
+            return Err(
+                "No account configured. Pass --account or set 'account' in the profile".into(),
+            );
+        }
+        accounts
+            .iter()
+            .map(|account| {
+                let handle =
+                    new_game_from_account(account.resolve()?, &profile.endpoint, profile.network)?;
+                Ok(Game::new(handle, &profile))
+            })
+            .collect::<anyhow::Result<_>>()?
+    };
+
+    Server::bind(&cli.listen, games)?.run()?;
+    Ok(())
+}
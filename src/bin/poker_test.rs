@@ -1,12 +1,11 @@
 use clap::{Parser, Subcommand};
 use crossterm::{
-    event::{self, Event, KeyCode},
+    event::{self, DisableBracketedPaste, EnableBracketedPaste, Event, KeyCode},
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
-use poker::game::{
-    DEFAULT_ENDPOINT, Game, GameMessage, handle_game_key, new_local_game, new_testnet_game,
-};
+use poker::config::{Config, Profile};
+use poker::game::{Game, GameMessage, handle_game_key, new_local_game, new_testnet_game};
 use poker::game_state::NetworkType;
 use ratatui::{
     Terminal,
@@ -17,12 +16,19 @@ use ratatui::{
 };
 use std::io;
 use std::panic;
+use std::path::PathBuf;
 use std::time::Duration;
 
 #[derive(Parser)]
 #[command(name = "poker_test")]
 #[command(about = "Mental Poker test mode", long_about = None)]
 struct Cli {
+    /// Profile to load from poker.toml
+    #[arg(short, long, global = true)]
+    profile: Option<String>,
+    /// Write each player's session log to <path>.P<n> as JSON lines
+    #[arg(long, global = true)]
+    session_log: Option<PathBuf>,
     #[command(subcommand)]
     command: Commands,
 }
@@ -31,13 +37,14 @@ struct Cli {
 enum Commands {
     Local,
     Testnet {
-        #[arg(short, long, default_value = DEFAULT_ENDPOINT)]
-        endpoint: String,
+        #[arg(short, long)]
+        endpoint: Option<String>,
     },
 }
 
 struct TestModel {
     games: [Game; 3],
+    log_height: u16,
     active_index: usize,
 }
 
@@ -48,20 +55,21 @@ enum TestMessage {
 }
 
 impl TestModel {
-    fn new(network_type: NetworkType, endpoint: &str) -> Result<Self, Box<dyn std::error::Error>> {
-        let games = match network_type {
+    fn new(profile: &Profile) -> Result<Self, Box<dyn std::error::Error>> {
+        let endpoint = &profile.endpoint;
+        let games = match profile.network {
             NetworkType::Local => {
                 let [g0, g1, g2] = new_local_game()?;
                 [
-                    Game::new(g0, network_type),
-                    Game::new(g1, network_type),
-                    Game::new(g2, network_type),
+                    Game::new(g0, profile),
+                    Game::new(g1, profile),
+                    Game::new(g2, profile),
                 ]
             }
             NetworkType::Testnet => [
-                Game::new(new_testnet_game(0u16, endpoint)?, network_type),
-                Game::new(new_testnet_game(1u16, endpoint)?, network_type),
-                Game::new(new_testnet_game(2u16, endpoint)?, network_type),
+                Game::new(new_testnet_game(0u16, endpoint, profile.network)?, profile),
+                Game::new(new_testnet_game(1u16, endpoint, profile.network)?, profile),
+                Game::new(new_testnet_game(2u16, endpoint, profile.network)?, profile),
             ],
             NetworkType::Mainnet => {
                 panic!("Mainnet not supported in test mode");
@@ -70,6 +78,7 @@ impl TestModel {
 
         Ok(Self {
             games,
+            log_height: profile.ui.log_height,
             active_index: 0,
         })
     }
@@ -96,7 +105,7 @@ impl TestModel {
     fn view(&self, frame: &mut ratatui::Frame) {
         let main_layout = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Min(10), Constraint::Length(8)])
+            .constraints([Constraint::Min(10), Constraint::Length(self.log_height)])
             .split(frame.area());
 
         let player_layout = Layout::default()
@@ -136,23 +145,35 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let original_hook = panic::take_hook();
     panic::set_hook(Box::new(move |panic_info| {
         let _ = disable_raw_mode();
-        let _ = execute!(io::stdout(), LeaveAlternateScreen);
+        let _ = execute!(io::stdout(), DisableBracketedPaste, LeaveAlternateScreen);
         original_hook(panic_info);
     }));
 
-    let (network_type, endpoint) = match cli.command {
-        Commands::Local => (NetworkType::Local, DEFAULT_ENDPOINT.to_string()),
+    let config = Config::load()?;
+    let profile = match cli.command {
+        Commands::Local => {
+            let name = cli.profile.as_deref().unwrap_or("local");
+            let mut profile = config.profile(Some(name), name)?;
+            profile.network = NetworkType::Local;
+            profile
+        }
         Commands::Testnet { endpoint } => {
-            let endpoint = if endpoint == DEFAULT_ENDPOINT {
-                std::env::var("ENDPOINT").unwrap_or(endpoint)
-            } else {
-                endpoint
-            };
-            (NetworkType::Testnet, endpoint)
+            let name = cli.profile.as_deref().unwrap_or("testnet");
+            let mut profile = config.profile(Some(name), name)?;
+            profile.network = NetworkType::Testnet;
+            if let Some(endpoint) = endpoint {
+                profile.endpoint = endpoint;
+            }
+            profile
         }
     };
 
-    let mut model = TestModel::new(network_type, &endpoint)?;
+    let mut model = TestModel::new(&profile)?;
+    if let Some(path) = &cli.session_log {
+        for (i, game) in model.games.iter_mut().enumerate() {
+            game.set_session_log(&PathBuf::from(format!("{}.P{}", path.display(), i + 1)))?;
+        }
+    }
 
     let mut terminal = setup_terminal()?;
 
@@ -178,7 +199,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 fn setup_terminal() -> Result<Terminal<CrosstermBackend<io::Stdout>>, Box<dyn std::error::Error>> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
+    execute!(stdout, EnterAlternateScreen, EnableBracketedPaste)?;
     let backend = CrosstermBackend::new(stdout);
     let terminal = Terminal::new(backend)?;
     Ok(terminal)
@@ -188,19 +209,25 @@ fn restore_terminal(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    execute!(
+        terminal.backend_mut(),
+        DisableBracketedPaste,
+        LeaveAlternateScreen
+    )?;
     terminal.show_cursor()?;
     Ok(())
 }
 
 fn handle_event() -> Result<Option<TestMessage>, Box<dyn std::error::Error>> {
-    if event::poll(Duration::from_millis(100))?
-        && let Event::Key(key) = event::read()?
-    {
-        return Ok(match key.code {
-            KeyCode::Tab => Some(TestMessage::NextPlayer),
-            KeyCode::BackTab => Some(TestMessage::PrevPlayer),
-            _ => handle_game_key(key).map(TestMessage::GameMessage),
+    if event::poll(Duration::from_millis(100))? {
+        return Ok(match event::read()? {
+            Event::Key(key) => match key.code {
+                KeyCode::Tab => Some(TestMessage::NextPlayer),
+                KeyCode::BackTab => Some(TestMessage::PrevPlayer),
+                _ => handle_game_key(key).map(TestMessage::GameMessage),
+            },
+            Event::Paste(text) => Some(TestMessage::GameMessage(GameMessage::Paste(text))),
+            _ => None,
         });
     }
 
@@ -1,89 +1,445 @@
 use colored::*;
 use mental_poker_bindings::mental_poker::{Cards, RevealedCards};
+use serde::{Deserialize, Serialize, Serializer};
 use snarkvm::prelude::*;
 use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// On-chain index of a card that has not been revealed.
+pub const FACE_DOWN: u8 = 255;
+
+/// Suits in on-chain order: card index / 13.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Suit {
+    Spades,
+    Clubs,
+    Hearts,
+    Diamonds,
+}
+
+impl Suit {
+    pub const ALL: [Suit; 4] = [Suit::Spades, Suit::Clubs, Suit::Hearts, Suit::Diamonds];
+
+    pub fn index(self) -> u8 {
+        self as u8
+    }
+
+    pub fn letter(self) -> char {
+        match self {
+            Suit::Spades => 's',
+            Suit::Clubs => 'c',
+            Suit::Hearts => 'h',
+            Suit::Diamonds => 'd',
+        }
+    }
+
+    pub fn symbol(self) -> char {
+        match self {
+            Suit::Spades => '♠',
+            Suit::Clubs => '♣',
+            Suit::Hearts => '♥',
+            Suit::Diamonds => '♦',
+        }
+    }
+
+    /// The suit as drawn by the Unicode card style.
+    pub fn emoji(self) -> &'static str {
+        match self {
+            Suit::Spades => "♠️",
+            Suit::Clubs => "♣️",
+            Suit::Hearts => "❤️",
+            Suit::Diamonds => "♦️",
+        }
+    }
+
+    pub fn is_red(self) -> bool {
+        matches!(self, Suit::Hearts | Suit::Diamonds)
+    }
+}
+
+impl TryFrom<char> for Suit {
+    type Error = anyhow::Error;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            's' | 'S' | '♠' => Ok(Suit::Spades),
+            'c' | 'C' | '♣' => Ok(Suit::Clubs),
+            'h' | 'H' | '♥' => Ok(Suit::Hearts),
+            'd' | 'D' | '♦' => Ok(Suit::Diamonds),
+            _ => anyhow::bail!("Invalid suit '{}'", c),
+        }
+    }
+}
+
+/// Ranks in on-chain order: card index % 13.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Rank {
+    Two,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    Ten,
+    Jack,
+    Queen,
+    King,
+    Ace,
+}
+
+impl Rank {
+    pub const ALL: [Rank; 13] = [
+        Rank::Two,
+        Rank::Three,
+        Rank::Four,
+        Rank::Five,
+        Rank::Six,
+        Rank::Seven,
+        Rank::Eight,
+        Rank::Nine,
+        Rank::Ten,
+        Rank::Jack,
+        Rank::Queen,
+        Rank::King,
+        Rank::Ace,
+    ];
+
+    pub fn index(self) -> u8 {
+        self as u8
+    }
+
+    /// Face value, 2 through 14 for the ace.
+    pub fn value(self) -> u8 {
+        self.index() + 2
+    }
+
+    pub fn symbol(self) -> char {
+        b"23456789TJQKA"[self.index() as usize] as char
+    }
+
+    /// Two columns wide, with tens written out, so card faces line up.
+    pub fn label(self) -> String {
+        match self {
+            Rank::Ten => "10".to_string(),
+            _ => format!(" {}", self.symbol()),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        [
+            "Two", "Three", "Four", "Five", "Six", "Seven", "Eight", "Nine", "Ten", "Jack",
+            "Queen", "King", "Ace",
+        ][self.index() as usize]
+    }
+
+    pub fn plural(self) -> &'static str {
+        [
+            "Twos", "Threes", "Fours", "Fives", "Sixes", "Sevens", "Eights", "Nines", "Tens",
+            "Jacks", "Queens", "Kings", "Aces",
+        ][self.index() as usize]
+    }
+}
+
+impl FromStr for Rank {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rank = match s.to_uppercase().as_str() {
+            "10" | "T" => Rank::Ten,
+            "J" => Rank::Jack,
+            "Q" => Rank::Queen,
+            "K" => Rank::King,
+            "A" => Rank::Ace,
+            digit => match digit.parse::<u8>() {
+                Ok(value @ 2..=9) => Rank::ALL[value as usize - 2],
+                _ => anyhow::bail!("Invalid rank '{}'", s),
+            },
+        };
+        Ok(rank)
+    }
+}
+
+/// A face-up card. Everything that reaches the UI or the hand logic goes
+/// through this type so a bare index can't be mistaken for a card.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PlayingCard {
+    pub rank: Rank,
+    pub suit: Suit,
+}
+
+impl PlayingCard {
+    pub const fn new(rank: Rank, suit: Suit) -> Self {
+        Self { rank, suit }
+    }
+
+    /// Index used by the contract (0-51).
+    pub fn index(self) -> u8 {
+        self.suit.index() * 13 + self.rank.index()
+    }
+
+    /// `None` for `FACE_DOWN` and anything else outside 0-51.
+    pub fn from_index(index: u8) -> Option<Self> {
+        if index > 51 {
+            return None;
+        }
+        Some(Self {
+            rank: Rank::ALL[(index % 13) as usize],
+            suit: Suit::ALL[(index / 13) as usize],
+        })
+    }
+}
+
+impl TryFrom<u8> for PlayingCard {
+    type Error = anyhow::Error;
+
+    fn try_from(index: u8) -> Result<Self, Self::Error> {
+        Self::from_index(index).ok_or_else(|| anyhow::anyhow!("Invalid card index {}", index))
+    }
+}
+
+impl From<PlayingCard> for u8 {
+    fn from(card: PlayingCard) -> Self {
+        card.index()
+    }
+}
+
+/// Standard notation: rank then suit, e.g. `As`, `Td` or `10h`.
+impl FromStr for PlayingCard {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let suit = s
+            .chars()
+            .last()
+            .ok_or_else(|| anyhow::anyhow!("Empty card"))?;
+        let rank = &s[..s.len() - suit.len_utf8()];
+        Ok(Self {
+            rank: rank
+                .parse()
+                .map_err(|e| anyhow::anyhow!("Invalid card '{}': {}", s, e))?,
+            suit: Suit::try_from(suit)
+                .map_err(|e| anyhow::anyhow!("Invalid card '{}': {}", s, e))?,
+        })
+    }
+}
+
+impl fmt::Display for PlayingCard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.rank.symbol(), self.suit.letter())
+    }
+}
+
+/// Serialized as the on-chain index so JSON clients see the same numbers the
+/// contract uses.
+impl Serialize for PlayingCard {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(self.index())
+    }
+}
 
 #[derive(Debug, Clone, Copy)]
 pub enum CardInfo {
-    Valid {
-        suit: &'static str,
-        value: &'static str,
-        is_red: bool,
-    },
+    Valid(PlayingCard),
     FaceDown,
     Invalid(u8),
 }
 
 pub fn card_info(card_index: u8) -> CardInfo {
-    if card_index == 255 {
+    if card_index == FACE_DOWN {
         return CardInfo::FaceDown;
     }
+    match PlayingCard::from_index(card_index) {
+        Some(card) => CardInfo::Valid(card),
+        None => CardInfo::Invalid(card_index),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CardStyle {
+    #[default]
+    Unicode,
+    Ascii,
+    FourColor,
+}
 
-    if card_index > 51 {
-        return CardInfo::Invalid(card_index);
+impl FromStr for CardStyle {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "unicode" => Ok(CardStyle::Unicode),
+            "ascii" => Ok(CardStyle::Ascii),
+            "four-color" | "fourcolor" => Ok(CardStyle::FourColor),
+            _ => anyhow::bail!(
+                "Unknown card style '{}' (expected unicode, ascii or four-color)",
+                s
+            ),
+        }
     }
+}
 
-    const SUITS: [&str; 4] = ["♠️", "♣️", "❤️", "♦️"];
-    const VALUES: [&str; 13] = [
-        " 2", " 3", " 4", " 5", " 6", " 7", " 8", " 9", "10", " J", " Q", " K", " A",
-    ];
+/// Renderer-neutral colors so the `colored` and ratatui output stay in sync.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CardColor {
+    Black,
+    Red,
+    Green,
+    Blue,
+    White,
+    Rgb(u8, u8, u8),
+}
+
+// Okabe-Ito colors, which stay distinct under the common forms of color
+// blindness.
+const VERMILLION: CardColor = CardColor::Rgb(213, 94, 0);
+const SKY_BLUE: CardColor = CardColor::Rgb(86, 180, 233);
+const YELLOW: CardColor = CardColor::Rgb(240, 228, 66);
+
+impl From<CardColor> for colored::Color {
+    fn from(color: CardColor) -> Self {
+        match color {
+            CardColor::Black => colored::Color::Black,
+            CardColor::Red => colored::Color::Red,
+            CardColor::Green => colored::Color::Green,
+            CardColor::Blue => colored::Color::Blue,
+            CardColor::White => colored::Color::White,
+            CardColor::Rgb(r, g, b) => colored::Color::TrueColor { r, g, b },
+        }
+    }
+}
 
-    let suit_index = card_index / 13;
-    let value_index = card_index % 13;
-    CardInfo::Valid {
-        suit: SUITS[suit_index as usize],
-        value: VALUES[value_index as usize],
-        is_red: suit_index == 2 || suit_index == 3,
+impl From<CardColor> for ratatui::style::Color {
+    fn from(color: CardColor) -> Self {
+        match color {
+            CardColor::Black => ratatui::style::Color::Black,
+            CardColor::Red => ratatui::style::Color::Red,
+            CardColor::Green => ratatui::style::Color::Green,
+            CardColor::Blue => ratatui::style::Color::Blue,
+            CardColor::White => ratatui::style::Color::White,
+            CardColor::Rgb(r, g, b) => ratatui::style::Color::Rgb(r, g, b),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CardFace {
+    pub text: String,
+    pub fg: CardColor,
+    pub bg: CardColor,
+    pub bold: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CardTheme {
+    pub style: CardStyle,
+    pub high_contrast: bool,
+}
+
+impl CardTheme {
+    pub fn face(&self, card: PlayingCard) -> CardFace {
+        let suit = card.suit.index() as usize;
+        let is_red = card.suit.is_red();
+
+        let text = match self.style {
+            CardStyle::Unicode => format!("{}{}", card.suit.emoji(), card.rank.label()),
+            CardStyle::FourColor => format!("{}{}", card.suit.symbol(), card.rank.label()),
+            CardStyle::Ascii => card.to_string(),
+        };
+
+        let four_color = self.style == CardStyle::FourColor;
+        let (fg, bg) = match (self.high_contrast, four_color) {
+            (false, false) => (
+                if is_red {
+                    CardColor::Red
+                } else {
+                    CardColor::Black
+                },
+                CardColor::Green,
+            ),
+            (false, true) => (
+                [
+                    CardColor::Black,
+                    CardColor::Green,
+                    CardColor::Red,
+                    CardColor::Blue,
+                ][suit],
+                CardColor::White,
+            ),
+            (true, false) => (
+                if is_red { VERMILLION } else { CardColor::White },
+                CardColor::Black,
+            ),
+            (true, true) => (
+                [CardColor::White, SKY_BLUE, VERMILLION, YELLOW][suit],
+                CardColor::Black,
+            ),
+        };
+
+        CardFace {
+            text,
+            fg,
+            bg,
+            bold: self.high_contrast,
+        }
     }
 }
 
 pub fn format_card(card_index: u8) -> ColoredString {
+    format_card_with(card_index, CardTheme::default())
+}
+
+pub fn format_card_with(card_index: u8, theme: CardTheme) -> ColoredString {
     match card_info(card_index) {
         CardInfo::FaceDown => "???".bright_black(),
         CardInfo::Invalid(idx) => format!("Incorrect card index: {}", idx).yellow(),
-        CardInfo::Valid {
-            suit,
-            value,
-            is_red,
-        } => {
-            let card_str = format!("{}{}", suit, value);
-            if is_red {
-                card_str.red().on_green()
-            } else {
-                card_str.black().on_green()
-            }
+        CardInfo::Valid(card) => {
+            let face = theme.face(card);
+            let card = face.text.color(face.fg).on_color(face.bg);
+            if face.bold { card.bold() } else { card }
         }
     }
 }
 
-pub fn compute_card_hashes_from_deck<N: Network>(deck: [Group<N>; 52]) -> HashMap<Group<N>, u8> {
+pub fn compute_card_hashes_from_deck<N: Network>(
+    deck: [Group<N>; 52],
+) -> HashMap<Group<N>, PlayingCard> {
     deck.iter()
         .enumerate()
-        .map(|(i, &hash)| (hash, i as u8))
+        .filter_map(|(i, &hash)| Some((hash, PlayingCard::from_index(i as u8)?)))
         .collect()
 }
 
 pub fn decrypt_hand_local<N: Network>(
     encrypted_hand: [Group<N>; 2],
     secret_inv: Scalar<N>,
-    card_hashes: &HashMap<Group<N>, u8>,
-) -> [u8; 2] {
+    card_hashes: &HashMap<Group<N>, PlayingCard>,
+) -> Option<[PlayingCard; 2]> {
     let decrypted_hand = [
         encrypted_hand[0] * secret_inv,
         encrypted_hand[1] * secret_inv,
     ];
-    [
-        card_hashes.get(&decrypted_hand[0]).copied().unwrap_or(255),
-        card_hashes.get(&decrypted_hand[1]).copied().unwrap_or(255),
-    ]
+    Some([
+        card_hashes.get(&decrypted_hand[0]).copied()?,
+        card_hashes.get(&decrypted_hand[1]).copied()?,
+    ])
 }
 
 pub trait CardDisplay {
-    fn display_cards(&self) -> String;
+    fn display_cards_with(&self, theme: CardTheme) -> String;
+
+    fn display_cards(&self) -> String {
+        self.display_cards_with(CardTheme::default())
+    }
 }
 
 impl<N: Network> CardDisplay for RevealedCards<N> {
-    fn display_cards(&self) -> String {
+    fn display_cards_with(&self, theme: CardTheme) -> String {
+        let format_card = |card| format_card_with(card, theme);
         format!(
             "Community: [{}, {}, {}, {}, {}]\nPlayer 1:  [{}, {}]\nPlayer 2:  [{}, {}]\nPlayer 3:  [{}, {}]",
             format_card(self.flop[0]),
@@ -130,6 +486,11 @@ pub fn get_player_cards<N: Network>(player_id: u8, cards: &Cards<N>) -> [Group<N
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashSet;
+
+    fn card(s: &str) -> PlayingCard {
+        s.parse().unwrap()
+    }
 
     #[test]
     fn test_format_card() {
@@ -156,4 +517,78 @@ mod tests {
         println!("{}", format_card(255));
         println!("{}", format_card(123));
     }
+
+    #[test]
+    fn test_ascii_style() {
+        let theme = CardTheme {
+            style: CardStyle::Ascii,
+            high_contrast: false,
+        };
+        assert_eq!(theme.face(card("As")).text, "As");
+        assert_eq!(theme.face(card("Td")).text, "Td");
+        assert_eq!(theme.face(card("2c")).text, "2c");
+        assert_eq!(format_card_with(26, theme), "2h".red().on_green());
+        assert_eq!(format_card_with(255, theme), "???".bright_black());
+    }
+
+    #[test]
+    fn test_four_color_suits_differ() {
+        for high_contrast in [false, true] {
+            let theme = CardTheme {
+                style: CardStyle::FourColor,
+                high_contrast,
+            };
+            let colors: HashSet<CardColor> = ["2s", "2c", "2h", "2d"]
+                .iter()
+                .map(|c| theme.face(card(c)).fg)
+                .collect();
+            assert_eq!(colors.len(), 4);
+            assert!(colors.iter().all(|&c| c != theme.face(card("2s")).bg));
+        }
+    }
+
+    #[test]
+    fn test_high_contrast_is_bold_on_black() {
+        let theme = CardTheme {
+            style: CardStyle::Unicode,
+            high_contrast: true,
+        };
+        let face = theme.face(card("3d"));
+        assert_eq!(face.bg, CardColor::Black);
+        assert!(face.bold);
+        assert_ne!(face.fg, theme.face(card("3s")).fg);
+    }
+
+    #[test]
+    fn test_card_style_from_str() {
+        assert_eq!("ASCII".parse::<CardStyle>().unwrap(), CardStyle::Ascii);
+        assert_eq!(
+            "four-color".parse::<CardStyle>().unwrap(),
+            CardStyle::FourColor
+        );
+        assert!("emoji".parse::<CardStyle>().is_err());
+    }
+
+    #[test]
+    fn test_index_roundtrip() {
+        for index in 0..52u8 {
+            let card = PlayingCard::try_from(index).unwrap();
+            assert_eq!(u8::from(card), index);
+            assert_eq!(card.to_string().parse::<PlayingCard>().unwrap(), card);
+        }
+        assert!(PlayingCard::try_from(FACE_DOWN).is_err());
+        assert!(PlayingCard::try_from(52).is_err());
+    }
+
+    #[test]
+    fn test_standard_notation() {
+        assert_eq!(card("As").index(), 12);
+        assert_eq!(card("2c").index(), 13);
+        assert_eq!(card("10h"), card("Th"));
+        assert_eq!(card("qD"), PlayingCard::new(Rank::Queen, Suit::Diamonds));
+        assert_eq!(card("K♣").to_string(), "Kc");
+        for bad in ["", "A", "1s", "Ax", "11h", "Asx"] {
+            assert!(bad.parse::<PlayingCard>().is_err(), "{}", bad);
+        }
+    }
 }
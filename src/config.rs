@@ -0,0 +1,334 @@
+use crate::cards::{CardStyle, CardTheme};
+use crate::game::{DEFAULT_ABANDON_AFTER, DEFAULT_ENDPOINT};
+use crate::game_state::NetworkType;
+use crate::keystore::{Keystore, read_passphrase};
+use leo_bindings::leo_bindings_sdk::Account;
+use serde::Deserialize;
+use snarkvm::prelude::TestnetV0;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+pub const CONFIG_FILE: &str = "poker.toml";
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    pub default_profile: Option<String>,
+    #[serde(default)]
+    pub profiles: BTreeMap<String, ProfileFile>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProfileFile {
+    pub network: Option<String>,
+    pub endpoint: Option<String>,
+    pub account: Option<String>,
+    pub poll_interval_ms: Option<u64>,
+    pub balance_reserve: Option<f64>,
+    pub abandon_after_minutes: Option<u64>,
+    #[serde(default)]
+    pub ui: UiFile,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UiFile {
+    pub log_height: Option<u16>,
+    pub buy_in: Option<f64>,
+    pub blind_frequency: Option<u8>,
+    pub card_style: Option<CardStyle>,
+    pub high_contrast: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Profile {
+    pub name: String,
+    pub network: NetworkType,
+    pub endpoint: String,
+    pub account: Option<AccountRef>,
+    pub poll_interval_ms: u64,
+    /// Microcredits the balance must hold on top of the buy-in, so the
+    /// game's transactions can pay their fees. The fees themselves are
+    /// chosen by the bindings.
+    pub balance_reserve: u64,
+    /// How long a new game may wait for players before it is shown as
+    /// abandoned and polled only now and then.
+    pub abandon_after: Duration,
+    pub ui: UiSettings,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UiSettings {
+    pub log_height: u16,
+    pub buy_in: f64,
+    pub blind_frequency: u8,
+    pub cards: CardTheme,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccountRef {
+    Dev(u16),
+    Env(String),
+    Keystore(String),
+    PrivateKey(String),
+}
+
+impl AccountRef {
+    pub fn resolve(&self) -> anyhow::Result<Account<TestnetV0>> {
+        match self {
+            AccountRef::Dev(index) => Account::dev_account(*index),
+            AccountRef::Env(var) => {
+                let private_key = std::env::var(var)
+                    .map_err(|_| anyhow::anyhow!("Environment variable {} is not set", var))?;
+                Account::from_str(&private_key)
+                    .map_err(|e| anyhow::anyhow!("Invalid private key in {}: {}", var, e))
+            }
+            AccountRef::Keystore(name) => {
+                let keystore = Keystore::open_default()?;
+                let entry = keystore
+                    .get(name)
+                    .ok_or_else(|| anyhow::anyhow!("No account named '{}' in keystore", name))?;
+                let passphrase = read_passphrase(&format!("Passphrase for '{}': ", name))?;
+                entry.account(&passphrase)
+            }
+            AccountRef::PrivateKey(private_key) => Account::from_str(private_key),
+        }
+    }
+}
+
+impl FromStr for AccountRef {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(index) = s.strip_prefix("dev:") {
+            let index = index
+                .parse::<u16>()
+                .map_err(|_| anyhow::anyhow!("Invalid dev account index '{}'", index))?;
+            Ok(AccountRef::Dev(index))
+        } else if let Some(var) = s.strip_prefix("env:") {
+            Ok(AccountRef::Env(var.to_string()))
+        } else if let Some(name) = s.strip_prefix("keystore:") {
+            Ok(AccountRef::Keystore(name.to_string()))
+        } else if s.starts_with("APrivateKey1") {
+            Ok(AccountRef::PrivateKey(s.to_string()))
+        } else {
+            anyhow::bail!(
+                "Invalid account '{}'. Use 'dev:<index>', 'env:<VAR>', 'keystore:<name>' or a private key",
+                s
+            )
+        }
+    }
+}
+
+impl Profile {
+    pub fn builtin(network: NetworkType) -> Self {
+        Self {
+            name: network.name().to_lowercase(),
+            network,
+            endpoint: DEFAULT_ENDPOINT.to_string(),
+            account: None,
+            poll_interval_ms: network.poll_interval_ms(),
+            balance_reserve: 0,
+            abandon_after: DEFAULT_ABANDON_AFTER,
+            ui: UiSettings {
+                log_height: 8,
+                buy_in: 100.0,
+                blind_frequency: 3,
+                cards: CardTheme::default(),
+            },
+        }
+    }
+
+    fn merge(mut self, file: &ProfileFile) -> anyhow::Result<Self> {
+        if let Some(endpoint) = &file.endpoint {
+            self.endpoint = endpoint.clone();
+        }
+        if let Some(account) = &file.account {
+            self.account = Some(account.parse()?);
+        }
+        if let Some(interval) = file.poll_interval_ms {
+            self.poll_interval_ms = interval;
+        }
+        if let Some(reserve) = file.balance_reserve {
+            self.balance_reserve = (reserve.max(0.0) * 1_000_000.0).round() as u64;
+        }
+        if let Some(minutes) = file.abandon_after_minutes {
+            self.abandon_after = Duration::from_secs(minutes * 60);
+        }
+        if let Some(log_height) = file.ui.log_height {
+            self.ui.log_height = log_height;
+        }
+        if let Some(buy_in) = file.ui.buy_in {
+            self.ui.buy_in = buy_in;
+        }
+        if let Some(blind_frequency) = file.ui.blind_frequency {
+            self.ui.blind_frequency = blind_frequency;
+        }
+        if let Some(card_style) = file.ui.card_style {
+            self.ui.cards.style = card_style;
+        }
+        if let Some(high_contrast) = file.ui.high_contrast {
+            self.ui.cards.high_contrast = high_contrast;
+        }
+        Ok(self)
+    }
+
+    pub fn apply_env(&mut self) -> anyhow::Result<()> {
+        if let Ok(network) = std::env::var("NETWORK") {
+            self.network = network.parse()?;
+        }
+        if let Ok(endpoint) = std::env::var("ENDPOINT") {
+            self.endpoint = endpoint;
+        }
+        if let Ok(private_key) = std::env::var("PRIVATE_KEY") {
+            self.account = Some(AccountRef::PrivateKey(private_key));
+        }
+        if let Ok(interval) = std::env::var("POLL_INTERVAL_MS") {
+            self.poll_interval_ms = interval
+                .parse()
+                .map_err(|_| anyhow::anyhow!("Invalid POLL_INTERVAL_MS value '{}'", interval))?;
+        }
+        Ok(())
+    }
+}
+
+impl Config {
+    pub fn path() -> PathBuf {
+        std::env::var("POKER_CONFIG")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| PathBuf::from(CONFIG_FILE))
+    }
+
+    pub fn load() -> anyhow::Result<Self> {
+        let path = Self::path();
+        if path.exists() {
+            Self::from_file(&path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn from_file(path: &Path) -> anyhow::Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?;
+        toml::from_str(&contents)
+            .map_err(|e| anyhow::anyhow!("Failed to parse {}: {}", path.display(), e))
+    }
+
+    pub fn profile_from_file(&self, name: &str) -> anyhow::Result<Profile> {
+        let file = self.profiles.get(name);
+        let network = match file.and_then(|f| f.network.as_deref()) {
+            Some(network) => network.parse()?,
+            None => name.parse().map_err(|_| {
+                anyhow::anyhow!(
+                    "Profile '{}' must set network to 'local', 'testnet' or 'mainnet'",
+                    name
+                )
+            })?,
+        };
+        let mut profile = Profile::builtin(network);
+        profile.name = name.to_string();
+        match file {
+            Some(file) => profile.merge(file),
+            None => Ok(profile),
+        }
+    }
+
+    /// Resolves a profile by name, falling back to `default_profile` and then
+    /// `fallback`, with environment variables taking precedence over the file.
+    pub fn profile(&self, name: Option<&str>, fallback: &str) -> anyhow::Result<Profile> {
+        let name = name.or(self.default_profile.as_deref()).unwrap_or(fallback);
+        let mut profile = self.profile_from_file(name)?;
+        profile.apply_env()?;
+        Ok(profile)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = r#"
+default_profile = "testnet"
+
+[profiles.testnet]
+endpoint = "https://api.example.com/v1"
+account = "env:MY_KEY"
+poll_interval_ms = 2500
+balance_reserve = 0.5
+abandon_after_minutes = 120
+
+[profiles.testnet.ui]
+log_height = 12
+buy_in = 25.0
+card_style = "four-color"
+high_contrast = true
+
+[profiles.friends]
+network = "mainnet"
+account = "dev:1"
+"#;
+
+    #[test]
+    fn test_builtin_profiles() {
+        let config = Config::default();
+        let local = config.profile_from_file("local").unwrap();
+        assert_eq!(local.network, NetworkType::Local);
+        assert_eq!(local.endpoint, DEFAULT_ENDPOINT);
+        assert_eq!(
+            local.poll_interval_ms,
+            NetworkType::Local.poll_interval_ms()
+        );
+        assert!(config.profile_from_file("mainnet").is_ok());
+        assert!(config.profile_from_file("unknown").is_err());
+    }
+
+    #[test]
+    fn test_file_overrides_defaults() {
+        let config: Config = toml::from_str(EXAMPLE).unwrap();
+        assert_eq!(config.default_profile.as_deref(), Some("testnet"));
+
+        let testnet = config.profile_from_file("testnet").unwrap();
+        assert_eq!(testnet.network, NetworkType::Testnet);
+        assert_eq!(testnet.endpoint, "https://api.example.com/v1");
+        assert_eq!(testnet.account, Some(AccountRef::Env("MY_KEY".to_string())));
+        assert_eq!(testnet.poll_interval_ms, 2500);
+        assert_eq!(testnet.balance_reserve, 500_000);
+        assert_eq!(testnet.abandon_after, Duration::from_secs(2 * 60 * 60));
+        assert_eq!(testnet.ui.log_height, 12);
+        assert_eq!(testnet.ui.buy_in, 25.0);
+        assert_eq!(testnet.ui.blind_frequency, 3);
+        assert_eq!(testnet.ui.cards.style, CardStyle::FourColor);
+        assert!(testnet.ui.cards.high_contrast);
+
+        let friends = config.profile_from_file("friends").unwrap();
+        assert_eq!(friends.name, "friends");
+        assert_eq!(friends.network, NetworkType::Mainnet);
+        assert_eq!(friends.account, Some(AccountRef::Dev(1)));
+        assert_eq!(friends.ui.cards, CardTheme::default());
+        assert_eq!(friends.abandon_after, DEFAULT_ABANDON_AFTER);
+    }
+
+    #[test]
+    fn test_unknown_keys_rejected() {
+        assert!(toml::from_str::<Config>("[profiles.local]\nendpont = \"x\"").is_err());
+    }
+
+    #[test]
+    fn test_account_ref_parse() {
+        assert_eq!("dev:2".parse::<AccountRef>().unwrap(), AccountRef::Dev(2));
+        assert_eq!(
+            "env:PRIVATE_KEY_P1".parse::<AccountRef>().unwrap(),
+            AccountRef::Env("PRIVATE_KEY_P1".to_string())
+        );
+        assert_eq!(
+            "keystore:alice".parse::<AccountRef>().unwrap(),
+            AccountRef::Keystore("alice".to_string())
+        );
+        assert!("dev:x".parse::<AccountRef>().is_err());
+        assert!("hunter2".parse::<AccountRef>().is_err());
+    }
+}
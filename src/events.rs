@@ -0,0 +1,475 @@
+use crate::cards::PlayingCard;
+use crate::game::{Card, Chip};
+use serde::Serialize;
+use std::fmt;
+use std::sync::mpsc::{Receiver, Sender, channel};
+
+/// Set by `claim_prize` once the winner has been paid.
+pub const CLAIMED_STATE: u8 = 255;
+
+/// The parts of the `games`, `chips` and `revealed_cards` mappings that events
+/// are derived from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameSnapshot {
+    pub game_id: u32,
+    pub state: u8,
+    pub buy_in: u64,
+    pub players_out: u8,
+    pub players_folded: u8,
+    pub hands_played: u8,
+    pub sb: u16,
+    pub bb: u16,
+    pub chips: Option<Chip>,
+    pub revealed: Card,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Reveal {
+    Hand(u8),
+    Flop,
+    Turn,
+    River,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "type")]
+pub enum GameEvent {
+    StateChanged {
+        from: Option<u8>,
+        to: u8,
+    },
+    BlindsPosted {
+        small_blind: u8,
+        small_amount: u16,
+        big_blind: u8,
+        big_amount: u16,
+    },
+    PlayerBet {
+        player: u8,
+        amount: u16,
+        total: u16,
+    },
+    PlayerFolded {
+        player: u8,
+    },
+    CardsRevealed {
+        reveal: Reveal,
+        cards: Vec<PlayingCard>,
+    },
+    HandWon {
+        player: u8,
+        amount: u16,
+    },
+    PlayerEliminated {
+        player: u8,
+    },
+    PrizeClaimed {
+        player: u8,
+        amount: u64,
+    },
+}
+
+impl fmt::Display for GameEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameEvent::StateChanged {
+                from: Some(from),
+                to,
+            } => {
+                write!(f, "State {} -> {}", from, to)
+            }
+            GameEvent::StateChanged { from: None, to } => write!(f, "State {}", to),
+            GameEvent::BlindsPosted {
+                small_blind,
+                small_amount,
+                big_blind,
+                big_amount,
+            } => write!(
+                f,
+                "Player {} posts small blind {}, Player {} posts big blind {}",
+                small_blind, small_amount, big_blind, big_amount
+            ),
+            GameEvent::PlayerBet {
+                player, amount: 0, ..
+            } => write!(f, "Player {} checks", player),
+            GameEvent::PlayerBet {
+                player,
+                amount,
+                total,
+            } => write!(f, "Player {} bets {} (total {})", player, amount, total),
+            GameEvent::PlayerFolded { player } => write!(f, "Player {} folds", player),
+            GameEvent::CardsRevealed { reveal, cards } => {
+                let cards: Vec<String> = cards.iter().map(|c| c.to_string()).collect();
+                match reveal {
+                    Reveal::Hand(player) => {
+                        write!(f, "Player {} shows {}", player, cards.join(" "))
+                    }
+                    Reveal::Flop => write!(f, "Flop: {}", cards.join(" ")),
+                    Reveal::Turn => write!(f, "Turn: {}", cards.join(" ")),
+                    Reveal::River => write!(f, "River: {}", cards.join(" ")),
+                }
+            }
+            GameEvent::HandWon { player, amount } => {
+                write!(f, "Player {} wins {} chips", player, amount)
+            }
+            GameEvent::PlayerEliminated { player } => {
+                write!(f, "Player {} is eliminated", player)
+            }
+            GameEvent::PrizeClaimed { player, amount } => write!(
+                f,
+                "Player {} claimed the prize of {} credits",
+                player,
+                crate::game::Credits::from(*amount)
+            ),
+        }
+    }
+}
+
+fn bit(player: u8) -> u8 {
+    1u8 << (player - 1)
+}
+
+fn is_dec_hand(state: u8) -> bool {
+    (2..=4).contains(&state)
+}
+
+fn betting_player(state: u8) -> Option<u8> {
+    match state {
+        5..=7 | 11..=13 | 17..=19 | 23..=25 => Some((state - 5) % 6 + 1),
+        _ => None,
+    }
+}
+
+fn stack(chips: &Chip, player: u8) -> u16 {
+    chips.get_chips(player)
+}
+
+fn bet(chips: &Chip, player: u8) -> u16 {
+    chips.get_current_bet(player)
+}
+
+/// Derives the events that explain the move from `prev` to `next`. The first
+/// observation of a game only yields a `StateChanged` event.
+pub fn diff(prev: Option<&GameSnapshot>, next: &GameSnapshot) -> Vec<GameEvent> {
+    let mut events = Vec::new();
+
+    let Some(prev) = prev else {
+        events.push(GameEvent::StateChanged {
+            from: None,
+            to: next.state,
+        });
+        return events;
+    };
+
+    if prev.state != next.state {
+        events.push(GameEvent::StateChanged {
+            from: Some(prev.state),
+            to: next.state,
+        });
+    }
+
+    let hand_finished = next.hands_played != prev.hands_played;
+    let blinds_posted = is_dec_hand(next.state) && !is_dec_hand(prev.state);
+
+    for player in 1..=3 {
+        let newly_folded = next.players_folded & !prev.players_folded & !next.players_out;
+        if newly_folded & bit(player) != 0 && !hand_finished {
+            events.push(GameEvent::PlayerFolded { player });
+        }
+    }
+
+    if let (Some(prev_chips), Some(next_chips)) = (&prev.chips, &next.chips) {
+        if blinds_posted {
+            let mut posted: Vec<(u8, u16)> = (1..=3)
+                .map(|p| (p, bet(next_chips, p)))
+                .filter(|&(_, amount)| amount > 0)
+                .collect();
+            posted.sort_by_key(|&(_, amount)| amount);
+            if let (Some(&(small_blind, small_amount)), Some(&(big_blind, big_amount))) =
+                (posted.first(), posted.last())
+                && small_blind != big_blind
+            {
+                events.push(GameEvent::BlindsPosted {
+                    small_blind,
+                    small_amount,
+                    big_blind,
+                    big_amount,
+                });
+            }
+        } else if !hand_finished {
+            let acting = betting_player(prev.state).filter(|_| prev.state != next.state);
+            for player in 1..=3 {
+                let before = bet(prev_chips, player);
+                let after = bet(next_chips, player);
+                let folded = next.players_folded & bit(player) != 0;
+                if after > before || (acting == Some(player) && !folded) {
+                    events.push(GameEvent::PlayerBet {
+                        player,
+                        amount: after.saturating_sub(before),
+                        total: after,
+                    });
+                }
+            }
+        }
+    }
+
+    let reveals = [
+        (
+            Reveal::Hand(1),
+            &prev.revealed.player1[..],
+            &next.revealed.player1[..],
+        ),
+        (
+            Reveal::Hand(2),
+            &prev.revealed.player2[..],
+            &next.revealed.player2[..],
+        ),
+        (
+            Reveal::Hand(3),
+            &prev.revealed.player3[..],
+            &next.revealed.player3[..],
+        ),
+        (
+            Reveal::Flop,
+            &prev.revealed.flop[..],
+            &next.revealed.flop[..],
+        ),
+        (
+            Reveal::Turn,
+            std::slice::from_ref(&prev.revealed.turn),
+            std::slice::from_ref(&next.revealed.turn),
+        ),
+        (
+            Reveal::River,
+            std::slice::from_ref(&prev.revealed.river),
+            std::slice::from_ref(&next.revealed.river),
+        ),
+    ];
+    for (reveal, before, after) in reveals {
+        if before != after
+            && let Some(cards) = after.iter().copied().collect::<Option<Vec<_>>>()
+        {
+            events.push(GameEvent::CardsRevealed { reveal, cards });
+        }
+    }
+
+    if hand_finished && let (Some(prev_chips), Some(next_chips)) = (&prev.chips, &next.chips) {
+        for player in 1..=3 {
+            let after = stack(next_chips, player) + bet(next_chips, player);
+            let amount = after.saturating_sub(stack(prev_chips, player));
+            if amount > 0 {
+                events.push(GameEvent::HandWon { player, amount });
+            }
+        }
+    }
+
+    if next.state != CLAIMED_STATE {
+        for player in 1..=3 {
+            if next.players_out & !prev.players_out & bit(player) != 0 {
+                events.push(GameEvent::PlayerEliminated { player });
+            }
+        }
+    }
+
+    if next.state == CLAIMED_STATE && prev.state != CLAIMED_STATE {
+        let player = match prev.state {
+            34..=36 => Some(prev.state - 33),
+            _ => (1..=3).find(|&p| prev.players_out & bit(p) == 0),
+        };
+        if let Some(player) = player {
+            events.push(GameEvent::PrizeClaimed {
+                player,
+                amount: next.buy_in * 3,
+            });
+        }
+    }
+
+    events
+}
+
+/// Fans game events out to any number of subscribers. Subscribers that drop
+/// their receiver are removed on the next publish.
+#[derive(Default)]
+pub struct EventBus {
+    subscribers: Vec<Sender<GameEvent>>,
+}
+
+impl EventBus {
+    pub fn subscribe(&mut self) -> Receiver<GameEvent> {
+        let (sender, receiver) = channel();
+        self.subscribers.push(sender);
+        receiver
+    }
+
+    pub fn publish(&mut self, event: &GameEvent) {
+        self.subscribers
+            .retain(|subscriber| subscriber.send(event.clone()).is_ok());
+    }
+}
+
+/// A snapshot of game 1 at blinds 5/10 with nothing revealed, for tests.
+#[cfg(test)]
+pub(crate) fn snapshot(
+    state: u8,
+    hands_played: u8,
+    stacks: [u16; 3],
+    bets: [u16; 3],
+    folded: u8,
+) -> GameSnapshot {
+    GameSnapshot {
+        game_id: 1,
+        state,
+        buy_in: 1_000_000,
+        players_out: 0,
+        players_folded: folded,
+        hands_played,
+        sb: 5,
+        bb: 10,
+        chips: Some(Chip {
+            player1: stacks[0],
+            player2: stacks[1],
+            player3: stacks[2],
+            player1_bet: bets[0],
+            player2_bet: bets[1],
+            player3_bet: bets[2],
+            pot: bets.iter().sum(),
+        }),
+        revealed: Card::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_observation() {
+        let next = snapshot(0, 0, [1000, 0, 0], [0, 0, 0], 0);
+        assert_eq!(
+            diff(None, &next),
+            vec![GameEvent::StateChanged { from: None, to: 0 }]
+        );
+    }
+
+    #[test]
+    fn test_blinds_posted_on_deal() {
+        let prev = snapshot(1, 0, [1000, 1000, 0], [0, 0, 0], 0);
+        let next = snapshot(2, 0, [995, 990, 1000], [5, 10, 0], 0);
+        let events = diff(Some(&prev), &next);
+        assert!(events.contains(&GameEvent::BlindsPosted {
+            small_blind: 1,
+            small_amount: 5,
+            big_blind: 2,
+            big_amount: 10,
+        }));
+        assert!(
+            !events
+                .iter()
+                .any(|e| matches!(e, GameEvent::PlayerBet { .. }))
+        );
+    }
+
+    #[test]
+    fn test_bet_call_and_check() {
+        let prev = snapshot(7, 0, [995, 990, 1000], [5, 10, 0], 0);
+        let next = snapshot(5, 0, [995, 990, 990], [5, 10, 10], 0);
+        assert!(diff(Some(&prev), &next).contains(&GameEvent::PlayerBet {
+            player: 3,
+            amount: 10,
+            total: 10,
+        }));
+
+        let prev = snapshot(6, 0, [990, 990, 990], [10, 10, 10], 0);
+        let next = snapshot(8, 0, [990, 990, 990], [10, 10, 10], 0);
+        assert!(diff(Some(&prev), &next).contains(&GameEvent::PlayerBet {
+            player: 2,
+            amount: 0,
+            total: 10,
+        }));
+    }
+
+    #[test]
+    fn test_fold() {
+        let prev = snapshot(7, 0, [995, 990, 1000], [5, 10, 0], 0);
+        let mut next = snapshot(5, 0, [995, 990, 1000], [5, 10, 0], 0);
+        next.players_folded = 4;
+        let events = diff(Some(&prev), &next);
+        assert!(events.contains(&GameEvent::PlayerFolded { player: 3 }));
+        assert!(
+            !events
+                .iter()
+                .any(|e| matches!(e, GameEvent::PlayerBet { .. }))
+        );
+    }
+
+    #[test]
+    fn test_cards_revealed() {
+        let prev = snapshot(10, 0, [990, 990, 990], [10, 10, 10], 0);
+        let mut next = prev;
+        next.state = 11;
+        let flop: Vec<PlayingCard> = ["2s", "2c", "2h"]
+            .iter()
+            .map(|c| c.parse().unwrap())
+            .collect();
+        next.revealed.flop = [Some(flop[0]), Some(flop[1]), Some(flop[2])];
+        let revealed = GameEvent::CardsRevealed {
+            reveal: Reveal::Flop,
+            cards: flop,
+        };
+        assert!(diff(Some(&prev), &next).contains(&revealed));
+        assert_eq!(revealed.to_string(), "Flop: 2s 2c 2h");
+    }
+
+    #[test]
+    fn test_hand_won_and_elimination() {
+        let mut prev = snapshot(29, 0, [0, 800, 960], [200, 200, 40], 0);
+        prev.hands_played = 2;
+        let mut next = snapshot(30, 0, [0, 1240, 960], [0, 0, 0], 0);
+        next.hands_played = 3;
+        next.players_out = 1;
+        next.players_folded = 1;
+        let events = diff(Some(&prev), &next);
+        assert!(events.contains(&GameEvent::HandWon {
+            player: 2,
+            amount: 440,
+        }));
+        assert!(events.contains(&GameEvent::PlayerEliminated { player: 1 }));
+        assert!(
+            !events
+                .iter()
+                .any(|e| matches!(e, GameEvent::PlayerFolded { .. }))
+        );
+    }
+
+    #[test]
+    fn test_prize_claimed() {
+        let mut prev = snapshot(35, 0, [0, 3000, 0], [0, 0, 0], 0);
+        prev.players_out = 5;
+        let mut next = prev;
+        next.state = 255;
+        next.players_out = 7;
+        let events = diff(Some(&prev), &next);
+        assert!(events.contains(&GameEvent::PrizeClaimed {
+            player: 2,
+            amount: 3_000_000,
+        }));
+        assert!(
+            !events
+                .iter()
+                .any(|e| matches!(e, GameEvent::PlayerEliminated { .. }))
+        );
+    }
+
+    #[test]
+    fn test_bus_drops_closed_subscribers() {
+        let mut bus = EventBus::default();
+        let kept = bus.subscribe();
+        drop(bus.subscribe());
+        bus.publish(&GameEvent::PlayerFolded { player: 1 });
+        assert_eq!(bus.subscribers.len(), 1);
+        assert_eq!(
+            kept.try_recv().unwrap(),
+            GameEvent::PlayerFolded { player: 1 }
+        );
+    }
+}
@@ -9,24 +9,47 @@ use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph, Widget},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Widget},
 };
+use serde::Serialize;
 use snarkvm::prelude::{Group, Inverse, Network, Scalar, TestRng, TestnetV0, Uniform};
 use std::collections::HashMap;
 use std::str::FromStr;
+use std::sync::{Arc, Mutex};
 use std::thread::{self, JoinHandle};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use crate::cards::{
-    CardInfo, card_info, decrypt_hand_local, get_opponents, get_other_players_cards,
+    CardTheme, FACE_DOWN, PlayingCard, decrypt_hand_local, get_opponents, get_other_players_cards,
     get_player_cards,
 };
+use crate::config::Profile;
+use crate::events::{EventBus, GameEvent, GameSnapshot};
 use crate::game_state::{
-    CreateGameField, GameModel, JoinGameField, MenuOption, NetworkType, Screen, describe_game_state,
+    BetPreset, CreateGameField, GameModel, GameOver, JoinGameField, MenuOption, NetworkType,
+    PreAction, QueuedPreAction, Screen, describe_game_state,
+};
+use crate::hand::{best_hand, hand_label};
+use crate::history::ActionHistory;
+use crate::invite::InviteCode;
+use crate::leaderboard::{GameRecord, GameSource};
+use crate::logs::{LogFilter, LogLevel};
+use crate::poll::{ReadCache, latest_height};
+use crate::pots::{Pot, active_players, side_pots};
+use crate::showdown::{HandResult, hand_result};
+use crate::stats::{AccountStats, StatsDb};
+use crate::telemetry::{Transition, TransitionTimes, format_duration, progress, timed};
+use crate::tx::{
+    self, Chain, Position, Sent, Submitted, TxHistory, TxRecord, TxStatus, confirmed_status,
+    export_path, inclusion_height,
 };
 
 pub const DEFAULT_ENDPOINT: &str = "http://localhost:3030";
 
+/// How long a game may wait for players before this client stops expecting
+/// them, unless the profile sets `abandon_after_minutes`.
+pub const DEFAULT_ABANDON_AFTER: Duration = Duration::from_secs(30 * 60);
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Credits(pub u64);
 
@@ -105,6 +128,12 @@ pub enum GameState {
     P1Claim = 34,
     P2Claim = 35,
     P3Claim = 36,
+
+    /// Client-side only: nobody joined within the profile's `abandon_after`.
+    /// The game is still polled, slowly, and play resumes if someone joins.
+    Abandoned = 254,
+    /// Set by `claim_prize` once the winner has been paid.
+    Completed = 255,
 }
 
 impl std::fmt::Display for GameState {
@@ -153,6 +182,7 @@ impl GameState {
             34 => Some(Self::P1Claim),
             35 => Some(Self::P2Claim),
             36 => Some(Self::P3Claim),
+            255 => Some(Self::Completed),
             _ => None,
         }
     }
@@ -161,6 +191,11 @@ impl GameState {
         self as u8
     }
 
+    /// Nothing more happens in the game, so there is nothing to poll for.
+    pub fn is_terminal(self) -> bool {
+        matches!(self, Self::Abandoned | Self::Completed)
+    }
+
     pub fn is_betting_state(self) -> bool {
         matches!(
             self,
@@ -220,7 +255,7 @@ impl GameState {
             | Self::P3Shuffle
             | Self::P3Claim => Some(3),
 
-            Self::Compare => None,
+            Self::Compare | Self::Abandoned | Self::Completed => None,
         }
     }
 }
@@ -246,6 +281,17 @@ impl DecryptionStep {
     }
 }
 
+/// The mapping reads one poll of a game makes.
+#[derive(Clone)]
+struct Reads<N: Network> {
+    game: mental_poker_bindings::mental_poker::Game<N>,
+    chips: Option<Chip>,
+    cards: Option<Cards<N>>,
+    revealed: Option<RevealedCards<N>>,
+}
+
+const API_TIMEOUT: Duration = Duration::from_secs(2);
+
 pub struct PokerGame<N: Network, M: VMManager<N> + Clone> {
     pub account: Account<N>,
     pub secret: Scalar<N>,
@@ -254,7 +300,12 @@ pub struct PokerGame<N: Network, M: VMManager<N> + Clone> {
     pub credits: CreditsAleo<N, M>,
     pub player_id: u8,
     pub keys: Option<Keys<N>>,
-    pub card_hashes: HashMap<Group<N>, u8>,
+    pub card_hashes: HashMap<Group<N>, PlayingCard>,
+    /// The node's REST API for the game's network. Without it reads are not
+    /// reused between polls and transactions count as accepted once the
+    /// call returns.
+    api: Option<String>,
+    reads: Arc<Mutex<ReadCache<Reads<N>>>>,
 }
 
 impl<N: Network, M: VMManager<N> + 'static> PokerGame<N, M> {
@@ -281,9 +332,100 @@ impl<N: Network, M: VMManager<N> + 'static> PokerGame<N, M> {
             player_id,
             keys: None,
             card_hashes,
+            api: None,
+            reads: Arc::default(),
+        })
+    }
+
+    /// Reuses a game's mapping reads until `endpoint` reports a new block,
+    /// and confirms transactions there.
+    pub fn with_block_height(mut self, endpoint: &str, network: NetworkType) -> Self {
+        self.api = Some(network.api(endpoint));
+        self
+    }
+
+    fn latest_height(&self) -> Option<u32> {
+        let api = self.api.as_ref()?;
+        latest_height(api, API_TIMEOUT)
+            .map_err(|e| log::debug!("Block height unavailable: {}", e))
+            .ok()
+    }
+
+    /// The game's mappings as of the latest block. Nothing is read again
+    /// while the height stays the same, and chips and cards are kept while
+    /// the game record is unchanged, since every transition updates it.
+    fn reads(&self, game_id: u32) -> anyhow::Result<Reads<N>> {
+        let height = self.latest_height();
+        let mut cache = self.reads.lock().unwrap();
+        if let Some(reads) = cache.at_height(game_id, height) {
+            return Ok(reads);
+        }
+        let game = self
+            .poker
+            .get_games(game_id)
+            .ok_or_else(|| anyhow::anyhow!("Game {} not found", game_id))?;
+        let reads = match cache.last(game_id) {
+            Some(last) if last.game == game => last.clone(),
+            _ => Reads {
+                game,
+                chips: self.get_chip(game_id),
+                cards: self.poker.get_cards(game_id),
+                revealed: self.poker.get_revealed_cards(game_id),
+            },
+        };
+        cache.store(game_id, height, reads.clone());
+        Ok(reads)
+    }
+
+    /// Whether this account is seated, and the state and hand it sees.
+    /// `None` while the game cannot be read.
+    fn position(&self, game_id: u32) -> Option<Position> {
+        let game = self.poker.get_games(game_id)?;
+        let address = self.account.address();
+        Some(Position {
+            seated: [game.player1, game.player2, game.player3].contains(&address),
+            state: game.state,
+            hands_played: game.hands_played,
         })
     }
 
+    /// Proves and submits `transition` on `game_id`, following it in
+    /// `model.transactions` as `tx::submit` does. `inputs` goes into the
+    /// transaction history, so it never holds secrets, passwords or cards.
+    fn submit<T: Submitted<N>>(
+        &self,
+        model: &mut GameModel,
+        game_id: u32,
+        transition: Transition,
+        inputs: String,
+        mut call: impl FnMut() -> anyhow::Result<T>,
+    ) -> anyhow::Result<T> {
+        let chain = OnChain {
+            game: self,
+            game_id,
+        };
+        let samples = &mut model.transition_samples;
+        tx::submit(
+            &chain,
+            &mut model.transactions,
+            TxRecord::new(transition, game_id, inputs),
+            || {
+                let value = timed(samples, transition, &mut call)?;
+                let transaction = value.transaction();
+                Ok(Sent {
+                    id: transaction.id().to_string(),
+                    fee: transaction.fee_amount().ok().map(|fee| *fee),
+                    value,
+                })
+            },
+        )
+    }
+
+    fn shuffle(&self, deck: [Group<N>; 52]) -> [bool; 249] {
+        let (_, control_bits) = waksman_ctrl::shuffle_deck(deck);
+        control_bits
+    }
+
     fn set_player_id(&mut self, game_id: u32) -> anyhow::Result<()> {
         let game = self
             .poker
@@ -311,54 +453,83 @@ impl<N: Network, M: VMManager<N> + 'static> PokerGame<N, M> {
     ) -> anyhow::Result<()> {
         model.log_action_start(step.log_message().to_string());
 
+        // Keep the current keys until the step lands, so a failed step can
+        // be proven again.
+        let keys = self.keys.as_ref().unwrap();
         let new_keys = match step {
             DecryptionStep::Hands => {
                 let (other1, other2) = get_other_players_cards(self.player_id, cards);
-                self.poker
-                    .decrypt_hands(
-                        &self.account,
-                        game_id,
-                        other1,
-                        other2,
-                        self.keys.take().unwrap(),
-                    )?
-                    .0
+                self.submit(
+                    model,
+                    game_id,
+                    Transition::DecryptHands,
+                    String::new(),
+                    || {
+                        self.poker.decrypt_hands(
+                            &self.account,
+                            game_id,
+                            other1,
+                            other2,
+                            keys.clone(),
+                        )
+                    },
+                )?
+                .0
             }
             DecryptionStep::Flop => {
-                self.poker
-                    .decrypt_flop(
-                        &self.account,
-                        game_id,
-                        cards.flop,
-                        self.keys.take().unwrap(),
-                    )?
-                    .0
+                self.submit(
+                    model,
+                    game_id,
+                    Transition::DecryptFlop,
+                    String::new(),
+                    || {
+                        self.poker
+                            .decrypt_flop(&self.account, game_id, cards.flop, keys.clone())
+                    },
+                )?
+                .0
             }
             DecryptionStep::Turn => {
-                self.poker
-                    .decrypt_turn_river(
-                        &self.account,
-                        game_id,
-                        cards.turn,
-                        self.keys.take().unwrap(),
-                    )?
-                    .0
+                self.submit(
+                    model,
+                    game_id,
+                    Transition::DecryptTurnRiver,
+                    "turn".to_string(),
+                    || {
+                        self.poker.decrypt_turn_river(
+                            &self.account,
+                            game_id,
+                            cards.turn,
+                            keys.clone(),
+                        )
+                    },
+                )?
+                .0
             }
             DecryptionStep::River => {
-                self.poker
-                    .decrypt_turn_river(
-                        &self.account,
-                        game_id,
-                        cards.river,
-                        self.keys.take().unwrap(),
-                    )?
-                    .0
+                self.submit(
+                    model,
+                    game_id,
+                    Transition::DecryptTurnRiver,
+                    "river".to_string(),
+                    || {
+                        self.poker.decrypt_turn_river(
+                            &self.account,
+                            game_id,
+                            cards.river,
+                            keys.clone(),
+                        )
+                    },
+                )?
+                .0
             }
             DecryptionStep::Showdown => {
                 let own_cards = get_player_cards(self.player_id, cards);
-                self.poker
-                    .showdown(&self.account, game_id, own_cards, self.keys.take().unwrap())?
-                    .0
+                self.submit(model, game_id, Transition::Showdown, String::new(), || {
+                    self.poker
+                        .showdown(&self.account, game_id, own_cards, keys.clone())
+                })?
+                .0
             }
         };
 
@@ -410,11 +581,23 @@ impl<N: Network, M: VMManager<N> + 'static> PokerGame<N, M> {
         let call_amount = highest_bet.saturating_sub(current_bet);
         let min_raise = (highest_bet + min_raise_size).saturating_sub(current_bet);
 
-        model.betting_ui = Some(crate::game_state::BettingUIState::new(
-            player_chips as u64,
-            call_amount as u64,
-            min_raise as u64,
-        ));
+        let preflop = matches!(
+            state,
+            GameState::P1BetPre | GameState::P2BetPre | GameState::P3BetPre
+        );
+        model.betting_ui = Some(
+            crate::game_state::BettingUIState::new(
+                player_chips as u64,
+                call_amount as u64,
+                min_raise as u64,
+            )
+            .with_table(
+                current_bet as u64,
+                chip_data.pot as u64,
+                game.bb as u64,
+                preflop,
+            ),
+        );
     }
 
     fn handle_compare_hands(
@@ -430,10 +613,13 @@ impl<N: Network, M: VMManager<N> + 'static> PokerGame<N, M> {
         }
 
         model.log_action_start("Comparing hands".to_string());
-        if let Err(e) = self.poker.compare_hands(&self.account, game_id) {
-            model.log(format!("Error comparing hands: {}", e));
-            return Ok(());
-        }
+        self.submit(
+            model,
+            game_id,
+            Transition::CompareHands,
+            String::new(),
+            || self.poker.compare_hands(&self.account, game_id),
+        )?;
 
         model.log_action_complete();
 
@@ -442,7 +628,7 @@ impl<N: Network, M: VMManager<N> + 'static> PokerGame<N, M> {
             if updated_state != new_state {
                 if let Some(state) = updated_state {
                     let description = describe_game_state(state);
-                    model.log(format!("State {}: {}", state, description));
+                    model.log_state(format!("State {}: {}", state, description));
                 }
                 model.current_state = updated_state;
             }
@@ -461,10 +647,13 @@ impl<N: Network, M: VMManager<N> + 'static> PokerGame<N, M> {
         let prize = game.buy_in * 3;
         model.log_action_start(format!("Claiming prize: {} credits", Credits::from(prize)));
 
-        if let Err(e) = self.poker.claim_prize(&self.account, game_id, prize) {
-            model.log(format!("Error claiming prize: {}", e));
-            return Ok(());
-        }
+        self.submit(
+            model,
+            game_id,
+            Transition::ClaimPrize,
+            format!("prize {}", Credits::from(prize)),
+            || self.poker.claim_prize(&self.account, game_id, prize),
+        )?;
 
         model.log_action_complete();
         model.log(format!("Claimed {} credits", Credits::from(prize)));
@@ -474,7 +663,7 @@ impl<N: Network, M: VMManager<N> + 'static> PokerGame<N, M> {
             if updated_state != new_state {
                 if let Some(state) = updated_state {
                     let description = describe_game_state(state);
-                    model.log(format!("State {}: {}", state, description));
+                    model.log_state(format!("State {}: {}", state, description));
                 }
                 model.current_state = updated_state;
             }
@@ -483,36 +672,37 @@ impl<N: Network, M: VMManager<N> + 'static> PokerGame<N, M> {
         Ok(())
     }
 
-    fn update_render_data(&self, game_id: u32, model: &mut GameModel) {
-        let current_chips = self.get_chip(game_id);
-        let revealed_cards = self.poker.get_revealed_cards(game_id);
-
-        let mut render_data = card_from_revealed(revealed_cards);
+    fn update_render_data(&self, reads: &Reads<N>, model: &mut GameModel) {
+        let mut render_data = card_from_revealed(reads.revealed.as_ref());
 
         if let Some(decrypted) = model.decrypted_hand {
             render_data.set_cards(model.current_player_id, decrypted);
         }
 
-        model.card = Some(render_data);
-        model.chip = current_chips;
-
-        if let Some(game) = self.poker.get_games(game_id) {
-            model.dealer_button = game.dealer_button;
+        let previous = model.card.and_then(|card| card.invalid);
+        if let Some(index) = render_data.invalid
+            && previous != Some(index)
+        {
+            log::error!("Revealed cards hold invalid card index {}", index);
+            model.log_error(format!(
+                "Revealed cards hold invalid card index {}, shown face-down",
+                index
+            ));
         }
+
+        model.card = Some(render_data);
+        model.chip = reads.chips;
+        model.dealer_button = reads.game.dealer_button;
     }
 
     pub fn initialize_game(&mut self, model: &mut GameModel) -> anyhow::Result<()> {
         use crate::deck::initialized_deck;
 
         model.log_action_start("Shuffling deck".to_string());
-        let (_, control_bits) = waksman_ctrl::shuffle_deck(initialized_deck::<N>());
+        let control_bits = self.shuffle(initialized_deck::<N>());
         model.log_action_complete();
 
-        let password = if model.password_input.is_empty() {
-            0u128
-        } else {
-            model.password_input.parse::<u128>().unwrap_or(0u128)
-        };
+        let password = model.password();
 
         let buy_in_credits = model.buy_in_input.parse::<f64>().unwrap_or(100.0).max(0.0);
         let buy_in = (buy_in_credits * 1_000_000.0).round() as u64;
@@ -523,24 +713,34 @@ impl<N: Network, M: VMManager<N> + 'static> PokerGame<N, M> {
         })?;
         model.log_action_complete();
 
-        if balance < buy_in {
+        let required = buy_in + model.balance_reserve;
+        if balance < required {
             anyhow::bail!(
                 "Insufficient balance. Need {} credits but have {} credits",
-                Credits::from(buy_in),
+                Credits::from(required),
                 Credits::from(balance)
             );
         }
 
         model.log_action_start("Creating game".to_string());
-        let (keys, _) = self.poker.create_game(
-            &self.account,
-            buy_in,
-            control_bits,
-            self.secret,
-            self.secret_inv,
-            password,
-            model.blind_frequency,
-        )?;
+        let game_id = self.next_game_id();
+        let blind_frequency = model.blind_frequency;
+        let inputs = format!(
+            "buy_in {}, blind_frequency {}",
+            Credits::from(buy_in),
+            blind_frequency
+        );
+        let (keys, _) = self.submit(model, game_id, Transition::CreateGame, inputs, || {
+            self.poker.create_game(
+                &self.account,
+                buy_in,
+                control_bits,
+                self.secret,
+                self.secret_inv,
+                password,
+                blind_frequency,
+            )
+        })?;
         model.log_action_complete();
 
         self.keys = Some(keys);
@@ -553,19 +753,25 @@ impl<N: Network, M: VMManager<N> + 'static> PokerGame<N, M> {
             return Ok(false);
         }
 
-        let game = self
-            .poker
-            .get_games(game_id)
-            .ok_or_else(|| anyhow::anyhow!("Game {} not found", game_id))?;
+        let reads = self.reads(game_id)?;
+        let game = &reads.game;
 
-        let new_state = GameState::from_u8(game.state);
+        let mut new_state = GameState::from_u8(game.state);
         let previous_state = model.current_state;
+        if matches!(new_state, Some(GameState::P2Join | GameState::P3Join))
+            && (previous_state == Some(GameState::Abandoned)
+                || (new_state == previous_state
+                    && model.state_since.elapsed() >= model.abandon_after))
+        {
+            new_state = Some(GameState::Abandoned);
+        }
         let state_changed = previous_state != new_state;
 
         if state_changed {
+            model.state_since = Instant::now();
             if let Some(state) = new_state {
                 let description = describe_game_state(state);
-                model.log(format!("State {}: {}", state, description));
+                model.log_state(format!("State {}: {}", state, description));
             }
             model.current_state = new_state;
 
@@ -578,7 +784,7 @@ impl<N: Network, M: VMManager<N> + 'static> PokerGame<N, M> {
                             (state, self.player_id),
                             (GameState::P1NewShuffle, 1) | (GameState::P2NewShuffle, 2)
                         ) {
-                            model.log(format!("Starting new hand (state: {})", state));
+                            model.log_state(format!("Starting new hand (state: {})", state));
                         }
                     }
                     GameState::P2Shuffle | GameState::P3Shuffle => {
@@ -586,7 +792,7 @@ impl<N: Network, M: VMManager<N> + 'static> PokerGame<N, M> {
                             (state, self.player_id),
                             (GameState::P2Shuffle, 2) | (GameState::P3Shuffle, 3)
                         ) {
-                            model.log(format!("Shuffling deck (state: {})", state));
+                            model.log_state(format!("Shuffling deck (state: {})", state));
                         }
                     }
                     _ => {}
@@ -594,20 +800,41 @@ impl<N: Network, M: VMManager<N> + 'static> PokerGame<N, M> {
             }
         }
 
-        let current_chips = self.get_chip(game_id);
-        let cards = self.poker.get_cards(game_id);
+        let current_chips = reads.chips;
+        let cards = &reads.cards;
         let mut hand_decrypted = false;
 
+        if state_changed || model.snapshot.is_none_or(|s| s.game_id != game_id) {
+            model.seats = Some([
+                game.player1.to_string(),
+                game.player2.to_string(),
+                game.player3.to_string(),
+            ]);
+            model.snapshot = Some(GameSnapshot {
+                game_id,
+                state: game.state,
+                buy_in: game.buy_in,
+                players_out: game.players_out,
+                players_folded: game.players_folded,
+                hands_played: game.hands_played,
+                sb: game.sb,
+                bb: game.bb,
+                chips: current_chips,
+                revealed: card_from_revealed(reads.revealed.as_ref()),
+            });
+        }
+
         if let Some(state) = new_state {
-            self.setup_betting_ui(state, state_changed, current_chips, &game, model);
+            self.setup_betting_ui(state, state_changed, current_chips, game, model);
 
             if model.decrypted_hand.is_none()
-                && let (Some(c), Some(_keys)) = (&cards, &self.keys)
+                && let (Some(c), Some(_keys)) = (cards, &self.keys)
             {
                 let is_past_decrypt = !matches!(
                     state,
                     GameState::P2Join
                         | GameState::P3Join
+                        | GameState::Abandoned
                         | GameState::P1DecHand
                         | GameState::P2DecHand
                         | GameState::P3DecHand
@@ -619,10 +846,10 @@ impl<N: Network, M: VMManager<N> + 'static> PokerGame<N, M> {
 
                 if is_past_decrypt {
                     let encrypted_hand = get_player_cards(self.player_id, c);
-                    let result =
-                        decrypt_hand_local(encrypted_hand, self.secret_inv, &self.card_hashes);
-                    if result != [255, 255] {
-                        model.decrypted_hand = Some(result);
+                    if let Some(hand) =
+                        decrypt_hand_local(encrypted_hand, self.secret_inv, &self.card_hashes)
+                    {
+                        model.decrypted_hand = Some(hand);
                         hand_decrypted = true;
                     }
                 }
@@ -632,7 +859,7 @@ impl<N: Network, M: VMManager<N> + 'static> PokerGame<N, M> {
         let should_update_render = state_changed || hand_decrypted || model.card.is_none();
 
         if should_update_render {
-            self.update_render_data(game_id, model);
+            self.update_render_data(&reads, model);
         }
 
         model.update_eliminated_players(game.players_out);
@@ -640,7 +867,7 @@ impl<N: Network, M: VMManager<N> + 'static> PokerGame<N, M> {
         if let Some(winner) = model.check_for_winner()
             && model.game_winner.is_none()
         {
-            model.log(format!("Player {} wins!", winner));
+            model.log_state(format!("Player {} wins!", winner));
         }
 
         model.last_poll_time = Instant::now();
@@ -654,7 +881,7 @@ impl<N: Network, M: VMManager<N> + 'static> PokerGame<N, M> {
         game_id: u32,
         state_changed: bool,
     ) -> Option<GameCommand> {
-        if !state_changed || model.game_winner.is_some() {
+        if !state_changed || model.game_winner.is_some() || model.is_game_over() {
             return None;
         }
 
@@ -748,6 +975,34 @@ impl<N: Network, M: VMManager<N> + 'static> PokerGame<N, M> {
     }
 }
 
+/// One game of a `PokerGame`, as `tx::submit` follows it.
+struct OnChain<'a, N: Network, M: VMManager<N> + Clone> {
+    game: &'a PokerGame<N, M>,
+    game_id: u32,
+}
+
+impl<N: Network, M: VMManager<N> + 'static> Chain for OnChain<'_, N, M> {
+    fn position(&self) -> Option<Position> {
+        self.game.position(self.game_id)
+    }
+
+    /// Without a node to ask, as on a local VM, the call only returns once
+    /// the transaction is in.
+    fn confirmed(&self, id: &str) -> Option<TxStatus> {
+        match &self.game.api {
+            Some(api) => confirmed_status(api, id, API_TIMEOUT),
+            None => Some(TxStatus::Accepted),
+        }
+    }
+
+    fn block_height(&self, id: &str) -> Option<u32> {
+        let api = self.game.api.as_ref()?;
+        inclusion_height(api, id, API_TIMEOUT)
+            .map_err(|e| log::debug!("Block of {} unavailable: {}", id, e))
+            .ok()
+    }
+}
+
 impl<N: Network, M: VMManager<N>> Clone for PokerGame<N, M> {
     fn clone(&self) -> Self {
         Self {
@@ -759,31 +1014,39 @@ impl<N: Network, M: VMManager<N>> Clone for PokerGame<N, M> {
             player_id: self.player_id,
             keys: self.keys.clone(),
             card_hashes: self.card_hashes.clone(),
+            api: self.api.clone(),
+            reads: self.reads.clone(),
         }
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+/// Face-up cards of a game as seen by this player; `None` is face-down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
 pub struct Card {
-    pub flop: [u8; 3],
-    pub turn: u8,
-    pub river: u8,
-    pub player1: [u8; 2],
-    pub player2: [u8; 2],
-    pub player3: [u8; 2],
+    pub flop: [Option<PlayingCard>; 3],
+    pub turn: Option<PlayingCard>,
+    pub river: Option<PlayingCard>,
+    pub player1: [Option<PlayingCard>; 2],
+    pub player2: [Option<PlayingCard>; 2],
+    pub player3: [Option<PlayingCard>; 2],
+    /// The first on-chain index that is neither a card nor face-down, which
+    /// points at a contract or decryption bug.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invalid: Option<u8>,
 }
 
 impl Card {
-    pub fn get_cards(&self, player_id: u8) -> [u8; 2] {
+    pub fn get_cards(&self, player_id: u8) -> [Option<PlayingCard>; 2] {
         match player_id {
             1 => self.player1,
             2 => self.player2,
             3 => self.player3,
-            _ => [255, 255],
+            _ => [None, None],
         }
     }
 
-    pub fn set_cards(&mut self, player_id: u8, cards: [u8; 2]) {
+    pub fn set_cards(&mut self, player_id: u8, cards: [PlayingCard; 2]) {
+        let cards = cards.map(Some);
         match player_id {
             1 => self.player1 = cards,
             2 => self.player2 = cards,
@@ -793,35 +1056,31 @@ impl Card {
     }
 }
 
-fn card_from_revealed<N: Network>(revealed: Option<RevealedCards<N>>) -> Card {
+fn card_from_revealed<N: Network>(revealed: Option<&RevealedCards<N>>) -> Card {
     if let Some(r) = revealed {
+        let card = PlayingCard::from_index;
+        let indices = r
+            .flop
+            .iter()
+            .chain([&r.turn, &r.river])
+            .chain(r.player1.iter().chain(&r.player2).chain(&r.player3));
         Card {
-            flop: r.flop,
-            turn: r.turn,
-            river: r.river,
-            player1: r.player1,
-            player2: r.player2,
-            player3: r.player3,
+            flop: r.flop.map(card),
+            turn: card(r.turn),
+            river: card(r.river),
+            player1: r.player1.map(card),
+            player2: r.player2.map(card),
+            player3: r.player3.map(card),
+            invalid: indices
+                .copied()
+                .find(|&index| index > 51 && index != FACE_DOWN),
         }
     } else {
         Card::default()
     }
 }
 
-impl Default for Card {
-    fn default() -> Self {
-        Self {
-            flop: [255, 255, 255],
-            turn: 255,
-            river: 255,
-            player1: [255, 255],
-            player2: [255, 255],
-            player3: [255, 255],
-        }
-    }
-}
-
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 pub struct Chip {
     pub player1: u16,
     pub player2: u16,
@@ -833,6 +1092,10 @@ pub struct Chip {
 }
 
 impl Chip {
+    pub fn highest_bet(&self) -> u16 {
+        self.player1_bet.max(self.player2_bet).max(self.player3_bet)
+    }
+
     pub fn get_chips(&self, player_id: u8) -> u16 {
         match player_id {
             1 => self.player1,
@@ -852,11 +1115,12 @@ impl Chip {
     }
 }
 
-pub trait GameHandle: Send {
+pub trait GameHandle: GameSource + Send {
     fn clone_handle(&self) -> Box<dyn GameHandle>;
     fn check_game_exists(&self, game_id: u32) -> bool;
     fn get_game_state(&self, game_id: u32) -> Option<u8>;
     fn get_player_id(&self) -> u8;
+    fn address(&self) -> String;
     fn get_card(&self, game_id: u32, current_player_id: u8, model: &GameModel) -> Option<Card>;
     fn get_chip(&self, game_id: u32) -> Option<Chip>;
     fn check_address_conflict(&self, game_id: u32) -> bool;
@@ -891,6 +1155,27 @@ pub trait GameHandle: Send {
     fn execute_auto_claim(&mut self, model: &mut GameModel, game_id: u32) -> anyhow::Result<()>;
 }
 
+impl<N: Network, M: VMManager<N> + 'static> GameSource for PokerGame<N, M> {
+    fn next_game_id(&self) -> u32 {
+        self.poker.get_next_game_id(0).unwrap_or(0)
+    }
+
+    fn game_record(&self, game_id: u32) -> Option<GameRecord> {
+        let game = self.poker.get_games(game_id)?;
+        let chips = self
+            .poker
+            .get_chips(game_id)
+            .map(|chips| [chips.player1, chips.player2, chips.player3]);
+        Some(GameRecord {
+            players: [game.player1, game.player2, game.player3].map(|a| a.to_string()),
+            state: game.state,
+            buy_in: game.buy_in,
+            hands_played: game.hands_played,
+            chips,
+        })
+    }
+}
+
 impl<N: Network, M: VMManager<N> + 'static> GameHandle for PokerGame<N, M> {
     fn clone_handle(&self) -> Box<dyn GameHandle> {
         Box::new(self.clone())
@@ -908,9 +1193,13 @@ impl<N: Network, M: VMManager<N> + 'static> GameHandle for PokerGame<N, M> {
         self.player_id
     }
 
+    fn address(&self) -> String {
+        self.account.address().to_string()
+    }
+
     fn get_card(&self, game_id: u32, current_player_id: u8, model: &GameModel) -> Option<Card> {
         let revealed = self.poker.get_revealed_cards(game_id);
-        let mut render_data = card_from_revealed(revealed);
+        let mut render_data = card_from_revealed(revealed.as_ref());
 
         if let Some(decrypted) = model.decrypted_hand {
             render_data.set_cards(current_player_id, decrypted);
@@ -972,14 +1261,10 @@ impl<N: Network, M: VMManager<N> + 'static> GameHandle for PokerGame<N, M> {
         model.log_action_complete();
 
         model.log_action_start("Shuffling deck".to_string());
-        let (_, control_bits) = waksman_ctrl::shuffle_deck(deck);
+        let control_bits = self.shuffle(deck);
         model.log_action_complete();
 
-        let password = if model.password_input.is_empty() {
-            0u128
-        } else {
-            model.password_input.parse::<u128>().unwrap_or(0u128)
-        };
+        let password = model.password();
 
         let game = self
             .poker
@@ -993,24 +1278,33 @@ impl<N: Network, M: VMManager<N> + 'static> GameHandle for PokerGame<N, M> {
             .ok_or_else(|| anyhow::anyhow!("Unable to fetch account balance"))?;
         model.log_action_complete();
 
-        if balance < buy_in {
+        let required = buy_in + model.balance_reserve;
+        if balance < required {
             anyhow::bail!(
                 "Insufficient balance. Need {} credits but have {} credits",
-                Credits::from(buy_in),
+                Credits::from(required),
                 Credits::from(balance)
             );
         }
 
         model.log_action_start(format!("Joining game {}", game_id));
-        let (keys, _) = self.poker.join_game(
-            &self.account,
+        let (keys, _) = self.submit(
+            model,
             game_id,
-            buy_in,
-            deck,
-            control_bits,
-            self.secret,
-            self.secret_inv,
-            password,
+            Transition::JoinGame,
+            format!("buy_in {}", Credits::from(buy_in)),
+            || {
+                self.poker.join_game(
+                    &self.account,
+                    game_id,
+                    buy_in,
+                    deck,
+                    control_bits,
+                    self.secret,
+                    self.secret_inv,
+                    password,
+                )
+            },
         )?;
         model.log_action_complete();
 
@@ -1046,8 +1340,10 @@ impl<N: Network, M: VMManager<N> + 'static> GameHandle for PokerGame<N, M> {
 
         match action {
             BettingAction::Fold => {
-                model.log_action_start("Folding".to_string());
-                self.poker.fold(&self.account, game_id)?;
+                model.log_bet_start("Folding".to_string());
+                self.submit(model, game_id, Transition::Fold, String::new(), || {
+                    self.poker.fold(&self.account, game_id)
+                })?;
                 model.log_action_complete();
             }
             BettingAction::Call => {
@@ -1083,16 +1379,28 @@ impl<N: Network, M: VMManager<N> + 'static> GameHandle for PokerGame<N, M> {
 
                 let call_amount = highest_bet - current_bet;
                 if call_amount == 0 {
-                    model.log_action_start("Checking".to_string());
+                    model.log_bet_start("Checking".to_string());
                 } else {
-                    model.log_action_start(format!("Calling {}", call_amount));
+                    model.log_bet_start(format!("Calling {}", call_amount));
                 }
-                self.poker.bet(&self.account, game_id, call_amount)?;
+                self.submit(
+                    model,
+                    game_id,
+                    Transition::Bet,
+                    format!("amount {}", call_amount),
+                    || self.poker.bet(&self.account, game_id, call_amount),
+                )?;
                 model.log_action_complete();
             }
             BettingAction::Raise => {
-                model.log_action_start(format!("Raising {}", amount));
-                self.poker.bet(&self.account, game_id, amount as u16)?;
+                model.log_bet_start(format!("Raising {}", amount));
+                self.submit(
+                    model,
+                    game_id,
+                    Transition::Bet,
+                    format!("amount {}", amount as u16),
+                    || self.poker.bet(&self.account, game_id, amount as u16),
+                )?;
                 model.log_action_complete();
             }
         }
@@ -1159,17 +1467,19 @@ impl<N: Network, M: VMManager<N> + 'static> GameHandle for PokerGame<N, M> {
     fn new_shuffle(&mut self, model: &mut GameModel, game_id: u32) -> anyhow::Result<()> {
         use crate::deck::initialized_deck;
 
-        let (_, control_bits) = waksman_ctrl::shuffle_deck(initialized_deck::<N>());
+        let control_bits = self.shuffle(initialized_deck::<N>());
         model.log_action_complete();
 
         model.log_action_start("Starting new hand".to_string());
-        let (keys, _) = self.poker.new_hand(
-            &self.account,
-            game_id,
-            control_bits,
-            self.secret,
-            self.secret_inv,
-        )?;
+        let (keys, _) = self.submit(model, game_id, Transition::NewHand, String::new(), || {
+            self.poker.new_hand(
+                &self.account,
+                game_id,
+                control_bits,
+                self.secret,
+                self.secret_inv,
+            )
+        })?;
         model.log_action_complete();
 
         self.keys = Some(keys);
@@ -1183,16 +1493,24 @@ impl<N: Network, M: VMManager<N> + 'static> GameHandle for PokerGame<N, M> {
         model.log_action_complete();
 
         model.log_action_start("Shuffling deck".to_string());
-        let (_, control_bits) = crate::waksman_ctrl::shuffle_deck(deck);
+        let control_bits = self.shuffle(deck);
         model.log_action_complete();
 
-        let (keys, _) = self.poker.shuffle_deck(
-            &self.account,
+        let (keys, _) = self.submit(
+            model,
             game_id,
-            deck,
-            control_bits,
-            self.secret,
-            self.secret_inv,
+            Transition::ShuffleDeck,
+            String::new(),
+            || {
+                self.poker.shuffle_deck(
+                    &self.account,
+                    game_id,
+                    deck,
+                    control_bits,
+                    self.secret,
+                    self.secret_inv,
+                )
+            },
         )?;
         model.log_action_complete();
 
@@ -1225,48 +1543,59 @@ pub fn new_local_game() -> anyhow::Result<[Box<dyn GameHandle>; 3]> {
     Ok(games)
 }
 
-pub fn new_testnet_game(account_index: u16, endpoint: &str) -> anyhow::Result<Box<dyn GameHandle>> {
+/// Plays as `PRIVATE_KEY_P{account_index + 1}`. Only a local devnet falls
+/// back to the dev account of that index when the variable is unset.
+pub fn new_testnet_game(
+    account_index: u16,
+    endpoint: &str,
+    network: NetworkType,
+) -> anyhow::Result<Box<dyn GameHandle>> {
     dotenvy::dotenv().ok();
 
-    let account = match account_index {
-        0 => std::env::var("PRIVATE_KEY_P1")
-            .ok()
-            .and_then(|pk| Account::from_str(&pk).ok())
-            .unwrap_or_else(|| Account::dev_account(0).unwrap()),
-        1 => std::env::var("PRIVATE_KEY_P2")
-            .ok()
-            .and_then(|pk| Account::from_str(&pk).ok())
-            .unwrap_or_else(|| Account::dev_account(1).unwrap()),
-        2 => std::env::var("PRIVATE_KEY_P3")
-            .ok()
-            .and_then(|pk| Account::from_str(&pk).ok())
-            .unwrap_or_else(|| Account::dev_account(2).unwrap()),
-        _ => Account::dev_account(account_index).unwrap(),
+    let env_var = format!("PRIVATE_KEY_P{}", account_index + 1);
+    let account = match std::env::var(&env_var) {
+        Ok(private_key) => Account::from_str(&private_key)
+            .map_err(|e| anyhow::anyhow!("Invalid private key in {}: {}", env_var, e))?,
+        Err(_) if network == NetworkType::Local => Account::dev_account(account_index)?,
+        Err(_) => anyhow::bail!(
+            "{} is not set; dev accounts are only used on a local network",
+            env_var
+        ),
     };
     let credentials = Credentials::from_env().ok();
     let client = Client::new(endpoint, credentials)?;
     let vm = NetworkVm::<TestnetV0>::new(&client)?;
-    let game = PokerGame::new(account, vm, 0)?;
+    let game = PokerGame::new(account, vm, 0)?.with_block_height(endpoint, network);
     Ok(Box::new(game))
 }
 
 pub fn new_game_from_private_key(
     private_key: &str,
     endpoint: &str,
+    network: NetworkType,
+) -> anyhow::Result<Box<dyn GameHandle>> {
+    let account: Account<TestnetV0> = Account::from_str(private_key)?;
+    new_game_from_account(account, endpoint, network)
+}
+
+pub fn new_game_from_account(
+    account: Account<TestnetV0>,
+    endpoint: &str,
+    network: NetworkType,
 ) -> anyhow::Result<Box<dyn GameHandle>> {
     dotenvy::dotenv().ok();
 
-    let account: Account<TestnetV0> = Account::from_str(private_key)?;
     let credentials = Credentials::from_env().ok();
     let client = Client::new(endpoint, credentials)?;
     let vm = NetworkVm::new(&client)?;
-    let game = PokerGame::new(account, vm, 0)?;
+    let game = PokerGame::new(account, vm, 0)?.with_block_height(endpoint, network);
     Ok(Box::new(game))
 }
 
 #[derive(Debug)]
 pub enum GameMessage {
     CharInput(char),
+    Paste(String),
     Backspace,
     Confirm,
     Quit,
@@ -1277,6 +1606,13 @@ pub enum GameMessage {
     Up,
     Down,
 
+    LogPageUp,
+    LogPageDown,
+    LogEnd,
+    CycleLogFilter,
+    ToggleTransactions,
+    ExportTransactions,
+
     GameInitialized(Result<(), String>),
     GameJoined(Result<(), String>),
     GameStatePolled(Result<(), String>),
@@ -1325,10 +1661,36 @@ impl GameCommand {
             Self::AutoClaim(_) => "Claiming prize",
         }
     }
+
+    fn transition(&self) -> Option<Transition> {
+        match self {
+            Self::InitializeGame(_) => Some(Transition::CreateGame),
+            Self::JoinGame(_) => Some(Transition::JoinGame),
+            Self::PlaceBet {
+                action: crate::game_state::BettingAction::Fold,
+                ..
+            } => Some(Transition::Fold),
+            Self::PlaceBet { .. } => Some(Transition::Bet),
+            Self::AutoDecrypt { step, .. } => Some(match step {
+                DecryptionStep::Hands => Transition::DecryptHands,
+                DecryptionStep::Flop => Transition::DecryptFlop,
+                DecryptionStep::Turn | DecryptionStep::River => Transition::DecryptTurnRiver,
+                DecryptionStep::Showdown => Transition::Showdown,
+            }),
+            Self::AutoNewShuffle(_) => Some(Transition::NewHand),
+            Self::AutoShuffleDeck(_) => Some(Transition::ShuffleDeck),
+            Self::AutoCompare(_) => Some(Transition::CompareHands),
+            Self::AutoClaim(_) => Some(Transition::ClaimPrize),
+            Self::SearchForGame | Self::RefreshGameState(_) => None,
+        }
+    }
 }
 
 struct RunningCommand {
     join_handle: JoinHandle<CommandResult>,
+    command: GameCommand,
+    /// Index of the first transaction the command submits.
+    first_tx: usize,
 }
 
 struct CommandResult {
@@ -1338,23 +1700,334 @@ struct CommandResult {
     message: Option<GameMessage>,
 }
 
+/// Reports a failed command with what became of its last transaction. One
+/// that landed anyway and returned no keys counts as done.
+fn settle(
+    result: anyhow::Result<()>,
+    model: &mut GameModel,
+    first_tx: usize,
+) -> Result<(), String> {
+    let Err(e) = result else {
+        return Ok(());
+    };
+    let Some(record) = model.transactions[first_tx..].last() else {
+        return Err(e.to_string());
+    };
+    if record.error.is_none() {
+        return Err(e.to_string());
+    }
+    match record.status {
+        TxStatus::Accepted if !record.transition.returns_record() => {
+            let message = format!("{} landed despite: {}", record.transition.name(), e);
+            model.log_warning(message);
+            Ok(())
+        }
+        TxStatus::Accepted => Err(format!(
+            "{}. {} landed, but the keys it returned were lost",
+            e,
+            record.transition.name()
+        )),
+        _ => Err(format!("{}. {}", e, record.describe())),
+    }
+}
+
+/// Offers to prove an automatic step again once its transaction is known
+/// not to have changed the table, and returns the command to keep for it.
+/// Bets are placed again from the betting UI instead.
+fn offer_retry(
+    model: &mut GameModel,
+    command: GameCommand,
+    first_tx: usize,
+) -> Option<GameCommand> {
+    let retryable = matches!(
+        command,
+        GameCommand::AutoDecrypt { .. }
+            | GameCommand::AutoNewShuffle(_)
+            | GameCommand::AutoShuffleDeck(_)
+            | GameCommand::AutoCompare(_)
+            | GameCommand::AutoClaim(_)
+    );
+    let record = model.transactions.get(first_tx..)?.last()?;
+    if !retryable || !record.status.can_retry() {
+        return None;
+    }
+    model.log_warning(format!(
+        "Press r to prove {} again",
+        record.transition.name()
+    ));
+    Some(command)
+}
+
 pub struct Game {
     handle: Option<Box<dyn GameHandle>>,
     refresh_handle: Option<Box<dyn GameHandle>>,
     pub model: GameModel,
     pending_command: Option<GameCommand>,
     running_command: Option<RunningCommand>,
+    events: EventBus,
+    last_snapshot: Option<GameSnapshot>,
+    /// The hand's last snapshot in a showdown state, which holds its final
+    /// bets even if polling skips the moment chips are settled.
+    showdown_snapshot: Option<GameSnapshot>,
+    log_filter: LogFilter,
+    log_scroll: usize,
+    /// Show this game's transactions in place of the logs.
+    show_transactions: bool,
+    history: ActionHistory,
+    pre_action: Option<QueuedPreAction>,
+    hand_result: Option<HandResult>,
+    game_over: Option<GameOver>,
+    address: String,
+    stats: Option<StatsDb>,
+    transition_times: Option<TransitionTimes>,
+    tx_history: Option<TxHistory>,
+    /// The last command, if its transaction failed without moving the table.
+    retry_command: Option<GameCommand>,
 }
 
+const LOG_PAGE: usize = 5;
+
 impl Game {
-    pub fn new(handle: Box<dyn GameHandle>, network_type: NetworkType) -> Self {
+    pub fn new(handle: Box<dyn GameHandle>, profile: &Profile) -> Self {
+        let address = handle.address();
         Self {
             handle: Some(handle),
             refresh_handle: None,
-            model: GameModel::new(network_type),
+            model: GameModel::new(profile),
             pending_command: None,
             running_command: None,
+            events: EventBus::default(),
+            last_snapshot: None,
+            showdown_snapshot: None,
+            log_filter: LogFilter::All,
+            log_scroll: 0,
+            show_transactions: false,
+            history: ActionHistory::default(),
+            pre_action: None,
+            hand_result: None,
+            game_over: None,
+            address,
+            stats: None,
+            transition_times: None,
+            tx_history: None,
+            retry_command: None,
+        }
+    }
+
+    /// Accumulates stats for every hand this client sees into `db`.
+    pub fn set_stats(&mut self, db: StatsDb) {
+        self.stats = Some(db);
+    }
+
+    /// Records how long each transition takes into `times` and uses them
+    /// for the proving ETA.
+    pub fn set_transition_times(&mut self, times: TransitionTimes) {
+        self.transition_times = Some(times);
+    }
+
+    /// Shows this account's earlier transactions on the network and saves
+    /// new ones into `history`.
+    pub fn set_tx_history(&mut self, history: TxHistory) {
+        let network = self.model.network_type.name();
+        let mut records = history.records(network, &self.address).to_vec();
+        records.append(&mut self.model.transactions);
+        self.model.transactions = records;
+        self.tx_history = Some(history);
+    }
+
+    pub fn set_session_log(&mut self, path: &std::path::Path) -> anyhow::Result<()> {
+        self.model.logs.set_session_file(path)
+    }
+
+    pub fn subscribe(&mut self) -> std::sync::mpsc::Receiver<GameEvent> {
+        self.events.subscribe()
+    }
+
+    fn emit_events(&mut self) {
+        let Some(snapshot) = self.model.snapshot else {
+            return;
+        };
+        if self.last_snapshot == Some(snapshot) {
+            return;
+        }
+        let previous = self
+            .last_snapshot
+            .filter(|previous| previous.game_id == snapshot.game_id);
+        let events = crate::events::diff(previous.as_ref(), &snapshot);
+        // Stats read the finished hand's actions, so record them before the
+        // history moves on to the next hand.
+        match &previous {
+            Some(previous) => {
+                let settled = self
+                    .showdown_snapshot
+                    .filter(|s| {
+                        s.game_id == previous.game_id && s.hands_played == previous.hands_played
+                    })
+                    .unwrap_or(*previous);
+                if let Some(result) = hand_result(&settled, &snapshot) {
+                    self.record_stats(|db, account, seats, history| {
+                        db.record_hand(account, seats, history, &result)
+                    });
+                    self.hand_result = Some(result);
+                }
+                if previous.players_out.count_ones() < 2
+                    && snapshot.players_out.count_ones() >= 2
+                    && let Some(winner) =
+                        (1..=3u8).find(|p| snapshot.players_out & (1 << (p - 1)) == 0)
+                {
+                    self.record_stats(|db, account, seats, _| {
+                        db.record_tournament(account, seats, winner, snapshot.buy_in)
+                    });
+                }
+            }
+            None => self.hand_result = None,
+        }
+        if matches!(
+            GameState::from_u8(snapshot.state),
+            Some(
+                GameState::P1Showdown
+                    | GameState::P2Showdown
+                    | GameState::P3Showdown
+                    | GameState::Compare
+            )
+        ) {
+            self.showdown_snapshot = Some(snapshot);
+        }
+        self.history.record(previous.as_ref(), &snapshot, &events);
+        for event in events {
+            if matches!(event, GameEvent::BlindsPosted { .. }) {
+                self.hand_result = None;
+            }
+            match event {
+                GameEvent::StateChanged { .. } => {}
+                GameEvent::BlindsPosted { .. }
+                | GameEvent::PlayerBet { .. }
+                | GameEvent::PlayerFolded { .. } => self.model.log_bet(event.to_string()),
+                _ => self.model.log_state(event.to_string()),
+            }
+            self.events.publish(&event);
+        }
+        self.last_snapshot = Some(snapshot);
+    }
+
+    fn record_stats(
+        &mut self,
+        update: impl FnOnce(&mut StatsDb, &str, &[String; 3], &ActionHistory) -> anyhow::Result<()>,
+    ) {
+        let (Some(db), Some(seats)) = (&mut self.stats, &self.model.seats) else {
+            return;
+        };
+        if let Err(e) = update(db, &self.address, seats, &self.history) {
+            self.model
+                .log_warning(format!("Failed to save stats: {}", e));
+        }
+    }
+
+    pub fn pre_action(&self) -> Option<PreAction> {
+        self.pre_action.map(|queued| queued.action)
+    }
+
+    /// Queues `action` for our next turn, or clears it if it is already queued.
+    pub fn toggle_pre_action(&mut self, action: PreAction) {
+        if self.pre_action() == Some(action) {
+            self.pre_action = None;
+            return;
+        }
+        let Some(snapshot) = self.model.snapshot else {
+            return;
+        };
+        self.pre_action = Some(QueuedPreAction {
+            action,
+            hands_played: snapshot.hands_played,
+            highest_bet: snapshot.chips.map(|c| c.highest_bet()).unwrap_or(0),
+        });
+    }
+
+    fn run_pre_action(&mut self) {
+        let (Some(queued), Some(snapshot)) = (self.pre_action, self.model.snapshot) else {
+            return;
+        };
+        let highest = snapshot.chips.map(|c| c.highest_bet()).unwrap_or(0);
+        if queued.is_stale(snapshot.hands_played, highest) {
+            self.pre_action = None;
+            self.model.log(format!(
+                "Pre-action '{}' cancelled: the bet changed",
+                queued.action.name()
+            ));
+            return;
         }
+
+        let Some(betting_ui) = &self.model.betting_ui else {
+            return;
+        };
+        if self.pending_command.is_some() || self.running_command.is_some() {
+            return;
+        }
+        let call_amount = betting_ui.call_amount;
+        self.pre_action = None;
+        match queued.action.resolve(call_amount) {
+            Some(action) => {
+                self.model
+                    .log_bet(format!("Pre-action: {}", queued.action.name()));
+                if let Err(e) = self.place_bet(action, 0) {
+                    self.model.log_error(e.to_string());
+                }
+            }
+            None => self.model.log(format!(
+                "Pre-action '{}' does not apply, your move",
+                queued.action.name()
+            )),
+        }
+    }
+
+    /// Switches to the game-over screen the first time the game reaches a
+    /// terminal state, and back to the table if an abandoned game fills up.
+    fn check_game_over(&mut self) {
+        if let (Some(over), Some(game_id)) = (&self.game_over, self.model.game_id)
+            && over.state == GameState::Abandoned
+            && !self.model.is_game_over()
+        {
+            self.model.log_state(format!(
+                "Someone joined game {}, back to the table",
+                game_id
+            ));
+            self.game_over = None;
+            self.model.screen = Screen::InGame;
+            return;
+        }
+        if self.game_over.is_some() || !self.model.is_game_over() {
+            return;
+        }
+        let (Some(game_id), Some(state)) = (self.model.game_id, self.model.current_state) else {
+            return;
+        };
+        let over = GameOver::new(
+            game_id,
+            state,
+            self.model.snapshot.as_ref(),
+            self.model.seats.as_ref(),
+        );
+        if state == GameState::Abandoned {
+            self.model.log_warning(format!(
+                "Nobody joined game {} in {} minutes, checking less often",
+                game_id,
+                self.model.abandon_after.as_secs() / 60
+            ));
+        }
+        self.pending_command = None;
+        self.pre_action = None;
+        self.hand_result = None;
+        self.model.betting_ui = None;
+        self.model.screen = Screen::GameOver;
+        self.game_over = Some(over);
+    }
+
+    fn leave_game(&mut self) {
+        self.model.leave_game();
+        self.retry_command = None;
+        self.game_over = None;
+        self.last_snapshot = None;
+        self.history = ActionHistory::default();
     }
 
     fn reader(&self) -> &dyn GameHandle {
@@ -1375,6 +2048,10 @@ impl Game {
         match command {
             GameCommand::RefreshGameState(game_id) => {
                 let refresh_result = handle.refresh_game_state(model, game_id);
+                model.poll_failures = match refresh_result {
+                    Ok(_) => 0,
+                    Err(_) => model.poll_failures + 1,
+                };
                 let state_changed = refresh_result.as_ref().copied().unwrap_or(false);
                 let next_command = if refresh_result.is_ok() {
                     handle.detect_auto_action(model, game_id, state_changed)
@@ -1404,8 +2081,17 @@ impl Game {
                     if let Some(game_id) = found_id {
                         model.game_id = Some(game_id);
                         model.log(format!("Found game {}", game_id));
+                        let invite = InviteCode::new(
+                            model.network_type,
+                            &model.endpoint,
+                            game_id,
+                            model.password(),
+                        );
+                        log::info!("Invite code for game {}: {}", game_id, invite);
+                        model.log(format!("Invite code: {}", invite));
+                        model.invite_code = Some(invite.to_string());
                         if let Err(e) = handle.try_set_player_id(game_id) {
-                            model.log(format!("Warning: Could not determine player ID: {}", e));
+                            model.log_warning(format!("Could not determine player ID: {}", e));
                         } else {
                             model.current_player_id = handle.get_player_id();
                             model.log(format!("You are Player {}", model.current_player_id));
@@ -1436,12 +2122,12 @@ impl Game {
         mut model: GameModel,
         command: GameCommand,
     ) -> CommandResult {
+        let first_tx = model.transactions.len();
         let message = match command {
             GameCommand::InitializeGame(game_id) => {
                 model.log(format!("Creating new game {}", game_id));
-                let result = handle
-                    .initialize_game(&mut model)
-                    .map_err(|e| e.to_string());
+                let result = handle.initialize_game(&mut model);
+                let result = settle(result, &mut model, first_tx);
                 Some(GameMessage::GameInitialized(result))
             }
 
@@ -1452,9 +2138,8 @@ impl Game {
                     3
                 };
                 model.log(format!("Joining game {} as Player {}", game_id, player_num));
-                let result = handle
-                    .join_game(&mut model, game_id)
-                    .map_err(|e| e.to_string());
+                let result = handle.join_game(&mut model, game_id);
+                let result = settle(result, &mut model, first_tx);
                 Some(GameMessage::GameJoined(result))
             }
 
@@ -1463,44 +2148,38 @@ impl Game {
                 action,
                 amount,
             } => {
-                let result = handle
-                    .place_bet(&mut model, game_id, action, amount)
-                    .map_err(|e| e.to_string());
+                let result = handle.place_bet(&mut model, game_id, action, amount);
+                let result = settle(result, &mut model, first_tx);
                 Some(GameMessage::BetPlaced(result))
             }
 
             GameCommand::AutoDecrypt { game_id, step } => {
-                let result = handle
-                    .execute_auto_decrypt(&mut model, game_id, step)
-                    .map_err(|e| e.to_string());
+                let result = handle.execute_auto_decrypt(&mut model, game_id, step);
+                let result = settle(result, &mut model, first_tx);
                 Some(GameMessage::GameStatePolled(result))
             }
 
             GameCommand::AutoNewShuffle(game_id) => {
-                let result = handle
-                    .new_shuffle(&mut model, game_id)
-                    .map_err(|e| e.to_string());
+                let result = handle.new_shuffle(&mut model, game_id);
+                let result = settle(result, &mut model, first_tx);
                 Some(GameMessage::NewShuffleComplete(result))
             }
 
             GameCommand::AutoShuffleDeck(game_id) => {
-                let result = handle
-                    .shuffle_existing_deck(&mut model, game_id)
-                    .map_err(|e| e.to_string());
+                let result = handle.shuffle_existing_deck(&mut model, game_id);
+                let result = settle(result, &mut model, first_tx);
                 Some(GameMessage::NewShuffleComplete(result))
             }
 
             GameCommand::AutoCompare(game_id) => {
-                let result = handle
-                    .execute_auto_compare(&mut model, game_id)
-                    .map_err(|e| e.to_string());
+                let result = handle.execute_auto_compare(&mut model, game_id);
+                let result = settle(result, &mut model, first_tx);
                 Some(GameMessage::HandsCompared(result))
             }
 
             GameCommand::AutoClaim(game_id) => {
-                let result = handle
-                    .execute_auto_claim(&mut model, game_id)
-                    .map_err(|e| e.to_string());
+                let result = handle.execute_auto_claim(&mut model, game_id);
+                let result = settle(result, &mut model, first_tx);
                 Some(GameMessage::GameStatePolled(result))
             }
 
@@ -1509,6 +2188,7 @@ impl Game {
 
         model.background_task = None;
         model.background_task_started_ms = None;
+        model.background_task_eta_ms = None;
 
         CommandResult {
             handle: Some(handle),
@@ -1539,6 +2219,7 @@ impl Game {
         let command = self.pending_command.take()?;
 
         if command.is_sync() {
+            let is_refresh = matches!(command, GameCommand::RefreshGameState(_));
             let result = if self.refresh_handle.is_some() {
                 let mut reader_handle = self.reader().clone_handle();
                 Self::run_sync_command(reader_handle.as_mut(), &mut self.model, command)
@@ -1551,7 +2232,13 @@ impl Game {
                 self.handle = Some(handle);
                 result
             };
-            return self.apply_command_result(result);
+            let message = self.apply_command_result(result);
+            if is_refresh {
+                self.emit_events();
+                self.run_pre_action();
+                self.check_game_over();
+            }
+            return message;
         }
 
         self.model.background_task =
@@ -1562,6 +2249,15 @@ impl Game {
                 .map(|d| d.as_millis() as u64)
                 .unwrap_or(0),
         );
+        let network = self.model.network_type.name();
+        self.model.background_task_eta_ms = command
+            .transition()
+            .and_then(|transition| {
+                self.transition_times
+                    .as_ref()?
+                    .estimate(network, transition)
+            })
+            .map(|eta| eta.as_millis() as u64);
 
         let handle = self
             .handle
@@ -1570,10 +2266,18 @@ impl Game {
         self.refresh_handle = Some(handle.clone_handle());
         let worker_model = self.model.clone();
 
-        let join_handle =
-            thread::spawn(move || Self::run_background_command(handle, worker_model, command));
-
-        self.running_command = Some(RunningCommand { join_handle });
+        let first_tx = self.model.transactions.len();
+        self.retry_command = None;
+        let worker_command = command.clone();
+        let join_handle = thread::spawn(move || {
+            Self::run_background_command(handle, worker_model, worker_command)
+        });
+
+        self.running_command = Some(RunningCommand {
+            join_handle,
+            command,
+            first_tx,
+        });
         None
     }
 
@@ -1591,7 +2295,44 @@ impl Game {
         self.refresh_handle = None;
         self.model.background_task = None;
         self.model.background_task_started_ms = None;
-        self.apply_command_result(result)
+        let message = self.apply_command_result(result);
+        self.record_transition_times();
+        if self.model.transactions.len() > running.first_tx {
+            self.save_tx_history();
+        }
+        if let Some(command) = offer_retry(&mut self.model, running.command, running.first_tx) {
+            self.retry_command = Some(command);
+        }
+        message
+    }
+
+    fn save_tx_history(&mut self) {
+        let Some(history) = &mut self.tx_history else {
+            return;
+        };
+        let network = self.model.network_type.name();
+        history.set_records(network, &self.address, &self.model.transactions);
+        if let Err(e) = history.save() {
+            self.model
+                .log_warning(format!("Failed to save transactions: {}", e));
+        }
+    }
+
+    fn record_transition_times(&mut self) {
+        let samples = std::mem::take(&mut self.model.transition_samples);
+        let Some(times) = &mut self.transition_times else {
+            return;
+        };
+        if samples.is_empty() {
+            return;
+        }
+        for sample in samples {
+            times.record(self.model.network_type.name(), sample);
+        }
+        if let Err(e) = times.save() {
+            self.model
+                .log_warning(format!("Failed to save transition times: {}", e));
+        }
     }
 
     pub fn is_running_background(&self) -> bool {
@@ -1622,18 +2363,23 @@ impl Game {
         match msg {
             GameMessage::CharInput(c) => {
                 match self.model.screen {
-                    Screen::JoinGame => {
-                        if c.is_ascii_digit() {
-                            match self.model.join_game_field {
-                                JoinGameField::GameId => {
-                                    self.model.game_id_input.push(c);
-                                }
-                                JoinGameField::Password => {
-                                    self.model.password_input.push(c);
-                                }
+                    Screen::JoinGame => match self.model.join_game_field {
+                        JoinGameField::GameId => {
+                            if c.is_ascii_digit() {
+                                self.model.game_id_input.push(c);
                             }
                         }
-                    }
+                        JoinGameField::Password => {
+                            if c.is_ascii_digit() {
+                                self.model.password_input.push(c);
+                            }
+                        }
+                        JoinGameField::InviteCode => {
+                            if c.is_ascii_alphanumeric() || c == '-' {
+                                self.model.invite_input.push(c);
+                            }
+                        }
+                    },
                     Screen::CreateGame => match self.model.create_game_field {
                         CreateGameField::BuyIn => {
                             if c.is_ascii_digit() {
@@ -1656,11 +2402,38 @@ impl Game {
                             }
                         }
                     },
+                    Screen::InGame => {
+                        if let Some(betting_ui) = &mut self.model.betting_ui {
+                            if let Some(preset) = BetPreset::from_key(c) {
+                                betting_ui.apply_preset(preset);
+                            } else {
+                                betting_ui.push_amount_digit(c);
+                            }
+                        } else if c == 'r'
+                            && self.pending_command.is_none()
+                            && self.running_command.is_none()
+                            && let Some(command) = self.retry_command.take()
+                        {
+                            self.pending_command = Some(command);
+                        } else if let Some(action) = PreAction::from_key(c)
+                            && in_hand(&self.model)
+                        {
+                            self.toggle_pre_action(action);
+                        }
+                    }
                     _ => {}
                 }
                 None
             }
 
+            GameMessage::Paste(text) => {
+                if self.model.screen == Screen::JoinGame {
+                    self.model.join_game_field = JoinGameField::InviteCode;
+                    self.model.invite_input = text.split_whitespace().collect();
+                }
+                None
+            }
+
             GameMessage::Backspace => {
                 match self.model.screen {
                     Screen::JoinGame => match self.model.join_game_field {
@@ -1670,6 +2443,9 @@ impl Game {
                         JoinGameField::Password => {
                             self.model.password_input.pop();
                         }
+                        JoinGameField::InviteCode => {
+                            self.model.invite_input.pop();
+                        }
                     },
                     Screen::CreateGame => match self.model.create_game_field {
                         CreateGameField::BuyIn => {
@@ -1680,6 +2456,13 @@ impl Game {
                             self.model.password_input.pop();
                         }
                     },
+                    Screen::InGame => {
+                        if let Some(betting_ui) = &mut self.model.betting_ui {
+                            betting_ui.pop_amount_digit();
+                        }
+                    }
+                    Screen::Stats => self.model.screen = Screen::Menu,
+                    Screen::GameOver => self.leave_game(),
                     _ => {}
                 }
                 None
@@ -1690,8 +2473,8 @@ impl Game {
                     Screen::Menu => match self.model.selected_menu_option {
                         MenuOption::CreateGame => {
                             self.model.screen = Screen::CreateGame;
-                            self.model.buy_in_input = "100".to_string();
-                            self.model.blind_frequency = 3;
+                            self.model.buy_in_input = self.model.ui.buy_in.to_string();
+                            self.model.blind_frequency = self.model.ui.blind_frequency;
                             self.model.password_input.clear();
                             self.model.create_game_field = CreateGameField::BuyIn;
                         }
@@ -1699,58 +2482,33 @@ impl Game {
                             self.model.screen = Screen::JoinGame;
                             self.model.game_id_input.clear();
                             self.model.password_input.clear();
+                            self.model.invite_input.clear();
                             self.model.join_game_field = JoinGameField::GameId;
                         }
+                        MenuOption::Stats => self.model.screen = Screen::Stats,
                     },
-                    Screen::CreateGame => {
-                        self.model.screen = Screen::InGame;
-                        self.pending_command = Some(GameCommand::InitializeGame(0));
-                    }
+                    Screen::Stats => self.model.screen = Screen::Menu,
+                    Screen::GameOver => self.leave_game(),
+                    Screen::CreateGame => self.start_create_game(),
                     Screen::JoinGame => {
-                        if let Ok(id) = self.model.game_id_input.parse::<u32>() {
-                            self.model.game_id = Some(id);
-                            self.model.screen = Screen::InGame;
-
-                            let game_exists = self.reader().check_game_exists(id);
-                            if let Some(state) = self.reader().get_game_state(id) {
-                                match state {
-                                    0 | 1 => {
-                                        if self.reader().check_address_conflict(id) {
-                                            self.model.log(format!(
-                                                "Cannot join game {}: Your address is already a player in this game",
-                                                id
-                                            ));
-                                        } else {
-                                            self.pending_command = Some(GameCommand::JoinGame(id));
-                                        }
-                                    }
-                                    _ => {
-                                        self.model.log(format!(
-                                            "Spectating game {} (already started)",
-                                            id
-                                        ));
-                                        self.model.game_initialized = true;
-                                        self.pending_command =
-                                            Some(GameCommand::RefreshGameState(id));
-                                    }
+                        if self.model.join_game_field == JoinGameField::InviteCode {
+                            match self.model.invite_input.parse::<InviteCode>() {
+                                Ok(invite) => self.join_invite(&invite),
+                                Err(e) => {
+                                    self.model.log_error(format!("Invalid invite code: {}", e))
                                 }
-                            } else if !game_exists {
-                                self.model.log(format!("Game {} does not exist", id));
-                                self.model.screen = Screen::JoinGame;
                             }
+                        } else if let Ok(id) = self.model.game_id_input.parse::<u32>() {
+                            self.join_game_id(id);
                         }
                     }
                     Screen::InGame => {
-                        if let (Some(betting_ui), Some(game_id)) =
-                            (&self.model.betting_ui, self.model.game_id)
-                        {
+                        if let Some(betting_ui) = &self.model.betting_ui {
                             let action = betting_ui.selected_action;
                             let amount = betting_ui.raise_amount;
-                            self.pending_command = Some(GameCommand::PlaceBet {
-                                game_id,
-                                action,
-                                amount,
-                            });
+                            if let Err(e) = self.place_bet(action, amount) {
+                                self.model.log_error(e.to_string());
+                            }
                         }
                     }
                 }
@@ -1842,7 +2600,10 @@ impl Game {
             }
 
             GameMessage::Tick => {
+                self.check_game_over();
                 if self.model.game_initialized
+                    && (!self.model.is_game_over()
+                        || self.model.current_state == Some(GameState::Abandoned))
                     && self.model.should_poll()
                     && self.pending_command.is_none()
                     && let Some(game_id) = self.model.game_id
@@ -1857,6 +2618,39 @@ impl Game {
                 None
             }
 
+            GameMessage::LogPageUp => {
+                let visible = self.visible_log_count();
+                self.log_scroll = (self.log_scroll + LOG_PAGE).min(visible.saturating_sub(1));
+                None
+            }
+
+            GameMessage::LogPageDown => {
+                self.log_scroll = self.log_scroll.saturating_sub(LOG_PAGE);
+                None
+            }
+
+            GameMessage::LogEnd => {
+                self.log_scroll = 0;
+                None
+            }
+
+            GameMessage::CycleLogFilter => {
+                self.log_filter = self.log_filter.next();
+                self.log_scroll = 0;
+                None
+            }
+
+            GameMessage::ToggleTransactions => {
+                self.show_transactions = !self.show_transactions;
+                self.log_scroll = 0;
+                None
+            }
+
+            GameMessage::ExportTransactions => {
+                self.export_transactions();
+                None
+            }
+
             GameMessage::GameInitialized(result) => {
                 match result {
                     Ok(()) => {
@@ -1865,7 +2659,7 @@ impl Game {
                         self.pending_command = Some(GameCommand::SearchForGame);
                     }
                     Err(e) => {
-                        self.model.log(format!("Error initializing: {}", e));
+                        self.model.log_error(format!("Error initializing: {}", e));
                     }
                 }
                 None
@@ -1881,7 +2675,7 @@ impl Game {
                         }
                     }
                     Err(e) => {
-                        self.model.log(format!("Error joining: {}", e));
+                        self.model.log_error(format!("Error joining: {}", e));
                     }
                 }
                 None
@@ -1889,7 +2683,7 @@ impl Game {
 
             GameMessage::GameStatePolled(result) => {
                 if let Err(e) = result {
-                    self.model.log(format!("Error polling: {}", e));
+                    self.model.log_error(format!("Error polling: {}", e));
                 }
                 None
             }
@@ -1903,7 +2697,7 @@ impl Game {
                         }
                     }
                     Err(e) => {
-                        self.model.log(format!("Error placing bet: {}", e));
+                        self.model.log_error(format!("Error placing bet: {}", e));
                     }
                 }
                 None
@@ -1917,7 +2711,8 @@ impl Game {
                         }
                     }
                     Err(e) => {
-                        self.model.log(format!("Error comparing hands: {}", e));
+                        self.model
+                            .log_error(format!("Error comparing hands: {}", e));
                     }
                 }
                 None
@@ -1931,7 +2726,8 @@ impl Game {
                         }
                     }
                     Err(e) => {
-                        self.model.log(format!("Error shuffling new hand: {}", e));
+                        self.model
+                            .log_error(format!("Error shuffling new hand: {}", e));
                     }
                 }
                 None
@@ -1939,12 +2735,132 @@ impl Game {
         }
     }
 
+    pub fn create_game(&mut self, buy_in: f64, blind_frequency: u8, password: u128) {
+        self.model.buy_in_input = buy_in.to_string();
+        self.model.blind_frequency = blind_frequency;
+        self.model.password_input = if password == 0 {
+            String::new()
+        } else {
+            password.to_string()
+        };
+        self.start_create_game();
+    }
+
+    fn start_create_game(&mut self) {
+        self.model.screen = Screen::InGame;
+        self.pending_command = Some(GameCommand::InitializeGame(0));
+    }
+
+    pub fn join_game(&mut self, game_id: u32, password: u128) -> anyhow::Result<()> {
+        if !self.reader().check_game_exists(game_id) {
+            anyhow::bail!("Game {} does not exist", game_id);
+        }
+        self.model.game_id_input = game_id.to_string();
+        self.model.password_input = if password == 0 {
+            String::new()
+        } else {
+            password.to_string()
+        };
+        self.join_game_id(game_id);
+        Ok(())
+    }
+
+    pub fn place_bet(
+        &mut self,
+        action: crate::game_state::BettingAction,
+        amount: u64,
+    ) -> anyhow::Result<()> {
+        let game_id = self
+            .model
+            .game_id
+            .ok_or_else(|| anyhow::anyhow!("Not in a game"))?;
+        if self.model.betting_ui.is_none() {
+            anyhow::bail!("It is not your turn to bet");
+        }
+        if self.pending_command.is_some() || self.running_command.is_some() {
+            anyhow::bail!("Another action is still in progress");
+        }
+        self.pending_command = Some(GameCommand::PlaceBet {
+            game_id,
+            action,
+            amount,
+        });
+        self.pre_action = None;
+        Ok(())
+    }
+
+    pub fn join_invite(&mut self, invite: &InviteCode) {
+        if invite.network != self.model.network_type || invite.endpoint != self.model.endpoint {
+            self.model.log(format!(
+                "Invite is for {} at {}, but this client is on {} at {}",
+                invite.network.name(),
+                invite.endpoint,
+                self.model.network_type.name(),
+                self.model.endpoint
+            ));
+            return;
+        }
+
+        if let Err(e) = self.join_game(invite.game_id, invite.password) {
+            self.model.log_error(e.to_string());
+        }
+    }
+
+    fn join_game_id(&mut self, id: u32) {
+        self.model.game_id = Some(id);
+        self.model.screen = Screen::InGame;
+
+        let game_exists = self.reader().check_game_exists(id);
+        if let Some(state) = self.reader().get_game_state(id) {
+            match state {
+                0 | 1 => {
+                    if self.reader().check_address_conflict(id) {
+                        self.model.log_error(format!(
+                            "Cannot join game {}: Your address is already a player in this game",
+                            id
+                        ));
+                    } else {
+                        self.pending_command = Some(GameCommand::JoinGame(id));
+                    }
+                }
+                _ => {
+                    self.model
+                        .log(format!("Spectating game {} (already started)", id));
+                    self.model.game_initialized = true;
+                    self.pending_command = Some(GameCommand::RefreshGameState(id));
+                }
+            }
+        } else if !game_exists {
+            self.model.log_error(format!("Game {} does not exist", id));
+            self.model.screen = Screen::JoinGame;
+        }
+    }
+
     pub fn view(&self, frame: &mut Frame, area: Rect) {
         match self.model.screen {
             Screen::Menu => render_menu(frame, &self.model, area),
             Screen::CreateGame => render_create_game(frame, &self.model, area),
             Screen::JoinGame => render_join_game(frame, &self.model, area),
-            Screen::InGame => render_in_game(frame, &self.model, area),
+            Screen::Stats => render_stats(frame, self.stats.as_ref(), &self.address, area),
+            Screen::GameOver => {
+                if let Some(over) = &self.game_over {
+                    render_game_over(frame, over, &self.address, area);
+                }
+            }
+            Screen::InGame => {
+                let stats = self.stats.as_ref().and_then(|db| db.account(&self.address));
+                render_in_game(
+                    frame,
+                    &self.model,
+                    &self.history,
+                    self.pre_action(),
+                    stats,
+                    area,
+                );
+                if let Some(result) = &self.hand_result {
+                    render_hand_result(frame, result, area);
+                }
+            }
         }
     }
 
@@ -1952,59 +2868,181 @@ impl Game {
         self.model.should_quit
     }
 
+    fn visible_log_count(&self) -> usize {
+        if self.show_transactions {
+            return self.game_transactions().count();
+        }
+        self.model
+            .logs
+            .iter()
+            .filter(|entry| self.log_filter.matches(entry))
+            .count()
+    }
+
+    fn game_transactions(&self) -> impl Iterator<Item = &TxRecord> {
+        let game_id = self.model.game_id;
+        self.model
+            .transactions
+            .iter()
+            .filter(move |record| Some(record.game_id) == game_id)
+    }
+
+    fn export_transactions(&mut self) {
+        let Some(game_id) = self.model.game_id else {
+            self.model
+                .log_warning("No game to export transactions for".to_string());
+            return;
+        };
+        let path = export_path(game_id);
+        match crate::tx::export(&self.model.transactions, game_id, &path) {
+            Ok(count) => self.model.log(format!(
+                "Exported {} transactions to {}",
+                count,
+                path.display()
+            )),
+            Err(e) => self
+                .model
+                .log_error(format!("Failed to export transactions: {}", e)),
+        }
+    }
+
+    fn render_transactions(&self, frame: &mut Frame, area: Rect) {
+        let height = area.height.saturating_sub(2) as usize;
+        let records: Vec<_> = self.game_transactions().collect();
+        let end = records.len().saturating_sub(self.log_scroll);
+        let start = end.saturating_sub(height);
+
+        let items: Vec<ListItem> = records[start..end]
+            .iter()
+            .map(|record| {
+                let status_style = match record.status {
+                    TxStatus::Accepted => Style::default().fg(Color::Green),
+                    TxStatus::Rejected | TxStatus::Failed => Style::default().fg(Color::Red),
+                    _ => Style::default().fg(Color::Yellow),
+                };
+                let fee = record
+                    .fee
+                    .map_or("-".to_string(), |fee| Credits::from(fee).to_string());
+                let block = record
+                    .block_height
+                    .map_or("-".to_string(), |height| format!("#{}", height));
+                ListItem::new(Line::from(vec![
+                    Span::raw(format!(
+                        "{:<18} {:<28} ",
+                        record.transition.name(),
+                        record.inputs
+                    )),
+                    Span::styled(format!("{:<9} ", record.status.name()), status_style),
+                    Span::styled(
+                        format!(
+                            "{:>10} {:>9} {}",
+                            fee,
+                            block,
+                            record.id.as_deref().unwrap_or("-")
+                        ),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ]))
+            })
+            .collect();
+
+        let mut title = match self.model.game_id {
+            Some(game_id) => format!("Transactions [game {}]", game_id),
+            None => "Transactions".to_string(),
+        };
+        if end < records.len() {
+            title.push_str(&format!(" ↓{} newer", records.len() - end));
+        }
+        title.push_str(" · F3 logs · F4 export");
+
+        let list = List::new(items).block(Block::default().title(title).borders(Borders::ALL));
+        frame.render_widget(list, area);
+    }
+
     pub fn render_logs(&self, frame: &mut Frame, area: Rect) {
-        let items: Vec<ListItem> = self
+        if self.show_transactions {
+            self.render_transactions(frame, area);
+            return;
+        }
+        let height = area.height.saturating_sub(2) as usize;
+        let entries: Vec<_> = self
             .model
             .logs
             .iter()
-            .rev()
-            .take(6)
-            .rev()
-            .map(|log| ListItem::new(log.as_str()))
+            .filter(|entry| self.log_filter.matches(entry))
             .collect();
+        let end = entries.len().saturating_sub(self.log_scroll);
+        let start = end.saturating_sub(height);
+
+        let items: Vec<ListItem> = entries[start..end]
+            .iter()
+            .map(|entry| {
+                let style = match entry.level {
+                    LogLevel::Info => Style::default(),
+                    LogLevel::Warn => Style::default().fg(Color::Yellow),
+                    LogLevel::Error => Style::default().fg(Color::Red),
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("{} ", entry.time()),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::styled(entry.text(), style),
+                ]))
+            })
+            .collect();
+
+        let mut title = format!("Logs [{}]", self.log_filter.name());
+        if end < entries.len() {
+            title.push_str(&format!(" ↓{} newer", entries.len() - end));
+        }
+        title.push_str(" · PgUp/PgDn scroll · F2 filter · F3 transactions");
 
-        let list = List::new(items).block(Block::default().title("Logs").borders(Borders::ALL));
+        let list = List::new(items).block(Block::default().title(title).borders(Borders::ALL));
 
         frame.render_widget(list, area);
     }
 }
 
-fn format_card_span(card_index: u8) -> Span<'static> {
-    match card_info(card_index) {
-        CardInfo::FaceDown => Span::styled("???", Style::default().fg(Color::DarkGray)),
-        CardInfo::Invalid(idx) => {
-            Span::styled(format!("Err:{}", idx), Style::default().fg(Color::Yellow))
-        }
-        CardInfo::Valid {
-            suit,
-            value,
-            is_red,
-        } => {
-            let card_str = format!("{}{}", suit, value);
-            let style = if is_red {
-                Style::default().fg(Color::Red).bg(Color::Green)
-            } else {
-                Style::default().fg(Color::Black).bg(Color::Green)
-            };
-            Span::styled(card_str, style)
+fn format_card_span(card: Option<PlayingCard>, theme: CardTheme) -> Span<'static> {
+    match card {
+        None => Span::styled("???", Style::default().fg(Color::DarkGray)),
+        Some(card) => {
+            let face = theme.face(card);
+            let mut style = Style::default().fg(face.fg.into()).bg(face.bg.into());
+            if face.bold {
+                style = style.add_modifier(Modifier::BOLD);
+            }
+            Span::styled(face.text, style)
         }
     }
 }
 
 struct CommunityWidget {
-    flop: [u8; 3],
-    turn: u8,
-    river: u8,
+    flop: [Option<PlayingCard>; 3],
+    turn: Option<PlayingCard>,
+    river: Option<PlayingCard>,
     pot: u16,
+    pots: Vec<Pot>,
+    card_theme: CardTheme,
 }
 
 impl CommunityWidget {
-    fn new(flop: [u8; 3], turn: u8, river: u8, pot: u16) -> Self {
+    fn new(
+        flop: [Option<PlayingCard>; 3],
+        turn: Option<PlayingCard>,
+        river: Option<PlayingCard>,
+        pot: u16,
+        pots: Vec<Pot>,
+        card_theme: CardTheme,
+    ) -> Self {
         Self {
             flop,
             turn,
             river,
             pot,
+            pots,
+            card_theme,
         }
     }
 }
@@ -2012,15 +3050,15 @@ impl CommunityWidget {
 impl Widget for CommunityWidget {
     fn render(self, area: Rect, buf: &mut ratatui::prelude::Buffer) {
         let cards = vec![
-            format_card_span(self.flop[0]),
+            format_card_span(self.flop[0], self.card_theme),
             Span::raw(" "),
-            format_card_span(self.flop[1]),
+            format_card_span(self.flop[1], self.card_theme),
             Span::raw(" "),
-            format_card_span(self.flop[2]),
+            format_card_span(self.flop[2], self.card_theme),
             Span::raw(" "),
-            format_card_span(self.turn),
+            format_card_span(self.turn, self.card_theme),
             Span::raw(" "),
-            format_card_span(self.river),
+            format_card_span(self.river, self.card_theme),
         ];
 
         let cards_line = Line::from(cards);
@@ -2038,6 +3076,180 @@ impl Widget for CommunityWidget {
 
         let pot_line = Line::from(format!("Pot: {}", self.pot)).alignment(Alignment::Center);
         pot_line.render(layout[1], buf);
+
+        if self.pots.len() > 1 {
+            let breakdown = self
+                .pots
+                .iter()
+                .enumerate()
+                .map(|(i, pot)| {
+                    let name = if i == 0 {
+                        "Main".to_string()
+                    } else {
+                        format!("Side {}", i)
+                    };
+                    let eligible = pot
+                        .eligible
+                        .iter()
+                        .map(|p| format!("P{}", p))
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    format!("{}: {} ({})", name, pot.amount, eligible)
+                })
+                .collect::<Vec<_>>()
+                .join("  ·  ");
+            Line::from(breakdown)
+                .alignment(Alignment::Center)
+                .style(Style::default().fg(Color::Gray))
+                .render(layout[2], buf);
+        }
+    }
+}
+
+struct ActionTickerWidget<'a> {
+    history: &'a ActionHistory,
+}
+
+impl<'a> ActionTickerWidget<'a> {
+    fn new(history: &'a ActionHistory) -> Self {
+        Self { history }
+    }
+}
+
+impl<'a> Widget for ActionTickerWidget<'a> {
+    fn render(self, area: Rect, buf: &mut ratatui::prelude::Buffer) {
+        let streets = self.history.streets();
+        let skip = streets.len().saturating_sub(area.height as usize);
+        let lines: Vec<Line> = streets[skip..]
+            .iter()
+            .map(|(street, actions)| {
+                let actions: Vec<String> = actions.iter().map(|a| a.to_string()).collect();
+                Line::from(vec![
+                    Span::styled(
+                        format!("{}: ", street.name()),
+                        Style::default().fg(Color::Yellow),
+                    ),
+                    Span::raw(actions.join(", ")),
+                ])
+            })
+            .collect();
+        Paragraph::new(lines).render(area, buf);
+    }
+}
+
+/// Result of the last hand, shown over the table until the next deal.
+struct HandResultWidget<'a> {
+    result: &'a HandResult,
+}
+
+impl<'a> HandResultWidget<'a> {
+    fn new(result: &'a HandResult) -> Self {
+        Self { result }
+    }
+
+    fn lines(&self) -> Vec<Line<'static>> {
+        let mut lines = vec![
+            Line::styled(
+                self.result.headline(),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Line::raw(""),
+        ];
+        for (i, pot) in self.result.pots.iter().enumerate() {
+            let winners: Vec<String> = pot.winners.iter().map(|p| format!("P{}", p)).collect();
+            lines.push(Line::raw(format!(
+                "{} {} → {}",
+                HandResult::pot_name(i),
+                pot.amount,
+                winners.join(", ")
+            )));
+        }
+        lines.push(Line::raw(""));
+        for player in &self.result.players {
+            let style = if player.change > 0 {
+                Style::default().fg(Color::Green)
+            } else if player.change < 0 {
+                Style::default().fg(Color::Red)
+            } else {
+                Style::default()
+            };
+            let detail = match &player.hand {
+                _ if player.folded => "folded".to_string(),
+                Some(hand) => hand.label(),
+                None => String::new(),
+            };
+            lines.push(Line::from(vec![
+                Span::raw(format!("P{}  ", player.player)),
+                Span::styled(format!("{:>+5}", player.change), style),
+                Span::raw(format!("  {}", detail)),
+            ]));
+        }
+        lines
+    }
+}
+
+impl<'a> Widget for HandResultWidget<'a> {
+    fn render(self, area: Rect, buf: &mut ratatui::prelude::Buffer) {
+        let title = format!(" Hand {} result ", self.result.hand_number);
+        Paragraph::new(self.lines())
+            .block(Block::default().title(title).borders(Borders::ALL))
+            .render(area, buf);
+    }
+}
+
+fn render_hand_result(frame: &mut Frame, result: &HandResult, area: Rect) {
+    let widget = HandResultWidget::new(result);
+    let lines = widget.lines();
+    let content_width = lines.iter().map(|l| l.width()).max().unwrap_or(0) as u16;
+    let width = (content_width + 4).max(30).min(area.width);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let popup = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    };
+    frame.render_widget(Clear, popup);
+    frame.render_widget(widget, popup);
+}
+
+struct PreActionWidget {
+    selected: Option<PreAction>,
+}
+
+impl PreActionWidget {
+    fn new(selected: Option<PreAction>) -> Self {
+        Self { selected }
+    }
+}
+
+impl Widget for PreActionWidget {
+    fn render(self, area: Rect, buf: &mut ratatui::prelude::Buffer) {
+        let mut spans = Vec::new();
+        for action in PreAction::all() {
+            let style = if Some(action) == self.selected {
+                Style::default().fg(Color::Black).bg(Color::Yellow)
+            } else {
+                Style::default().fg(Color::Gray)
+            };
+            spans.push(Span::styled(
+                format!("[{}] {}", action.key(), action.name()),
+                style,
+            ));
+            spans.push(Span::raw("  "));
+        }
+        spans.pop();
+
+        Line::from(spans).alignment(Alignment::Center).render(
+            Rect {
+                y: area.y + area.height / 2,
+                height: 1,
+                ..area
+            },
+            buf,
+        );
     }
 }
 
@@ -2069,9 +3281,10 @@ impl<'a> Widget for BettingWidget<'a> {
             };
 
             let text = match action {
-                BettingAction::Raise => {
-                    format!("Raise ({})", self.betting_ui.raise_amount)
-                }
+                BettingAction::Raise => match &self.betting_ui.amount_input {
+                    Some(input) => format!("Raise ({}_ → {})", input, self.betting_ui.raise_amount),
+                    None => format!("Raise ({})", self.betting_ui.raise_amount),
+                },
                 BettingAction::Call => {
                     if self.betting_ui.call_amount == 0 {
                         "Check".to_string()
@@ -2099,18 +3312,55 @@ impl<'a> Widget for BettingWidget<'a> {
                 buf,
             );
         }
+
+        let hint_style = Style::default().fg(Color::Gray);
+        if self.betting_ui.selected_action == BettingAction::Raise {
+            let presets = self
+                .betting_ui
+                .presets()
+                .iter()
+                .map(|p| format!("{}:{}", p.key(), p.name()))
+                .collect::<Vec<_>>()
+                .join("  ");
+            Line::from(format!("{}  0-9: amount", presets))
+                .alignment(Alignment::Center)
+                .style(hint_style)
+                .render(Rect { height: 1, ..area }, buf);
+        }
+
+        if self.betting_ui.selected_action != BettingAction::Fold && area.height > 2 {
+            Line::from(format!(
+                "Total bet: {}  Stack left: {}",
+                self.betting_ui.total_bet(),
+                self.betting_ui.stack_after()
+            ))
+            .alignment(Alignment::Center)
+            .style(hint_style)
+            .render(
+                Rect {
+                    y: area.y + area.height - 1,
+                    height: 1,
+                    ..area
+                },
+                buf,
+            );
+        }
     }
 }
 
 struct PlayerWidget {
     player_id: u8,
-    cards: [u8; 2],
+    cards: [Option<PlayingCard>; 2],
     chips: u16,
     is_eliminated: bool,
     dealer_button: u8,
     is_active_turn: bool,
     is_proving: bool,
     proving_started_ms: Option<u64>,
+    proving_eta_ms: Option<u64>,
+    card_theme: CardTheme,
+    hand: Option<HandSummary>,
+    tendencies: Option<String>,
 }
 
 struct PlayerWidgetState {
@@ -2119,10 +3369,52 @@ struct PlayerWidgetState {
     is_active_turn: bool,
     is_proving: bool,
     proving_started_ms: Option<u64>,
+    proving_eta_ms: Option<u64>,
+    card_theme: CardTheme,
+    hand: Option<HandSummary>,
+    tendencies: Option<String>,
+}
+
+/// Label shown under a player's chips. At showdown it also carries the five
+/// cards that played, so the payout can be followed.
+struct HandSummary {
+    label: String,
+    best_five: Option<Vec<PlayingCard>>,
+}
+
+fn hand_summary(
+    hole: [Option<PlayingCard>; 2],
+    board: &[PlayingCard],
+    showdown: bool,
+) -> Option<HandSummary> {
+    let [Some(first), Some(second)] = hole else {
+        return None;
+    };
+    if showdown {
+        let cards: Vec<PlayingCard> = [first, second]
+            .into_iter()
+            .chain(board.iter().copied())
+            .collect();
+        let made = best_hand(&cards)?;
+        Some(HandSummary {
+            label: made.label(),
+            best_five: Some(made.cards),
+        })
+    } else {
+        hand_label(&[first, second], board).map(|label| HandSummary {
+            label,
+            best_five: None,
+        })
+    }
 }
 
 impl PlayerWidget {
-    fn new(player_id: u8, cards: [u8; 2], chips: u16, state: PlayerWidgetState) -> Self {
+    fn new(
+        player_id: u8,
+        cards: [Option<PlayingCard>; 2],
+        chips: u16,
+        state: PlayerWidgetState,
+    ) -> Self {
         Self {
             player_id,
             cards,
@@ -2132,6 +3424,10 @@ impl PlayerWidget {
             is_active_turn: state.is_active_turn,
             is_proving: state.is_proving,
             proving_started_ms: state.proving_started_ms,
+            proving_eta_ms: state.proving_eta_ms,
+            card_theme: state.card_theme,
+            hand: state.hand,
+            tendencies: state.tendencies,
         }
     }
 }
@@ -2146,7 +3442,14 @@ impl Widget for PlayerWidget {
             Style::default()
         };
 
-        let block = Block::default().borders(Borders::ALL).style(block_style);
+        let mut block = Block::default().borders(Borders::ALL).style(block_style);
+        if let Some(tendencies) = &self.tendencies {
+            block = block.title_bottom(
+                Line::from(format!(" {} ", tendencies))
+                    .alignment(Alignment::Right)
+                    .style(Style::default().fg(Color::DarkGray)),
+            );
+        }
         let inner = block.inner(area);
         block.render(area, buf);
 
@@ -2182,10 +3485,19 @@ impl Widget for PlayerWidget {
         );
         line_y += 1;
 
-        let card1 = format_card_span(self.cards[0]);
-        let card2 = format_card_span(self.cards[1]);
-        let cards_line =
-            Line::from(vec![card1, Span::raw(" "), card2]).alignment(Alignment::Center);
+        let card1 = format_card_span(self.cards[0], self.card_theme);
+        let card2 = format_card_span(self.cards[1], self.card_theme);
+        let mut spans = vec![card1, Span::raw(" "), card2];
+        if let Some(best_five) = self.hand.as_ref().and_then(|h| h.best_five.as_ref()) {
+            spans.push(Span::raw(" → "));
+            for (i, &card) in best_five.iter().enumerate() {
+                if i > 0 {
+                    spans.push(Span::raw(" "));
+                }
+                spans.push(format_card_span(Some(card), self.card_theme));
+            }
+        }
+        let cards_line = Line::from(spans).alignment(Alignment::Center);
 
         cards_line.render(
             Rect {
@@ -2217,6 +3529,25 @@ impl Widget for PlayerWidget {
             },
             buf,
         );
+        line_y += 1;
+
+        if let Some(hand) = &self.hand
+            && !self.is_proving
+            && line_y < inner.y + inner.height
+        {
+            let label_line = Line::from(hand.label.as_str())
+                .alignment(Alignment::Center)
+                .style(Style::default().fg(Color::Cyan));
+            label_line.render(
+                Rect {
+                    x: inner.x,
+                    y: line_y,
+                    width: inner.width.saturating_sub(1),
+                    height: 1,
+                },
+                buf,
+            );
+        }
 
         let player_bit = 1u8 << (self.player_id - 1);
         let is_dealer = (self.dealer_button & player_bit) != 0;
@@ -2229,7 +3560,7 @@ impl Widget for PlayerWidget {
         }
 
         if self.is_proving && inner.width > 0 && inner.height > 0 {
-            let proving_text = proving_indicator_text(self.proving_started_ms);
+            let proving_text = proving_indicator_text(self.proving_started_ms, self.proving_eta_ms);
             let shown = trim_to_width(&proving_text, inner.width as usize);
             let proving_style = Style::default()
                 .fg(Color::Yellow)
@@ -2240,15 +3571,35 @@ impl Widget for PlayerWidget {
     }
 }
 
-fn proving_indicator_text(started_ms: Option<u64>) -> String {
+/// With an estimate from earlier runs the indicator also shows how far along
+/// the call should be and roughly how long is left. On a network the
+/// estimate covers confirmation as well as the proof.
+fn proving_indicator_text(started_ms: Option<u64>, eta_ms: Option<u64>) -> String {
     const DOT_FRAMES: [&str; 4] = [".  ", " . ", "  .", "   "];
     let start_ms = started_ms.unwrap_or(0);
     let now_ms = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .map(|duration| duration.as_millis() as u64)
         .unwrap_or(0);
-    let frame = ((now_ms.saturating_sub(start_ms) / 250) % 4) as usize;
-    format!("Proving{}", DOT_FRAMES[frame])
+    let elapsed_ms = now_ms.saturating_sub(start_ms);
+    let frame = ((elapsed_ms / 250) % 4) as usize;
+    let Some(eta_ms) = eta_ms else {
+        return format!("Proving{}", DOT_FRAMES[frame]);
+    };
+    let (percent, remaining) = progress(
+        Duration::from_millis(elapsed_ms),
+        Duration::from_millis(eta_ms),
+    );
+    if remaining.is_zero() {
+        format!("Proving{} {}%", DOT_FRAMES[frame], percent)
+    } else {
+        format!(
+            "Proving{} {}% ~{}",
+            DOT_FRAMES[frame],
+            percent,
+            format_duration(remaining)
+        )
+    }
 }
 
 fn trim_to_width(text: &str, width: usize) -> &str {
@@ -2266,7 +3617,7 @@ fn render_menu(frame: &mut Frame, model: &GameModel, area: Rect) {
     frame.render_widget(block, area);
 
     let options = MenuOption::all();
-    let button_width = inner.width / 2;
+    let button_width = inner.width / options.len() as u16;
     let center_y = inner.y + inner.height / 2;
 
     for (i, option) in options.iter().enumerate() {
@@ -2291,6 +3642,112 @@ fn render_menu(frame: &mut Frame, model: &GameModel, area: Rect) {
     }
 }
 
+fn render_stats(frame: &mut Frame, db: Option<&StatsDb>, address: &str, area: Rect) {
+    let title = format!("Stats - {}", short_address(address));
+    let block = Block::default().title(title).borders(Borders::ALL);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let Some(db) = db else {
+        render_status(frame, "Stats are not recorded in this session", inner);
+        return;
+    };
+    let account = db.account(address).cloned().unwrap_or_default();
+
+    let header = format!(
+        "{:<16} {:>6} {:>5} {:>5} {:>5} {:>5} {:>7} {:>10}",
+        "Player", "Hands", "VPIP", "PFR", "AF", "WSD", "Won", "Net"
+    );
+    let row = |name: &str, stats: &crate::stats::PlayerStats| {
+        format!(
+            "{:<16} {:>6} {:>4}% {:>4}% {:>5.1} {:>4}% {:>7} {:>10}",
+            name,
+            stats.hands,
+            stats.vpip_pct(),
+            stats.pfr_pct(),
+            stats.aggression(),
+            stats.showdown_win_pct(),
+            format!("{}/{}", stats.tournaments_won, stats.tournaments),
+            stats.net_credits()
+        )
+    };
+
+    let mut lines = vec![
+        Line::styled(header, Style::default().add_modifier(Modifier::BOLD)),
+        Line::raw(row("You", &account.own)),
+    ];
+    let mut opponents: Vec<_> = account.opponents.iter().collect();
+    opponents.sort_by_key(|(_, stats)| std::cmp::Reverse(stats.hands));
+    for (opponent, stats) in opponents {
+        lines.push(Line::raw(row(&short_address(opponent), stats)));
+    }
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        "Enter to return",
+        Style::default().fg(Color::DarkGray),
+    ));
+
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+fn render_game_over(frame: &mut Frame, over: &GameOver, address: &str, area: Rect) {
+    let title = format!("Game Over - Game ID: {}", over.game_id);
+    let block = Block::default().title(title).borders(Borders::ALL);
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let mut lines = vec![
+        Line::styled(describe_game_state(over.state), bold),
+        Line::raw(""),
+    ];
+    match &over.winner {
+        Some((seat, winner)) => {
+            let you = if winner == address { " (you)" } else { "" };
+            lines.push(Line::raw(format!(
+                "Winner: P{} {}{}",
+                seat,
+                short_address(winner),
+                you
+            )));
+            lines.push(Line::raw(format!(
+                "Payout: {} credits",
+                Credits::from(over.payout)
+            )));
+        }
+        None if over.state == GameState::Abandoned => {
+            lines.push(Line::raw(format!(
+                "Buy-in: {} credits",
+                Credits::from(over.payout)
+            )));
+        }
+        None => {}
+    }
+    lines.push(Line::raw(format!("Hands played: {}", over.hands_played)));
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        "Enter to return to the menu",
+        Style::default().fg(Color::DarkGray),
+    ));
+
+    let height = (lines.len() as u16).min(inner.height);
+    let top = inner.y + inner.height.saturating_sub(height) / 2;
+    let area = Rect {
+        y: top,
+        height,
+        ..inner
+    };
+    frame.render_widget(Paragraph::new(lines).alignment(Alignment::Center), area);
+}
+
+fn short_address(address: &str) -> String {
+    if address.len() > 16 {
+        format!("{}…{}", &address[..9], &address[address.len() - 6..])
+    } else {
+        address.to_string()
+    }
+}
+
 fn render_create_game(frame: &mut Frame, model: &GameModel, area: Rect) {
     let title = "Create New Game";
     let block = Block::default().title(title).borders(Borders::ALL);
@@ -2437,6 +3894,33 @@ fn render_join_game(frame: &mut Frame, model: &GameModel, area: Rect) {
         .alignment(Alignment::Center)
         .style(password_style);
     password_line.render(password_area, frame.buffer_mut());
+
+    // Render Invite Code field
+    let invite_display = if model.invite_input.is_empty() {
+        "_".to_string()
+    } else {
+        model.invite_input.clone()
+    };
+
+    let invite_selected = matches!(model.join_game_field, JoinGameField::InviteCode);
+    let invite_style = if invite_selected {
+        Style::default().fg(Color::Black).bg(Color::Yellow)
+    } else {
+        Style::default().fg(Color::White)
+    };
+
+    let invite_area = Rect {
+        x: inner.x,
+        y: (center_y + 2).min(inner.y + inner.height.saturating_sub(1)),
+        width: inner.width,
+        height: 1,
+    };
+
+    let invite_text = format!("Invite Code: {}", invite_display);
+    let invite_line = Line::from(invite_text)
+        .alignment(Alignment::Center)
+        .style(invite_style);
+    invite_line.render(invite_area, frame.buffer_mut());
 }
 
 fn render_status(frame: &mut Frame, message: &str, area: Rect) {
@@ -2444,7 +3928,25 @@ fn render_status(frame: &mut Frame, message: &str, area: Rect) {
     frame.render_widget(paragraph, area);
 }
 
-fn render_in_game(frame: &mut Frame, model: &GameModel, area: Rect) {
+fn append_invite_code(content: &mut String, model: &GameModel) {
+    if let Some(invite) = &model.invite_code
+        && matches!(
+            model.current_state,
+            None | Some(GameState::P2Join) | Some(GameState::P3Join)
+        )
+    {
+        content.push_str(&format!("\n\nInvite code: {}", invite));
+    }
+}
+
+fn render_in_game(
+    frame: &mut Frame,
+    model: &GameModel,
+    history: &ActionHistory,
+    pre_action: Option<PreAction>,
+    stats: Option<&AccountStats>,
+    area: Rect,
+) {
     let game_id = model.game_id.unwrap_or(0);
     let title = format!("Poker - Game ID: {}", game_id);
     let block = Block::default().title(title).borders(Borders::ALL);
@@ -2452,12 +3954,13 @@ fn render_in_game(frame: &mut Frame, model: &GameModel, area: Rect) {
     frame.render_widget(block, area);
 
     if model.current_player_id == 0 || !model.game_initialized {
-        let content = if let Some(state) = model.current_state {
+        let mut content = if let Some(state) = model.current_state {
             let description = describe_game_state(state);
             format!("Current State: {}\n\n{}", state, description)
         } else {
             "Connecting to game...".to_string()
         };
+        append_invite_code(&mut content, model);
 
         render_status(frame, &content, inner);
         return;
@@ -2466,22 +3969,45 @@ fn render_in_game(frame: &mut Frame, model: &GameModel, area: Rect) {
     let (_cards, _chips) = match (model.card, model.chip) {
         (Some(c), Some(ch)) => (c, ch),
         _ => {
-            let content = if let Some(state) = model.current_state {
+            let mut content = if let Some(state) = model.current_state {
                 let description = describe_game_state(state);
                 format!("Current State: {}\n\n{}", state, description)
             } else {
                 "Waiting for game data...".to_string()
             };
+            append_invite_code(&mut content, model);
 
             render_status(frame, &content, inner);
             return;
         }
     };
 
-    render_game_table(frame, inner, model);
+    render_game_table(frame, inner, model, history, pre_action, stats);
+}
+
+/// Whether we are dealt into the current hand and can still act in it.
+fn in_hand(model: &GameModel) -> bool {
+    let player = model.current_player_id;
+    let Some(state) = model.current_state else {
+        return false;
+    };
+    if player == 0 || model.is_player_eliminated(player) {
+        return false;
+    }
+    let folded = model
+        .snapshot
+        .is_some_and(|s| s.players_folded & (1 << (player - 1)) != 0);
+    (2..=25).contains(&state.to_u8()) && !folded
 }
 
-fn render_game_table(frame: &mut Frame, area: Rect, model: &GameModel) {
+fn render_game_table(
+    frame: &mut Frame,
+    area: Rect,
+    model: &GameModel,
+    history: &ActionHistory,
+    pre_action: Option<PreAction>,
+    stats: Option<&AccountStats>,
+) {
     let cards = model.card.unwrap();
     let chips = model.chip.unwrap();
     let current_player = model.current_player_id;
@@ -2496,6 +4022,39 @@ fn render_game_table(frame: &mut Frame, area: Rect, model: &GameModel) {
     let current_bet_2 = chips.get_current_bet(opponent2);
     let current_bet_current = chips.get_current_bet(current_player);
 
+    let active = model
+        .snapshot
+        .map(|s| active_players(s.players_folded, s.players_out))
+        .unwrap_or([true; 3]);
+    let pots = side_pots(
+        [chips.player1_bet, chips.player2_bet, chips.player3_bet],
+        active,
+    );
+
+    let board: Vec<PlayingCard> = cards
+        .flop
+        .iter()
+        .chain([&cards.turn, &cards.river])
+        .flatten()
+        .copied()
+        .collect();
+    // Opponents' hole cards only become visible once hands are compared.
+    let showdown = board.len() == 5
+        && [opponent1, opponent2]
+            .iter()
+            .any(|&p| cards.get_cards(p).iter().all(Option::is_some));
+    let hand_for = |player: u8| {
+        if !active[player as usize - 1] || (player != current_player && !showdown) {
+            return None;
+        }
+        hand_summary(cards.get_cards(player), &board, showdown)
+    };
+
+    let tendencies = |player: u8| {
+        let address = &model.seats.as_ref()?[player as usize - 1];
+        Some(stats?.opponents.get(address)?.tendencies())
+    };
+
     let is_opponent1_eliminated = model.is_player_eliminated(opponent1);
     let is_opponent2_eliminated = model.is_player_eliminated(opponent2);
     let is_current_eliminated = model.is_player_eliminated(current_player);
@@ -2526,6 +4085,10 @@ fn render_game_table(frame: &mut Frame, area: Rect, model: &GameModel) {
                 is_active_turn: acting_player == Some(opponent1),
                 is_proving: false,
                 proving_started_ms: None,
+                proving_eta_ms: None,
+                card_theme: model.ui.cards,
+                hand: hand_for(opponent1),
+                tendencies: tendencies(opponent1),
             },
         ),
         top_layout[0],
@@ -2541,6 +4104,10 @@ fn render_game_table(frame: &mut Frame, area: Rect, model: &GameModel) {
                 is_active_turn: acting_player == Some(opponent2),
                 is_proving: false,
                 proving_started_ms: None,
+                proving_eta_ms: None,
+                card_theme: model.ui.cards,
+                hand: hand_for(opponent2),
+                tendencies: tendencies(opponent2),
             },
         ),
         top_layout[1],
@@ -2566,12 +4133,23 @@ fn render_game_table(frame: &mut Frame, area: Rect, model: &GameModel) {
         }
     }
 
-    let community_area = vertical_layout[3];
+    let community_layout = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+        .split(vertical_layout[3]);
 
     frame.render_widget(
-        CommunityWidget::new(cards.flop, cards.turn, cards.river, chips.pot),
-        community_area,
+        CommunityWidget::new(
+            cards.flop,
+            cards.turn,
+            cards.river,
+            chips.pot,
+            pots,
+            model.ui.cards,
+        ),
+        community_layout[0],
     );
+    frame.render_widget(ActionTickerWidget::new(history), community_layout[1]);
 
     let bottom_area = vertical_layout[4];
 
@@ -2613,6 +4191,8 @@ fn render_game_table(frame: &mut Frame, area: Rect, model: &GameModel) {
     }
     if let Some(betting_ui) = &model.betting_ui {
         frame.render_widget(BettingWidget::new(betting_ui), betting_area);
+    } else if in_hand(model) {
+        frame.render_widget(PreActionWidget::new(pre_action), betting_area);
     } else {
         frame.render_widget(Paragraph::new(""), betting_area);
     }
@@ -2627,6 +4207,10 @@ fn render_game_table(frame: &mut Frame, area: Rect, model: &GameModel) {
                 is_active_turn: acting_player == Some(current_player),
                 is_proving: is_proving_local,
                 proving_started_ms,
+                proving_eta_ms: model.background_task_eta_ms,
+                card_theme: model.ui.cards,
+                hand: hand_for(current_player),
+                tendencies: None,
             },
         ),
         player_area,
@@ -2643,6 +4227,98 @@ pub fn handle_game_key(key: KeyEvent) -> Option<GameMessage> {
         KeyCode::Right => Some(GameMessage::Right),
         KeyCode::Up => Some(GameMessage::Up),
         KeyCode::Down => Some(GameMessage::Down),
+        KeyCode::PageUp => Some(GameMessage::LogPageUp),
+        KeyCode::PageDown => Some(GameMessage::LogPageDown),
+        KeyCode::End => Some(GameMessage::LogEnd),
+        KeyCode::F(2) => Some(GameMessage::CycleLogFilter),
+        KeyCode::F(3) => Some(GameMessage::ToggleTransactions),
+        KeyCode::F(4) => Some(GameMessage::ExportTransactions),
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_state::BettingAction;
+
+    fn model_with(records: &[(Transition, TxStatus, Option<&str>)]) -> GameModel {
+        let mut model = GameModel::new(&Profile::builtin(NetworkType::Local));
+        for &(transition, status, error) in records {
+            let mut record = TxRecord::new(transition, 4, String::new());
+            record.status = status;
+            record.error = error.map(str::to_string);
+            model.transactions.push(record);
+        }
+        model
+    }
+
+    #[test]
+    fn test_settle() {
+        let failed = || Err(anyhow::anyhow!("timed out"));
+        assert_eq!(settle(Ok(()), &mut model_with(&[]), 0), Ok(()));
+        // A failure before anything was sent.
+        assert_eq!(
+            settle(failed(), &mut model_with(&[]), 0),
+            Err("timed out".to_string())
+        );
+        // A bet that landed anyway counts as done, with a warning.
+        let mut model = model_with(&[(Transition::Bet, TxStatus::Accepted, Some("timed out"))]);
+        assert_eq!(settle(failed(), &mut model, 0), Ok(()));
+        assert!(model.logs.last().unwrap().message.contains("bet landed"));
+        // Keys returned by a decryption that landed are gone.
+        let mut model = model_with(&[(
+            Transition::DecryptFlop,
+            TxStatus::Accepted,
+            Some("timed out"),
+        )]);
+        assert!(
+            settle(failed(), &mut model, 0)
+                .unwrap_err()
+                .contains("keys")
+        );
+        // Earlier commands' records are not this failure's.
+        let mut model = model_with(&[(Transition::Bet, TxStatus::Accepted, Some("timed out"))]);
+        assert_eq!(
+            settle(failed(), &mut model, 1),
+            Err("timed out".to_string())
+        );
+        let mut model = model_with(&[(Transition::Fold, TxStatus::Failed, Some("timed out"))]);
+        assert!(
+            settle(failed(), &mut model, 0)
+                .unwrap_err()
+                .contains("safe to prove it again")
+        );
+    }
+
+    #[test]
+    fn test_offer_retry() {
+        let compare = || GameCommand::AutoCompare(4);
+        let mut model = model_with(&[(Transition::CompareHands, TxStatus::Failed, Some("x"))]);
+        assert!(matches!(
+            offer_retry(&mut model, compare(), 0),
+            Some(GameCommand::AutoCompare(4))
+        ));
+        assert!(
+            model
+                .logs
+                .last()
+                .unwrap()
+                .message
+                .contains("prove compare_hands again")
+        );
+
+        // Not while the transaction may still land, nor for bets.
+        let mut model = model_with(&[(Transition::CompareHands, TxStatus::Pending, Some("x"))]);
+        assert!(offer_retry(&mut model, compare(), 0).is_none());
+        let mut model = model_with(&[(Transition::Bet, TxStatus::Rejected, None)]);
+        let bet = GameCommand::PlaceBet {
+            game_id: 4,
+            action: BettingAction::Call,
+            amount: 0,
+        };
+        assert!(offer_retry(&mut model, bet, 0).is_none());
+        // Nothing sent by this command.
+        assert!(offer_retry(&mut model, compare(), 1).is_none());
+    }
+}
@@ -1,3 +1,12 @@
+use crate::cards::PlayingCard;
+use crate::config::{Profile, UiSettings};
+use crate::events::GameSnapshot;
+use crate::leaderboard::{GameRecord, completed_game};
+use crate::logs::{LogBuffer, LogCategory, LogLevel, Progress};
+use crate::poll::poll_interval_ms;
+use crate::telemetry::TransitionSample;
+use crate::tx::TxRecord;
+use std::str::FromStr;
 use std::time::Instant;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -16,6 +25,16 @@ impl NetworkType {
         }
     }
 
+    /// The node's REST API for this network under `endpoint`. A local
+    /// devnet serves the testnet routes.
+    pub fn api(&self, endpoint: &str) -> String {
+        let network = match self {
+            NetworkType::Mainnet => "mainnet",
+            NetworkType::Local | NetworkType::Testnet => "testnet",
+        };
+        format!("{}/{}", endpoint.trim_end_matches('/'), network)
+    }
+
     pub fn poll_interval_ms(&self) -> u64 {
         match self {
             NetworkType::Local => 100,
@@ -25,12 +44,30 @@ impl NetworkType {
     }
 }
 
+impl FromStr for NetworkType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "local" => Ok(NetworkType::Local),
+            "testnet" => Ok(NetworkType::Testnet),
+            "mainnet" => Ok(NetworkType::Mainnet),
+            _ => anyhow::bail!(
+                "Invalid network '{}'. Must be 'local', 'testnet' or 'mainnet'",
+                s
+            ),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Screen {
     Menu,
     CreateGame,
     JoinGame,
     InGame,
+    Stats,
+    GameOver,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -54,13 +91,15 @@ impl CreateGameField {
 pub enum JoinGameField {
     GameId,
     Password,
+    InviteCode,
 }
 
 impl JoinGameField {
     pub fn next(&self) -> Self {
         match self {
             JoinGameField::GameId => JoinGameField::Password,
-            JoinGameField::Password => JoinGameField::GameId,
+            JoinGameField::Password => JoinGameField::InviteCode,
+            JoinGameField::InviteCode => JoinGameField::GameId,
         }
     }
 }
@@ -69,29 +108,36 @@ impl JoinGameField {
 pub enum MenuOption {
     CreateGame,
     JoinGame,
+    Stats,
 }
 
 impl MenuOption {
-    pub fn all() -> [Self; 2] {
-        [Self::CreateGame, Self::JoinGame]
+    pub fn all() -> [Self; 3] {
+        [Self::CreateGame, Self::JoinGame, Self::Stats]
     }
 
     pub fn name(&self) -> &'static str {
         match self {
             MenuOption::CreateGame => "Create Game",
             MenuOption::JoinGame => "Join Game",
+            MenuOption::Stats => "Stats",
         }
     }
 
     pub fn next(&self) -> Self {
         match self {
             MenuOption::CreateGame => MenuOption::JoinGame,
-            MenuOption::JoinGame => MenuOption::CreateGame,
+            MenuOption::JoinGame => MenuOption::Stats,
+            MenuOption::Stats => MenuOption::CreateGame,
         }
     }
 
     pub fn prev(&self) -> Self {
-        self.next()
+        match self {
+            MenuOption::CreateGame => MenuOption::Stats,
+            MenuOption::JoinGame => MenuOption::CreateGame,
+            MenuOption::Stats => MenuOption::JoinGame,
+        }
     }
 }
 
@@ -116,6 +162,127 @@ impl BettingAction {
     }
 }
 
+/// An action chosen before our turn and submitted as soon as it comes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreAction {
+    CheckFold,
+    Check,
+    CallAny,
+    FoldToBet,
+}
+
+impl PreAction {
+    pub fn all() -> [Self; 4] {
+        [Self::CheckFold, Self::Check, Self::CallAny, Self::FoldToBet]
+    }
+
+    pub fn from_key(c: char) -> Option<Self> {
+        match c {
+            '1' => Some(Self::CheckFold),
+            '2' => Some(Self::Check),
+            '3' => Some(Self::CallAny),
+            '4' => Some(Self::FoldToBet),
+            _ => None,
+        }
+    }
+
+    pub fn key(self) -> char {
+        match self {
+            Self::CheckFold => '1',
+            Self::Check => '2',
+            Self::CallAny => '3',
+            Self::FoldToBet => '4',
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::CheckFold => "Check/Fold",
+            Self::Check => "Check",
+            Self::CallAny => "Call any",
+            Self::FoldToBet => "Fold to any bet",
+        }
+    }
+
+    /// The bet to submit once it is our turn, or `None` to leave the
+    /// decision to the player.
+    pub fn resolve(self, call_amount: u64) -> Option<BettingAction> {
+        match (self, call_amount) {
+            (Self::CheckFold, 0) | (Self::Check, 0) | (Self::CallAny, _) => {
+                Some(BettingAction::Call)
+            }
+            (Self::CheckFold, _) | (Self::FoldToBet, 1..) => Some(BettingAction::Fold),
+            (Self::Check, _) | (Self::FoldToBet, 0) => None,
+        }
+    }
+}
+
+/// A pre-action together with the hand and bet it was chosen against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QueuedPreAction {
+    pub action: PreAction,
+    pub hands_played: u8,
+    pub highest_bet: u16,
+}
+
+impl QueuedPreAction {
+    /// A pre-action was chosen against one bet; once the bet changes the
+    /// player has to look again, whatever they queued.
+    pub fn is_stale(&self, hands_played: u8, highest_bet: u16) -> bool {
+        hands_played != self.hands_played || highest_bet != self.highest_bet
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BetPreset {
+    ThirdPot,
+    HalfPot,
+    ThreeQuarterPot,
+    Pot,
+    BigBlinds2_5,
+    BigBlinds3,
+    AllIn,
+}
+
+impl BetPreset {
+    pub fn from_key(c: char) -> Option<Self> {
+        match c {
+            't' => Some(Self::ThirdPot),
+            'h' => Some(Self::HalfPot),
+            'f' => Some(Self::ThreeQuarterPot),
+            'p' => Some(Self::Pot),
+            'b' => Some(Self::BigBlinds2_5),
+            'x' => Some(Self::BigBlinds3),
+            'a' => Some(Self::AllIn),
+            _ => None,
+        }
+    }
+
+    pub fn key(self) -> char {
+        match self {
+            Self::ThirdPot => 't',
+            Self::HalfPot => 'h',
+            Self::ThreeQuarterPot => 'f',
+            Self::Pot => 'p',
+            Self::BigBlinds2_5 => 'b',
+            Self::BigBlinds3 => 'x',
+            Self::AllIn => 'a',
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::ThirdPot => "1/3",
+            Self::HalfPot => "1/2",
+            Self::ThreeQuarterPot => "3/4",
+            Self::Pot => "pot",
+            Self::BigBlinds2_5 => "2.5BB",
+            Self::BigBlinds3 => "3BB",
+            Self::AllIn => "all-in",
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct BettingUIState {
     pub selected_action: BettingAction,
@@ -123,6 +290,11 @@ pub struct BettingUIState {
     pub call_amount: u64,
     pub min_raise: u64,
     pub max_raise: u64,
+    pub current_bet: u64,
+    pub pot: u64,
+    pub big_blind: u64,
+    pub preflop: bool,
+    pub amount_input: Option<String>,
 }
 
 impl BettingUIState {
@@ -133,9 +305,22 @@ impl BettingUIState {
             call_amount,
             min_raise,
             max_raise: player_chips,
+            current_bet: 0,
+            pot: 0,
+            big_blind: 0,
+            preflop: false,
+            amount_input: None,
         }
     }
 
+    pub fn with_table(mut self, current_bet: u64, pot: u64, big_blind: u64, preflop: bool) -> Self {
+        self.current_bet = current_bet;
+        self.pot = pot;
+        self.big_blind = big_blind;
+        self.preflop = preflop;
+        self
+    }
+
     pub fn select_next(&mut self) {
         self.selected_action = match self.selected_action {
             BettingAction::Fold => BettingAction::Call,
@@ -154,12 +339,14 @@ impl BettingUIState {
 
     pub fn increase_raise(&mut self) {
         if self.selected_action == BettingAction::Raise {
+            self.amount_input = None;
             self.raise_amount = (self.raise_amount + self.min_raise).min(self.max_raise);
         }
     }
 
     pub fn decrease_raise(&mut self) {
         if self.selected_action == BettingAction::Raise {
+            self.amount_input = None;
             self.raise_amount =
                 (self.raise_amount.saturating_sub(self.min_raise)).max(self.min_raise);
         }
@@ -167,9 +354,150 @@ impl BettingUIState {
 
     pub fn set_all_in(&mut self) {
         if self.selected_action == BettingAction::Raise {
+            self.amount_input = None;
             self.raise_amount = self.max_raise;
         }
     }
+
+    pub fn presets(&self) -> Vec<BetPreset> {
+        let mut presets = vec![
+            BetPreset::ThirdPot,
+            BetPreset::HalfPot,
+            BetPreset::ThreeQuarterPot,
+            BetPreset::Pot,
+        ];
+        if self.preflop {
+            presets.extend([BetPreset::BigBlinds2_5, BetPreset::BigBlinds3]);
+        }
+        presets.push(BetPreset::AllIn);
+        presets
+    }
+
+    /// Chips to put in for a preset. Pot fractions follow the usual
+    /// convention of calling first and then raising by that share of the
+    /// pot; blind multiples are the total bet to raise to.
+    pub fn preset_amount(&self, preset: BetPreset) -> u64 {
+        let pot_after_call = self.pot + self.call_amount;
+        let amount = match preset {
+            BetPreset::ThirdPot => self.call_amount + pot_after_call / 3,
+            BetPreset::HalfPot => self.call_amount + pot_after_call / 2,
+            BetPreset::ThreeQuarterPot => self.call_amount + pot_after_call * 3 / 4,
+            BetPreset::Pot => self.call_amount + pot_after_call,
+            BetPreset::BigBlinds2_5 => (self.big_blind * 5 / 2).saturating_sub(self.current_bet),
+            BetPreset::BigBlinds3 => (self.big_blind * 3).saturating_sub(self.current_bet),
+            BetPreset::AllIn => self.max_raise,
+        };
+        self.clamp_raise(amount)
+    }
+
+    pub fn apply_preset(&mut self, preset: BetPreset) -> bool {
+        if !self.presets().contains(&preset) {
+            return false;
+        }
+        self.selected_action = BettingAction::Raise;
+        self.amount_input = None;
+        self.raise_amount = self.preset_amount(preset);
+        true
+    }
+
+    pub fn push_amount_digit(&mut self, c: char) {
+        if !c.is_ascii_digit() {
+            return;
+        }
+        self.selected_action = BettingAction::Raise;
+        let input = self.amount_input.get_or_insert_with(String::new);
+        if input.len() < 5 {
+            input.push(c);
+        }
+        self.update_typed_amount();
+    }
+
+    pub fn pop_amount_digit(&mut self) {
+        if let Some(input) = &mut self.amount_input {
+            input.pop();
+            if input.is_empty() {
+                self.amount_input = None;
+            }
+        }
+        self.update_typed_amount();
+    }
+
+    fn update_typed_amount(&mut self) {
+        let typed = self
+            .amount_input
+            .as_deref()
+            .and_then(|input| input.parse::<u64>().ok())
+            .unwrap_or(self.min_raise);
+        self.raise_amount = self.clamp_raise(typed);
+    }
+
+    fn clamp_raise(&self, amount: u64) -> u64 {
+        // An all-in for less than a full raise is still allowed, so the
+        // upper bound wins when the two cross.
+        amount.max(self.min_raise).min(self.max_raise)
+    }
+
+    /// Chips this player would add with the selected action.
+    pub fn selected_amount(&self) -> u64 {
+        match self.selected_action {
+            BettingAction::Fold => 0,
+            BettingAction::Call => self.call_amount.min(self.max_raise),
+            BettingAction::Raise => self.raise_amount,
+        }
+    }
+
+    pub fn total_bet(&self) -> u64 {
+        self.current_bet + self.selected_amount()
+    }
+
+    pub fn stack_after(&self) -> u64 {
+        self.max_raise.saturating_sub(self.selected_amount())
+    }
+}
+
+/// What the game-over screen shows once a game reaches a terminal state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameOver {
+    pub game_id: u32,
+    pub state: crate::game::GameState,
+    /// Seat and address of the player who took the prize.
+    pub winner: Option<(u8, String)>,
+    /// Microcredits paid to the winner, or the buy-in of an abandoned game.
+    pub payout: u64,
+    pub hands_played: u8,
+}
+
+impl GameOver {
+    pub fn new(
+        game_id: u32,
+        state: crate::game::GameState,
+        snapshot: Option<&GameSnapshot>,
+        seats: Option<&[String; 3]>,
+    ) -> Self {
+        let snapshot = snapshot.filter(|s| s.game_id == game_id);
+        let winner = snapshot.zip(seats).and_then(|(snapshot, seats)| {
+            let record = GameRecord {
+                players: seats.clone(),
+                // The snapshot can predate the claim when this client
+                // submitted it, so trust `state` over it.
+                state: state.to_u8(),
+                buy_in: snapshot.buy_in,
+                hands_played: snapshot.hands_played,
+                chips: snapshot.chips.map(|c| [c.player1, c.player2, c.player3]),
+            };
+            let game = completed_game(game_id, &record)?;
+            let seat = seats.iter().position(|a| *a == game.winner)?;
+            Some((seat as u8 + 1, game.winner))
+        });
+        let buy_in = snapshot.map_or(0, |s| s.buy_in);
+        Self {
+            game_id,
+            state,
+            payout: if winner.is_some() { buy_in * 3 } else { buy_in },
+            winner,
+            hands_played: snapshot.map_or(0, |s| s.hands_played),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -178,22 +506,31 @@ pub struct GameModel {
     pub game_initialized: bool,
     pub last_poll_time: Instant,
     pub current_state: Option<crate::game::GameState>,
+    /// When `current_state` last changed.
+    pub state_since: Instant,
     pub current_player_id: u8,
 
     pub screen: Screen,
     pub selected_menu_option: MenuOption,
     pub game_id_input: String,
     pub password_input: String,
+    pub invite_input: String,
     pub buy_in_input: String,
     pub blind_frequency: u8,
     pub create_game_field: CreateGameField,
     pub join_game_field: JoinGameField,
-    pub logs: Vec<String>,
+    pub logs: LogBuffer,
 
     pub network_type: NetworkType,
+    pub endpoint: String,
+    pub poll_interval_ms: u64,
+    pub balance_reserve: u64,
+    pub abandon_after: std::time::Duration,
+    pub ui: UiSettings,
+    pub invite_code: Option<String>,
     pub should_quit: bool,
 
-    pub decrypted_hand: Option<[u8; 2]>,
+    pub decrypted_hand: Option<[PlayingCard; 2]>,
 
     pub card: Option<crate::game::Card>,
     pub chip: Option<crate::game::Chip>,
@@ -202,73 +539,179 @@ pub struct GameModel {
 
     pub last_known_game_id: u32,
 
+    pub snapshot: Option<crate::events::GameSnapshot>,
+    /// Addresses of P1-P3 in the current game.
+    pub seats: Option<[String; 3]>,
+
     pub eliminated_players: [bool; 3],
     pub game_winner: Option<u8>,
     pub dealer_button: u8,
     pub background_task: Option<String>,
     pub background_task_started_ms: Option<u64>,
+    /// Expected duration of the running task, from earlier runs.
+    pub background_task_eta_ms: Option<u64>,
+    /// Transitions timed since `Game` last collected them.
+    pub transition_samples: Vec<TransitionSample>,
+    /// Every transaction this client submitted, oldest first.
+    pub transactions: Vec<TxRecord>,
+    /// Polls that failed in a row; each one doubles the interval.
+    pub poll_failures: u32,
 }
 
 impl GameModel {
-    pub fn new(network_type: NetworkType) -> Self {
+    pub fn new(profile: &Profile) -> Self {
         let mut model = Self {
             game_id: None,
             game_initialized: false,
             last_poll_time: Instant::now(),
             current_state: None,
+            state_since: Instant::now(),
             current_player_id: 0,
             screen: Screen::Menu,
             selected_menu_option: MenuOption::CreateGame,
             game_id_input: String::new(),
             password_input: String::new(),
-            buy_in_input: "100".to_string(),
-            blind_frequency: 3,
+            invite_input: String::new(),
+            buy_in_input: profile.ui.buy_in.to_string(),
+            blind_frequency: profile.ui.blind_frequency,
             create_game_field: CreateGameField::BuyIn,
             join_game_field: JoinGameField::GameId,
-            logs: Vec::new(),
-            network_type,
+            logs: LogBuffer::default(),
+            network_type: profile.network,
+            endpoint: profile.endpoint.clone(),
+            poll_interval_ms: profile.poll_interval_ms,
+            balance_reserve: profile.balance_reserve,
+            abandon_after: profile.abandon_after,
+            ui: profile.ui,
+            invite_code: None,
             should_quit: false,
             decrypted_hand: None,
             card: None,
             chip: None,
             betting_ui: None,
             last_known_game_id: 0,
+            snapshot: None,
+            seats: None,
             eliminated_players: [false, false, false],
             game_winner: None,
             dealer_button: 0,
             background_task: None,
             background_task_started_ms: None,
+            background_task_eta_ms: None,
+            transition_samples: Vec::new(),
+            transactions: Vec::new(),
+            poll_failures: 0,
         };
-        model.log(format!("Starting poker with {}", network_type.name()));
+        model.log(format!(
+            "Starting poker with {} (profile '{}')",
+            profile.network.name(),
+            profile.name
+        ));
         model
     }
 
-    fn add_log(&mut self, message: String) {
-        self.logs.push(message);
-        if self.logs.len() > 100 {
-            self.logs.remove(0);
-        }
+    fn add_log(
+        &mut self,
+        level: LogLevel,
+        category: LogCategory,
+        message: String,
+        progress: Option<Progress>,
+    ) {
+        self.logs
+            .push(level, category, self.game_id, message, progress);
     }
 
     pub fn log(&mut self, message: String) {
-        self.add_log(message);
+        self.add_log(LogLevel::Info, LogCategory::General, message, None);
+    }
+
+    pub fn log_warning(&mut self, message: String) {
+        self.add_log(LogLevel::Warn, LogCategory::General, message, None);
+    }
+
+    pub fn log_error(&mut self, message: String) {
+        self.add_log(LogLevel::Error, LogCategory::General, message, None);
+    }
+
+    pub fn log_state(&mut self, message: String) {
+        self.add_log(LogLevel::Info, LogCategory::State, message, None);
+    }
+
+    pub fn log_bet(&mut self, message: String) {
+        self.add_log(LogLevel::Info, LogCategory::Bet, message, None);
     }
 
     pub fn log_action_start(&mut self, message: String) {
-        self.add_log(format!("⏳ {}", message));
+        self.add_log(
+            LogLevel::Info,
+            LogCategory::Proof,
+            message,
+            Some(Progress::Running),
+        );
+    }
+
+    pub fn log_bet_start(&mut self, message: String) {
+        self.add_log(
+            LogLevel::Info,
+            LogCategory::Bet,
+            message,
+            Some(Progress::Running),
+        );
     }
 
     pub fn log_action_complete(&mut self) {
-        if let Some(last) = self.logs.last_mut()
-            && last.starts_with("⏳ ")
-        {
-            *last = last.replace("⏳ ", "✓ ");
+        self.logs.complete_last();
+    }
+
+    pub fn password(&self) -> u128 {
+        if self.password_input.is_empty() {
+            0u128
+        } else {
+            self.password_input.parse::<u128>().unwrap_or(0u128)
         }
     }
 
     pub fn should_poll(&self) -> bool {
-        let interval_ms = self.network_type.poll_interval_ms();
-        self.last_poll_time.elapsed() >= std::time::Duration::from_millis(interval_ms)
+        self.last_poll_time.elapsed()
+            >= std::time::Duration::from_millis(self.current_poll_interval_ms())
+    }
+
+    /// The profile's poll interval, adapted to how close this player is to
+    /// acting and backed off while the node keeps failing.
+    pub fn current_poll_interval_ms(&self) -> u64 {
+        let (players_out, players_folded) = self
+            .snapshot
+            .map_or((0, 0), |s| (s.players_out, s.players_folded));
+        poll_interval_ms(
+            self.poll_interval_ms,
+            self.current_state,
+            self.current_player_id,
+            players_out,
+            players_folded,
+        ) << self.poll_failures.min(5)
+    }
+
+    pub fn is_game_over(&self) -> bool {
+        self.current_state.is_some_and(|state| state.is_terminal())
+    }
+
+    /// Forgets the current game so a new one can be created or joined.
+    pub fn leave_game(&mut self) {
+        self.game_id = None;
+        self.game_initialized = false;
+        self.current_state = None;
+        self.state_since = Instant::now();
+        self.invite_code = None;
+        self.decrypted_hand = None;
+        self.card = None;
+        self.chip = None;
+        self.betting_ui = None;
+        self.snapshot = None;
+        self.seats = None;
+        self.eliminated_players = [false, false, false];
+        self.game_winner = None;
+        self.dealer_button = 0;
+        self.screen = Screen::Menu;
     }
 
     pub fn update_eliminated_players(&mut self, players_out_bitmap: u8) {
@@ -348,5 +791,151 @@ pub fn describe_game_state(state: crate::game::GameState) -> &'static str {
         GameState::P1Claim => "Waiting for Player 1 to claim prize",
         GameState::P2Claim => "Waiting for Player 2 to claim prize",
         GameState::P3Claim => "Waiting for Player 3 to claim prize",
+        GameState::Abandoned => "Game abandoned before all players joined",
+        GameState::Completed => "Game over, prize claimed",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_network_api() {
+        assert_eq!(
+            NetworkType::Mainnet.api("https://api.explorer.provable.com/v1/"),
+            "https://api.explorer.provable.com/v1/mainnet"
+        );
+        assert_eq!(
+            NetworkType::Local.api("http://localhost:3030"),
+            "http://localhost:3030/testnet"
+        );
+    }
+
+    #[test]
+    fn test_pre_action_resolve() {
+        assert_eq!(PreAction::CheckFold.resolve(0), Some(BettingAction::Call));
+        assert_eq!(PreAction::CheckFold.resolve(20), Some(BettingAction::Fold));
+        assert_eq!(PreAction::Check.resolve(0), Some(BettingAction::Call));
+        assert_eq!(PreAction::Check.resolve(20), None);
+        assert_eq!(PreAction::CallAny.resolve(200), Some(BettingAction::Call));
+        assert_eq!(PreAction::FoldToBet.resolve(0), None);
+        assert_eq!(PreAction::FoldToBet.resolve(5), Some(BettingAction::Fold));
+    }
+
+    #[test]
+    fn test_pre_action_invalidation() {
+        let queued = |action| QueuedPreAction {
+            action,
+            hands_played: 2,
+            highest_bet: 10,
+        };
+        assert!(!queued(PreAction::Check).is_stale(2, 10));
+        assert!(queued(PreAction::Check).is_stale(2, 40));
+        assert!(queued(PreAction::CallAny).is_stale(2, 40));
+        assert!(queued(PreAction::FoldToBet).is_stale(2, 40));
+        assert!(!queued(PreAction::CheckFold).is_stale(2, 10));
+        assert!(queued(PreAction::CallAny).is_stale(3, 10));
+    }
+
+    fn betting(call_amount: u64, min_raise: u64, pot: u64) -> BettingUIState {
+        BettingUIState::new(500, call_amount, min_raise).with_table(10, pot, 20, true)
+    }
+
+    #[test]
+    fn test_pot_presets() {
+        let ui = betting(10, 30, 60);
+        assert_eq!(ui.preset_amount(BetPreset::HalfPot), 10 + 35);
+        assert_eq!(ui.preset_amount(BetPreset::Pot), 10 + 70);
+        assert_eq!(ui.preset_amount(BetPreset::ThirdPot), 10 + 23);
+        assert_eq!(ui.preset_amount(BetPreset::AllIn), 500);
+    }
+
+    #[test]
+    fn test_presets_are_clamped() {
+        let ui = betting(10, 50, 20);
+        assert_eq!(ui.preset_amount(BetPreset::ThirdPot), 50);
+
+        let short = BettingUIState::new(40, 10, 30).with_table(10, 600, 20, false);
+        assert_eq!(short.preset_amount(BetPreset::Pot), 40);
+    }
+
+    #[test]
+    fn test_blind_presets_only_preflop() {
+        let mut ui = betting(10, 30, 60);
+        assert_eq!(ui.preset_amount(BetPreset::BigBlinds2_5), 40);
+        assert_eq!(ui.preset_amount(BetPreset::BigBlinds3), 50);
+        assert!(ui.apply_preset(BetPreset::BigBlinds3));
+        assert_eq!(ui.selected_action, BettingAction::Raise);
+        assert_eq!(ui.total_bet(), 60);
+
+        let mut flop = BettingUIState::new(500, 0, 20).with_table(0, 60, 20, false);
+        assert!(!flop.apply_preset(BetPreset::BigBlinds3));
+        assert_eq!(flop.selected_action, BettingAction::Call);
+    }
+
+    #[test]
+    fn test_typed_amount_is_clamped() {
+        let mut ui = betting(10, 30, 60);
+        ui.push_amount_digit('5');
+        assert_eq!(ui.raise_amount, 30);
+        ui.push_amount_digit('5');
+        assert_eq!(ui.raise_amount, 55);
+        assert_eq!(ui.total_bet(), 65);
+        assert_eq!(ui.stack_after(), 445);
+        ui.push_amount_digit('0');
+        assert_eq!(ui.raise_amount, 500);
+        ui.pop_amount_digit();
+        ui.pop_amount_digit();
+        ui.pop_amount_digit();
+        assert_eq!(ui.amount_input, None);
+        assert_eq!(ui.raise_amount, 30);
+    }
+
+    #[test]
+    fn test_game_over_winner_and_payout() {
+        use crate::events::snapshot;
+        use crate::game::GameState;
+        let snapshot = GameSnapshot {
+            game_id: 4,
+            buy_in: 2_000_000,
+            players_out: 7,
+            sb: 20,
+            bb: 40,
+            ..snapshot(255, 17, [0, 3000, 0], [0, 0, 0], 7)
+        };
+        let seats = ["aleo1a", "aleo1b", "aleo1c"].map(String::from);
+
+        let claiming = GameSnapshot {
+            state: 35,
+            players_out: 5,
+            players_folded: 0,
+            ..snapshot
+        };
+        for snapshot in [&snapshot, &claiming] {
+            let over = GameOver::new(4, GameState::Completed, Some(snapshot), Some(&seats));
+            assert_eq!(over.winner, Some((2, "aleo1b".to_string())));
+        }
+        let over = GameOver::new(4, GameState::Completed, Some(&snapshot), Some(&seats));
+        assert_eq!((over.payout, over.hands_played), (6_000_000, 17));
+
+        let waiting = GameSnapshot {
+            state: 1,
+            hands_played: 0,
+            ..snapshot
+        };
+        let over = GameOver::new(4, GameState::Abandoned, Some(&waiting), Some(&seats));
+        assert_eq!((over.winner, over.payout), (None, 2_000_000));
+    }
+
+    #[test]
+    fn test_poll_backoff() {
+        let mut model = GameModel::new(&Profile::builtin(NetworkType::Local));
+        model.poll_interval_ms = 1000;
+        assert_eq!(model.current_poll_interval_ms(), 1000);
+        model.poll_failures = 2;
+        assert_eq!(model.current_poll_interval_ms(), 4000);
+        model.poll_failures = 40;
+        assert_eq!(model.current_poll_interval_ms(), 32_000);
     }
 }
@@ -0,0 +1,632 @@
+use crate::cards::{PlayingCard, Rank, Suit};
+use std::cmp::{Ordering, Reverse};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HandCategory {
+    HighCard,
+    Pair,
+    TwoPair,
+    ThreeOfAKind,
+    Straight,
+    Flush,
+    FullHouse,
+    FourOfAKind,
+    StraightFlush,
+}
+
+impl HandCategory {
+    pub fn name(self) -> &'static str {
+        match self {
+            HandCategory::HighCard => "High Card",
+            HandCategory::Pair => "Pair",
+            HandCategory::TwoPair => "Two Pair",
+            HandCategory::ThreeOfAKind => "Three of a Kind",
+            HandCategory::Straight => "Straight",
+            HandCategory::Flush => "Flush",
+            HandCategory::FullHouse => "Full House",
+            HandCategory::FourOfAKind => "Four of a Kind",
+            HandCategory::StraightFlush => "Straight Flush",
+        }
+    }
+}
+
+/// The best hand made from up to five cards. `cards` are ordered by
+/// importance (the pair first in a one-pair hand, the five first in a wheel)
+/// and `kickers` holds the ranks that break ties within the category.
+/// Hands compare by strength only, so a split pot is two equal hands.
+#[derive(Debug, Clone)]
+pub struct MadeHand {
+    pub category: HandCategory,
+    pub kickers: Vec<Rank>,
+    pub cards: Vec<PlayingCard>,
+}
+
+impl PartialEq for MadeHand {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for MadeHand {}
+
+impl Ord for MadeHand {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.category
+            .cmp(&other.category)
+            .then_with(|| self.kickers.cmp(&other.kickers))
+    }
+}
+
+impl PartialOrd for MadeHand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl MadeHand {
+    pub fn label(&self) -> String {
+        let first = self.kickers[0];
+        match self.category {
+            HandCategory::HighCard => format!("High Card, {}", first.name()),
+            HandCategory::Pair => format!("Pair of {}", first.plural()),
+            HandCategory::TwoPair => {
+                format!(
+                    "Two Pair, {} and {}",
+                    first.plural(),
+                    self.kickers[1].plural()
+                )
+            }
+            HandCategory::ThreeOfAKind => format!("Three of a Kind, {}", first.plural()),
+            HandCategory::Straight => format!("Straight, {} high", first.name()),
+            HandCategory::Flush => format!("Flush, {} high", first.name()),
+            HandCategory::FullHouse => {
+                format!(
+                    "Full House, {} over {}",
+                    first.plural(),
+                    self.kickers[1].plural()
+                )
+            }
+            HandCategory::FourOfAKind => format!("Four of a Kind, {}", first.plural()),
+            HandCategory::StraightFlush if first == Rank::Ace => "Royal Flush".to_string(),
+            HandCategory::StraightFlush => format!("Straight Flush, {} high", first.name()),
+        }
+    }
+}
+
+/// Best five-card hand out of `cards` (hole cards plus board). With all
+/// seven cards the hand is read from [`evaluate_hand`], so it matches the
+/// contract; with fewer, as before the river, it is the client's own
+/// estimate, and before the flop only pairs and sets can be made.
+pub fn best_hand(cards: &[PlayingCard]) -> Option<MadeHand> {
+    if let Ok(seven) = <&[PlayingCard; 7]>::try_from(cards) {
+        return Some(from_value(evaluate_hand(seven), seven));
+    }
+    if cards.len() <= 5 {
+        return (!cards.is_empty()).then(|| rank_cards(cards));
+    }
+    (0u32..1 << cards.len())
+        .filter(|mask| mask.count_ones() == 5)
+        .map(|mask| {
+            let five: Vec<PlayingCard> = (0..cards.len())
+                .filter(|i| mask & (1 << i) != 0)
+                .map(|i| cards[i])
+                .collect();
+            rank_cards(&five)
+        })
+        .max()
+}
+
+/// The hand value `evaluate_hand` in the contract computes: the category
+/// in the top nibble and tie-breaking ranks below it, so a higher value
+/// wins. This is a line-by-line port and keeps the contract's quirks:
+/// two sets count as three of a kind, and with three pairs the second
+/// pair is the lowest one.
+pub fn evaluate_hand(cards: &[PlayingCard; 7]) -> u32 {
+    let mut sorted = *cards;
+    sorted.sort_by_key(|card| card.rank);
+    let r = sorted.map(|card| card.rank.index());
+    let s = sorted.map(|card| card.suit.index());
+
+    let eq = |i: usize| r[i] == r[i + 1];
+    let (eq_12, eq_23, eq_34, eq_45, eq_56, eq_67) = (eq(0), eq(1), eq(2), eq(3), eq(4), eq(5));
+
+    let quad_1234 = eq_12 && eq_23 && eq_34;
+    let quad_2345 = eq_23 && eq_34 && eq_45;
+    let quad_3456 = eq_34 && eq_45 && eq_56;
+    let quad_4567 = eq_45 && eq_56 && eq_67;
+    let has_quads = quad_1234 || quad_2345 || quad_3456 || quad_4567;
+    let quad_rank = if quad_4567 {
+        r[6]
+    } else if quad_3456 {
+        r[5]
+    } else if quad_2345 {
+        r[4]
+    } else {
+        r[3]
+    };
+    let quad_kicker = if quad_4567 { r[2] } else { r[6] };
+
+    let trip_123 = eq_12 && eq_23;
+    let trip_234 = eq_23 && eq_34;
+    let trip_345 = eq_34 && eq_45;
+    let trip_456 = eq_45 && eq_56;
+    let trip_567 = eq_56 && eq_67;
+    let has_trips = trip_123 || trip_234 || trip_345 || trip_456 || trip_567;
+    let trip_rank = if trip_567 {
+        r[6]
+    } else if trip_456 {
+        r[5]
+    } else if trip_345 {
+        r[4]
+    } else if trip_234 {
+        r[3]
+    } else {
+        r[2]
+    };
+
+    let pair_12 = eq_12 && !trip_123;
+    let pair_23 = eq_23 && !trip_123 && !trip_234;
+    let pair_34 = eq_34 && !trip_234 && !trip_345;
+    let pair_45 = eq_45 && !trip_345 && !trip_456;
+    let pair_56 = eq_56 && !trip_456 && !trip_567;
+    let pair_67 = eq_67 && !trip_567;
+    let pairs = [pair_12, pair_23, pair_34, pair_45, pair_56, pair_67];
+    let has_pair = pairs.contains(&true);
+    let pair_rank = if pair_67 {
+        r[6]
+    } else if pair_56 {
+        r[5]
+    } else if pair_45 {
+        r[4]
+    } else if pair_34 {
+        r[3]
+    } else if pair_23 {
+        r[2]
+    } else {
+        r[1]
+    };
+
+    let has_fh = has_trips && has_pair;
+    let trips_only = has_trips && !has_pair;
+    let pair_only = has_pair && !has_trips;
+
+    let suit_count = |suit: u8| s.iter().filter(|&&x| x == suit).count() as u8;
+    let flush_suit = (0..4u8).rev().find(|&suit| suit_count(suit) >= 5);
+    let has_flush = flush_suit.is_some();
+    let flush_suit = flush_suit.unwrap_or(0);
+    let flush_count = suit_count(flush_suit);
+
+    let hand_bitfield = r.iter().fold(0u32, |bits, &rank| bits | 1 << rank);
+    let mut flush_bitfield = (0..7)
+        .filter(|&i| s[i] == flush_suit)
+        .fold(0u32, |bits, i| bits | 1 << r[i]);
+
+    // Room for a low ace at bit 0, and the flush suit's cards 16 bits up.
+    let combined_bitfield = ((flush_bitfield << 16) | hand_bitfield) << 1;
+    let ace_low_bits = (combined_bitfield >> 13) & ((1 << 16) | 1);
+    let bitfield = combined_bitfield | ace_low_bits;
+
+    let mut straight_high =
+        bitfield & bitfield >> 1 & bitfield >> 2 & bitfield >> 3 & bitfield >> 4;
+    straight_high ^= straight_high & (straight_high >> 1);
+    let sf_high = straight_high >> 16;
+    let has_straight = straight_high != 0;
+    let has_sf = sf_high != 0;
+
+    if flush_count == 7 {
+        flush_bitfield &= flush_bitfield.wrapping_sub(1);
+    }
+    if flush_count >= 6 {
+        flush_bitfield &= flush_bitfield.wrapping_sub(1);
+    }
+
+    let has_two_pair = pairs.iter().filter(|&&pair| pair).count() >= 2;
+    let second_pair_rank = if pair_56 && !pair_67 {
+        r[5]
+    } else if pair_45 && !pair_56 && !pair_67 {
+        r[4]
+    } else if pair_34 {
+        r[3]
+    } else if pair_23 {
+        r[2]
+    } else {
+        r[1]
+    };
+    let two_pair_kicker = if !pair_67 {
+        r[6]
+    } else if !pair_56 {
+        r[5]
+    } else if !pair_45 {
+        r[4]
+    } else if !pair_34 {
+        r[3]
+    } else if !pair_23 {
+        r[2]
+    } else {
+        r[1]
+    };
+
+    let trips_kicker1 = if trip_567 { r[3] } else { r[6] };
+    let trips_kicker2 = if trip_567 || trip_456 { r[2] } else { r[5] };
+
+    let pair_kicker1 = if pair_67 { r[4] } else { r[6] };
+    let pair_kicker2 = if pair_67 || pair_56 { r[3] } else { r[5] };
+    let pair_kicker3 = if pair_67 || pair_56 || pair_45 {
+        r[2]
+    } else {
+        r[4]
+    };
+
+    let category = |category: HandCategory| (category as u32 + 1) << CATEGORY_SHIFT;
+    let nibbles = |ranks: &[u8]| {
+        ranks
+            .iter()
+            .fold(0u32, |value, &rank| value << 4 | rank as u32)
+    };
+    if has_sf {
+        category(HandCategory::StraightFlush) | sf_high
+    } else if has_quads {
+        category(HandCategory::FourOfAKind) | nibbles(&[quad_rank, quad_kicker])
+    } else if has_fh {
+        category(HandCategory::FullHouse) | nibbles(&[trip_rank, pair_rank])
+    } else if has_flush {
+        category(HandCategory::Flush) | flush_bitfield
+    } else if has_straight {
+        category(HandCategory::Straight) | straight_high
+    } else if trips_only {
+        category(HandCategory::ThreeOfAKind) | nibbles(&[trip_rank, trips_kicker1, trips_kicker2])
+    } else if has_two_pair {
+        category(HandCategory::TwoPair) | nibbles(&[pair_rank, second_pair_rank, two_pair_kicker])
+    } else if pair_only {
+        category(HandCategory::Pair)
+            | nibbles(&[pair_rank, pair_kicker1, pair_kicker2, pair_kicker3])
+    } else {
+        category(HandCategory::HighCard) | nibbles(&[r[6], r[5], r[4], r[3], r[2]])
+    }
+}
+
+const CATEGORY_SHIFT: u32 = 28;
+
+/// Reads the category and ranks back out of an `evaluate_hand` value and
+/// picks the cards from `cards` that make them.
+fn from_value(value: u32, cards: &[PlayingCard; 7]) -> MadeHand {
+    let category = [
+        HandCategory::HighCard,
+        HandCategory::Pair,
+        HandCategory::TwoPair,
+        HandCategory::ThreeOfAKind,
+        HandCategory::Straight,
+        HandCategory::Flush,
+        HandCategory::FullHouse,
+        HandCategory::FourOfAKind,
+        HandCategory::StraightFlush,
+    ][(value >> CATEGORY_SHIFT) as usize - 1];
+    let nibbles = |count: u32| -> Vec<Rank> {
+        (0..count)
+            .rev()
+            .map(|i| Rank::ALL[(value >> (4 * i) & 0xf) as usize])
+            .collect()
+    };
+    let kickers = match category {
+        HandCategory::Straight | HandCategory::StraightFlush => {
+            // Bit n is set for a straight from rank index n - 1 (the ace
+            // at bit 0) up to n + 3.
+            let low = (value & 0xffff).trailing_zeros() as usize;
+            vec![Rank::ALL[low + 3]]
+        }
+        HandCategory::Flush => Rank::ALL
+            .iter()
+            .rev()
+            .copied()
+            .filter(|rank| value & 1 << rank.index() != 0)
+            .collect(),
+        HandCategory::FourOfAKind | HandCategory::FullHouse => nibbles(2),
+        HandCategory::ThreeOfAKind | HandCategory::TwoPair => nibbles(3),
+        HandCategory::Pair => nibbles(4),
+        HandCategory::HighCard => nibbles(5),
+    };
+
+    let wanted: Vec<(Rank, usize)> = match category {
+        HandCategory::Straight | HandCategory::StraightFlush => (0..5)
+            .map(|below| {
+                let index = (kickers[0].index() as usize + 13 - below) % 13;
+                (Rank::ALL[index], 1)
+            })
+            .collect(),
+        _ => {
+            let counts: &[usize] = match category {
+                HandCategory::FourOfAKind => &[4, 1],
+                HandCategory::FullHouse => &[3, 2],
+                HandCategory::ThreeOfAKind => &[3, 1, 1],
+                HandCategory::TwoPair => &[2, 2, 1],
+                HandCategory::Pair => &[2, 1, 1, 1],
+                _ => &[1; 5],
+            };
+            kickers
+                .iter()
+                .copied()
+                .zip(counts.iter().copied())
+                .collect()
+        }
+    };
+    let suit = matches!(category, HandCategory::Flush | HandCategory::StraightFlush)
+        .then(|| {
+            Suit::ALL
+                .iter()
+                .rev()
+                .copied()
+                .find(|&suit| cards.iter().filter(|c| c.suit == suit).count() >= 5)
+        })
+        .flatten();
+
+    let mut pool = cards.to_vec();
+    pool.sort_by_key(|card| Reverse(card.rank));
+    let mut picked = Vec::with_capacity(5);
+    for (rank, count) in wanted {
+        for _ in 0..count {
+            if let Some(i) = pool
+                .iter()
+                .position(|c| c.rank == rank && suit.is_none_or(|suit| c.suit == suit))
+            {
+                picked.push(pool.remove(i));
+            }
+        }
+    }
+
+    MadeHand {
+        category,
+        kickers,
+        cards: picked,
+    }
+}
+
+fn rank_cards(cards: &[PlayingCard]) -> MadeHand {
+    let mut sorted = cards.to_vec();
+    sorted.sort_by_key(|card| Reverse(card.rank));
+
+    let mut groups: Vec<(usize, Rank)> = Vec::new();
+    for card in &sorted {
+        match groups.iter_mut().find(|(_, rank)| *rank == card.rank) {
+            Some((count, _)) => *count += 1,
+            None => groups.push((1, card.rank)),
+        }
+    }
+    groups.sort_by_key(|&group| Reverse(group));
+
+    let is_flush = cards.len() == 5 && cards.iter().all(|c| c.suit == cards[0].suit);
+    let straight_high = if cards.len() == 5 && groups.len() == 5 {
+        let ranks: Vec<Rank> = sorted.iter().map(|c| c.rank).collect();
+        if ranks[0].index() - ranks[4].index() == 4 {
+            Some(ranks[0])
+        } else if ranks == [Rank::Ace, Rank::Five, Rank::Four, Rank::Three, Rank::Two] {
+            sorted.rotate_left(1);
+            Some(Rank::Five)
+        } else {
+            None
+        }
+    } else {
+        None
+    };
+
+    let counts: Vec<usize> = groups.iter().map(|(count, _)| *count).collect();
+    let category = match (straight_high, is_flush, counts.as_slice()) {
+        (Some(_), true, _) => HandCategory::StraightFlush,
+        (_, _, [4, ..]) => HandCategory::FourOfAKind,
+        (_, _, [3, 2]) => HandCategory::FullHouse,
+        (_, true, _) => HandCategory::Flush,
+        (Some(_), _, _) => HandCategory::Straight,
+        (_, _, [3, ..]) => HandCategory::ThreeOfAKind,
+        (_, _, [2, 2, ..]) => HandCategory::TwoPair,
+        (_, _, [2, ..]) => HandCategory::Pair,
+        _ => HandCategory::HighCard,
+    };
+
+    let (kickers, cards) = match straight_high {
+        Some(high) => (vec![high], sorted),
+        None => {
+            let kickers: Vec<Rank> = groups.iter().map(|(_, rank)| *rank).collect();
+            let cards = kickers
+                .iter()
+                .flat_map(|&rank| sorted.iter().copied().filter(move |c| c.rank == rank))
+                .collect();
+            (kickers, cards)
+        }
+    };
+
+    MadeHand {
+        category,
+        kickers,
+        cards,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Draw {
+    Flush,
+    Straight,
+    FlushAndStraight,
+}
+
+impl Draw {
+    pub fn name(self) -> &'static str {
+        match self {
+            Draw::Flush => "Flush draw",
+            Draw::Straight => "Straight draw",
+            Draw::FlushAndStraight => "Flush and straight draw",
+        }
+    }
+}
+
+/// Draws to a flush or straight that `made` does not already beat. Only
+/// meaningful while there are cards to come.
+pub fn draw(cards: &[PlayingCard], made: &MadeHand) -> Option<Draw> {
+    let flush = made.category < HandCategory::Flush
+        && Suit::ALL
+            .iter()
+            .any(|&suit| cards.iter().filter(|c| c.suit == suit).count() == 4);
+
+    // Bit n is set when rank value n is held; the ace also counts as 1.
+    let mut held = 0u16;
+    for card in cards {
+        held |= 1 << card.rank.value();
+        if card.rank == Rank::Ace {
+            held |= 1 << 1;
+        }
+    }
+    let straight = made.category < HandCategory::Straight
+        && (1..=10).any(|low| ((held >> low) & 0b11111).count_ones() == 4);
+
+    match (flush, straight) {
+        (true, true) => Some(Draw::FlushAndStraight),
+        (true, false) => Some(Draw::Flush),
+        (false, true) => Some(Draw::Straight),
+        (false, false) => None,
+    }
+}
+
+/// What `hole` makes with the board so far, e.g. "Two Pair, Kings and
+/// Sevens" or "Pair of Nines + Flush draw".
+pub fn hand_label(hole: &[PlayingCard], board: &[PlayingCard]) -> Option<String> {
+    let cards: Vec<PlayingCard> = hole.iter().chain(board).copied().collect();
+    let made = best_hand(&cards)?;
+    let draw = if (3..5).contains(&board.len()) {
+        draw(&cards, &made)
+    } else {
+        None
+    };
+    Some(match draw {
+        Some(draw) if made.category == HandCategory::HighCard => draw.name().to_string(),
+        Some(draw) => format!("{} + {}", made.label(), draw.name()),
+        None => made.label(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cards(s: &str) -> Vec<PlayingCard> {
+        s.split_whitespace().map(|c| c.parse().unwrap()).collect()
+    }
+
+    fn best(s: &str) -> MadeHand {
+        best_hand(&cards(s)).unwrap()
+    }
+
+    fn five(hand: &MadeHand) -> String {
+        let cards: Vec<String> = hand.cards.iter().map(|c| c.to_string()).collect();
+        cards.join(" ")
+    }
+
+    #[test]
+    fn test_categories_and_labels() {
+        let cases = [
+            ("Ah Kh Qh Jh Th 2c 3d", "Royal Flush"),
+            ("9s 6s 8s 7s 5s 3s Th", "Straight Flush, Nine high"),
+            ("As Ah Ad Ac Qs Qh Qd", "Four of a Kind, Aces"),
+            ("Ks Kh 7d 7c 7s 2h 3h", "Full House, Sevens over Kings"),
+            ("2h 9h Jh 4h Kh Ks Kd", "Flush, King high"),
+            ("Td 9c 8s 7h 6h 2c 2d", "Straight, Ten high"),
+            ("Qs Qh Qd 8c 3s", "Three of a Kind, Queens"),
+            ("Ks Kh 7d 7c 2s 3h 9d", "Two Pair, Kings and Sevens"),
+            ("Ks Kh Kd 7c 7s 7h 3h", "Three of a Kind, Kings"),
+            ("9s 9h", "Pair of Nines"),
+            ("As 7h", "High Card, Ace"),
+        ];
+        for (hand, label) in cases {
+            assert_eq!(best(hand).label(), label, "{}", hand);
+        }
+    }
+
+    #[test]
+    fn test_best_five_order() {
+        assert_eq!(five(&best("7c As Ks 2h 7d Qh 9d")), "7c 7d As Ks Qh");
+        assert_eq!(five(&best("7c As Ks 7d Kh")), "Ks Kh 7c 7d As");
+        let wheel = best("Ah 2c 3d 4s 5h Kc Qd");
+        assert_eq!(wheel.label(), "Straight, Five high");
+        assert_eq!(five(&wheel), "5h 4s 3d 2c Ah");
+    }
+
+    #[test]
+    fn test_contract_quirks() {
+        // Two sets are three of a kind on chain, with the other set as kickers.
+        let two_sets = best("Ks Kh Kd 7c 7s 7h 3h");
+        assert_eq!(two_sets.category, HandCategory::ThreeOfAKind);
+        assert_eq!(five(&two_sets), "Ks Kh Kd 7c 7s");
+        // With three pairs the contract pairs the top one with the lowest
+        // and kicks with the top rank again.
+        let seven: [PlayingCard; 7] = cards("Ks Kh 9d 9c 5s 5h 2c").try_into().unwrap();
+        assert_eq!(evaluate_hand(&seven), 3 << 28 | 11 << 8 | 3 << 4 | 11);
+        assert_eq!(
+            best("Ks Kh 9d 9c 5s 5h 2c").label(),
+            "Two Pair, Kings and Fives"
+        );
+    }
+
+    #[test]
+    fn test_values_order_like_hands() {
+        let hands = [
+            "7c 5d 4h 3s 2c 9d Jh",
+            "7c 7d 4h 3s 2c 9d Jh",
+            "7c 7d 4h 4s 2c 9d Jh",
+            "7c 7d 7h 3s 2c 9d Jh",
+            "Ah 2c 3d 4s 5h Kc Qd",
+            "6h 2c 3d 4s 5h Kc Qd",
+            "2h 9h Jh 4h Kh Ks Kd",
+            "Ks Kh 7d 7c 7s 2h 3h",
+            "As Ah Ad Ac Qs Qh Qd",
+            "5h 2h 3h 4h Ah Kc Qd",
+            "Ah Kh Qh Jh Th 2c 3d",
+        ];
+        let values: Vec<u32> = hands
+            .iter()
+            .map(|hand| evaluate_hand(&cards(hand).try_into().unwrap()))
+            .collect();
+        assert!(values.windows(2).all(|pair| pair[0] < pair[1]));
+        for pair in hands.windows(2) {
+            assert!(best(pair[0]) < best(pair[1]), "{} < {}", pair[0], pair[1]);
+        }
+    }
+
+    #[test]
+    fn test_ordering_matches_showdown() {
+        // Board and hands from test_sidepot: P1 > P2 > P3.
+        let board = "Qs 9s 7s 8c 6h";
+        let p1 = best(&format!("As Ks {}", board));
+        let p2 = best(&format!("Th 9d {}", board));
+        let p3 = best(&format!("2h 3h {}", board));
+        assert!(p1 > p2 && p2 > p3);
+        assert!(best("Ah Ad Kc Qc 2s") > best("Ac As Kd Jd 9s"));
+        assert_eq!(
+            best("Ah Ad Kc Qc 2s").cmp(&best("As Ac Kh Qh 2d")),
+            Ordering::Equal
+        );
+        assert!(best("6h 5c 4d 3s 2h") > best("Ah 2c 3d 4s 5h"));
+    }
+
+    #[test]
+    fn test_draws() {
+        assert_eq!(
+            hand_label(&cards("Ah 7h"), &cards("Kh 2h 9c")).as_deref(),
+            Some("Flush draw")
+        );
+        assert_eq!(
+            hand_label(&cards("9h 9c"), &cards("Th Jh 2h")).as_deref(),
+            Some("Pair of Nines + Flush draw")
+        );
+        assert_eq!(
+            hand_label(&cards("5c 6d"), &cards("7h 8s Kd")).as_deref(),
+            Some("Straight draw")
+        );
+        assert_eq!(
+            hand_label(&cards("Ah 2h"), &cards("3h 4c Kh Qd")).as_deref(),
+            Some("Flush and straight draw")
+        );
+        // No more cards to come on the river.
+        assert_eq!(
+            hand_label(&cards("Ah 7h"), &cards("Kh 2h 9c 3s Jd")).as_deref(),
+            Some("High Card, Ace")
+        );
+        assert_eq!(hand_label(&[], &[]), None);
+    }
+}
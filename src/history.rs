@@ -0,0 +1,264 @@
+use crate::events::{GameEvent, GameSnapshot};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Street {
+    Preflop,
+    Flop,
+    Turn,
+    River,
+}
+
+impl Street {
+    pub fn of_state(state: u8) -> Option<Self> {
+        match state {
+            5..=7 => Some(Street::Preflop),
+            11..=13 => Some(Street::Flop),
+            17..=19 => Some(Street::Turn),
+            23..=25 => Some(Street::River),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Street::Preflop => "Pre",
+            Street::Flop => "Flop",
+            Street::Turn => "Turn",
+            Street::River => "River",
+        }
+    }
+}
+
+/// Amounts are what the player has put in on this street.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActionKind {
+    SmallBlind(u16),
+    BigBlind(u16),
+    Check,
+    Call(u16),
+    Bet(u16),
+    Raise(u16),
+    AllIn(u16),
+    Fold,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Action {
+    pub player: u8,
+    pub kind: ActionKind,
+}
+
+impl fmt::Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let player = self.player;
+        match self.kind {
+            ActionKind::SmallBlind(amount) => write!(f, "P{} posts SB {}", player, amount),
+            ActionKind::BigBlind(amount) => write!(f, "P{} posts BB {}", player, amount),
+            ActionKind::Check => write!(f, "P{} checks", player),
+            ActionKind::Call(_) => write!(f, "P{} calls", player),
+            ActionKind::Bet(amount) => write!(f, "P{} bets {}", player, amount),
+            ActionKind::Raise(amount) => write!(f, "P{} raises to {}", player, amount),
+            ActionKind::AllIn(amount) => write!(f, "P{} all-in {}", player, amount),
+            ActionKind::Fold => write!(f, "P{} folds", player),
+        }
+    }
+}
+
+/// Actions of the current hand grouped by street, rebuilt from the events
+/// `diff` derives between consecutive snapshots.
+#[derive(Debug, Clone, Default)]
+pub struct ActionHistory {
+    game_id: Option<u32>,
+    streets: Vec<(Street, Vec<Action>)>,
+    street_base: u16,
+}
+
+impl ActionHistory {
+    pub fn streets(&self) -> &[(Street, Vec<Action>)] {
+        &self.streets
+    }
+
+    pub fn record(
+        &mut self,
+        prev: Option<&GameSnapshot>,
+        next: &GameSnapshot,
+        events: &[GameEvent],
+    ) {
+        if self.game_id != Some(next.game_id) {
+            *self = Self {
+                game_id: Some(next.game_id),
+                ..Self::default()
+            };
+        }
+        let Some(prev) = prev else {
+            return;
+        };
+
+        for event in events {
+            match *event {
+                GameEvent::BlindsPosted {
+                    small_blind,
+                    small_amount,
+                    big_blind,
+                    big_amount,
+                } => {
+                    self.streets.clear();
+                    self.enter(Street::Preflop, 0);
+                    self.push(small_blind, ActionKind::SmallBlind(small_amount));
+                    self.push(big_blind, ActionKind::BigBlind(big_amount));
+                }
+                GameEvent::PlayerBet {
+                    player,
+                    amount,
+                    total,
+                } => {
+                    let (Some(street), Some(prev_chips), Some(next_chips)) =
+                        (Street::of_state(prev.state), prev.chips, next.chips)
+                    else {
+                        continue;
+                    };
+                    let highest = (1..=3)
+                        .map(|p| prev_chips.get_current_bet(p))
+                        .max()
+                        .unwrap_or(0);
+                    self.enter(street, highest);
+                    let on_street = total.saturating_sub(self.street_base);
+                    let kind = if amount == 0 {
+                        ActionKind::Check
+                    } else if next_chips.get_chips(player) == 0 {
+                        ActionKind::AllIn(on_street)
+                    } else if total <= highest {
+                        ActionKind::Call(on_street)
+                    } else if highest == self.street_base {
+                        ActionKind::Bet(on_street)
+                    } else {
+                        ActionKind::Raise(on_street)
+                    };
+                    self.push(player, kind);
+                }
+                GameEvent::PlayerFolded { player } => {
+                    let Some(street) = Street::of_state(prev.state) else {
+                        continue;
+                    };
+                    let highest = prev
+                        .chips
+                        .map(|c| (1..=3).map(|p| c.get_current_bet(p)).max().unwrap_or(0))
+                        .unwrap_or(0);
+                    self.enter(street, highest);
+                    self.push(player, ActionKind::Fold);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn enter(&mut self, street: Street, highest: u16) {
+        if self.streets.last().map(|(s, _)| *s) != Some(street) {
+            self.streets.push((street, Vec::new()));
+            // Blinds count as preflop bets, later streets start from the
+            // matched total.
+            self.street_base = if street == Street::Preflop {
+                0
+            } else {
+                highest
+            };
+        }
+    }
+
+    fn push(&mut self, player: u8, kind: ActionKind) {
+        if let Some((_, actions)) = self.streets.last_mut() {
+            actions.push(Action { player, kind });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{diff, snapshot};
+
+    fn play(history: &mut ActionHistory, snapshots: &[GameSnapshot]) {
+        let mut prev: Option<&GameSnapshot> = None;
+        for next in snapshots {
+            let events = diff(prev, next);
+            history.record(prev, next, &events);
+            prev = Some(next);
+        }
+    }
+
+    fn ticker(history: &ActionHistory) -> Vec<String> {
+        history
+            .streets()
+            .iter()
+            .map(|(street, actions)| {
+                let actions: Vec<String> = actions.iter().map(|a| a.to_string()).collect();
+                format!("{}: {}", street.name(), actions.join(", "))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_preflop_and_flop_ticker() {
+        let mut history = ActionHistory::default();
+        play(
+            &mut history,
+            &[
+                snapshot(33, 0, [100, 100, 100], [0, 0, 0], 0),
+                snapshot(2, 0, [100, 95, 90], [0, 5, 10], 0),
+                snapshot(5, 0, [100, 95, 90], [0, 5, 10], 0),
+                snapshot(6, 0, [60, 95, 90], [40, 5, 10], 0),
+                snapshot(7, 0, [60, 95, 90], [40, 5, 10], 0b010),
+                snapshot(8, 0, [60, 95, 60], [40, 5, 40], 0b010),
+                snapshot(11, 0, [60, 95, 60], [40, 5, 40], 0b010),
+                snapshot(13, 0, [60, 95, 60], [40, 5, 40], 0b010),
+                snapshot(11, 0, [60, 95, 40], [40, 5, 60], 0b010),
+            ],
+        );
+        assert_eq!(
+            ticker(&history),
+            [
+                "Pre: P2 posts SB 5, P3 posts BB 10, P1 raises to 40, P2 folds, P3 calls",
+                "Flop: P1 checks, P3 bets 20",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_all_in_and_new_hand_resets() {
+        let mut history = ActionHistory::default();
+        play(
+            &mut history,
+            &[
+                snapshot(7, 0, [95, 90, 100], [5, 10, 0], 0),
+                snapshot(5, 0, [95, 90, 0], [5, 10, 100], 0),
+            ],
+        );
+        assert_eq!(ticker(&history), ["Pre: P3 all-in 100"]);
+
+        play(
+            &mut history,
+            &[
+                snapshot(32, 0, [100, 100, 100], [0, 0, 0], 0),
+                snapshot(2, 0, [95, 90, 100], [5, 10, 0], 0),
+            ],
+        );
+        assert_eq!(ticker(&history), ["Pre: P1 posts SB 5, P2 posts BB 10"]);
+    }
+
+    #[test]
+    fn test_other_game_clears_history() {
+        let mut history = ActionHistory::default();
+        play(
+            &mut history,
+            &[
+                snapshot(7, 0, [95, 90, 100], [5, 10, 0], 0),
+                snapshot(5, 0, [95, 90, 0], [5, 10, 100], 0),
+            ],
+        );
+        let mut other = snapshot(5, 0, [95, 90, 100], [5, 10, 0], 0);
+        other.game_id = 2;
+        history.record(None, &other, &[]);
+        assert!(history.streets().is_empty());
+    }
+}
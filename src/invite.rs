@@ -0,0 +1,238 @@
+use crate::game_state::NetworkType;
+use std::fmt;
+use std::str::FromStr;
+
+const PREFIX: &str = "poker-";
+const VERSION: u8 = 1;
+// Lowercase base32 without i, l, o and q so codes survive being read aloud and
+// never contain the TUI quit key.
+const ALPHABET: &[u8; 32] = b"0123456789abcdefghjkmnprstuvwxyz";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InviteCode {
+    pub network: NetworkType,
+    pub endpoint: String,
+    pub game_id: u32,
+    pub password: u128,
+}
+
+impl InviteCode {
+    pub fn new(network: NetworkType, endpoint: &str, game_id: u32, password: u128) -> Self {
+        Self {
+            network,
+            endpoint: endpoint.to_string(),
+            game_id,
+            password,
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        let mut payload = vec![VERSION, network_to_byte(self.network)];
+        payload.extend_from_slice(&self.game_id.to_be_bytes());
+
+        let password_bytes = self.password.to_be_bytes();
+        let skip = password_bytes.iter().take_while(|&&b| b == 0).count();
+        payload.push((password_bytes.len() - skip) as u8);
+        payload.extend_from_slice(&password_bytes[skip..]);
+
+        payload.extend_from_slice(self.endpoint.as_bytes());
+        payload.extend_from_slice(&crc32(&payload).to_be_bytes());
+
+        format!("{}{}", PREFIX, base32_encode(&payload))
+    }
+
+    pub fn decode(code: &str) -> anyhow::Result<Self> {
+        let normalized: String = code
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>()
+            .to_lowercase();
+        let body = normalized
+            .strip_prefix(PREFIX)
+            .ok_or_else(|| anyhow::anyhow!("Invite code must start with '{}'", PREFIX))?;
+
+        let bytes = base32_decode(body)?;
+        if bytes.len() < 4 {
+            anyhow::bail!("Invite code is too short");
+        }
+        let (payload, checksum) = bytes.split_at(bytes.len() - 4);
+        if crc32(payload).to_be_bytes() != checksum {
+            anyhow::bail!("Invite code checksum mismatch, check for typos");
+        }
+
+        let mut reader = payload;
+        let version = take(&mut reader, 1)?[0];
+        if version != VERSION {
+            anyhow::bail!("Unsupported invite code version {}", version);
+        }
+        let network = network_from_byte(take(&mut reader, 1)?[0])?;
+        let game_id = u32::from_be_bytes(take(&mut reader, 4)?.try_into().unwrap());
+
+        let password_len = take(&mut reader, 1)?[0] as usize;
+        if password_len > 16 {
+            anyhow::bail!("Invalid password length in invite code");
+        }
+        let mut password_bytes = [0u8; 16];
+        password_bytes[16 - password_len..].copy_from_slice(take(&mut reader, password_len)?);
+        let password = u128::from_be_bytes(password_bytes);
+
+        let endpoint = String::from_utf8(reader.to_vec())
+            .map_err(|_| anyhow::anyhow!("Invite code endpoint is not valid UTF-8"))?;
+
+        Ok(Self {
+            network,
+            endpoint,
+            game_id,
+            password,
+        })
+    }
+}
+
+impl fmt::Display for InviteCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.encode())
+    }
+}
+
+impl FromStr for InviteCode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::decode(s)
+    }
+}
+
+fn take<'a>(reader: &mut &'a [u8], len: usize) -> anyhow::Result<&'a [u8]> {
+    if reader.len() < len {
+        anyhow::bail!("Invite code is truncated");
+    }
+    let (head, tail) = reader.split_at(len);
+    *reader = tail;
+    Ok(head)
+}
+
+fn network_to_byte(network: NetworkType) -> u8 {
+    match network {
+        NetworkType::Local => 0,
+        NetworkType::Testnet => 1,
+        NetworkType::Mainnet => 2,
+    }
+}
+
+fn network_from_byte(byte: u8) -> anyhow::Result<NetworkType> {
+    match byte {
+        0 => Ok(NetworkType::Local),
+        1 => Ok(NetworkType::Testnet),
+        2 => Ok(NetworkType::Mainnet),
+        _ => anyhow::bail!("Unknown network {} in invite code", byte),
+    }
+}
+
+fn base32_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() * 8 / 5 + 1);
+    let mut buffer = 0u32;
+    let mut bits = 0u32;
+    for &byte in bytes {
+        buffer = (buffer << 8) | byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(ALPHABET[((buffer >> bits) & 31) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(ALPHABET[((buffer << (5 - bits)) & 31) as usize] as char);
+    }
+    out
+}
+
+fn base32_decode(text: &str) -> anyhow::Result<Vec<u8>> {
+    let mut out = Vec::with_capacity(text.len() * 5 / 8);
+    let mut buffer = 0u32;
+    let mut bits = 0u32;
+    for c in text.chars() {
+        let value = ALPHABET
+            .iter()
+            .position(|&a| a as char == c)
+            .ok_or_else(|| anyhow::anyhow!("Invalid character '{}' in invite code", c))?;
+        buffer = (buffer << 5) | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+        }
+    }
+    Ok(out)
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let invite = InviteCode::new(
+            NetworkType::Testnet,
+            "https://api.explorer.provable.com/v1",
+            42,
+            123456789,
+        );
+        let code = invite.to_string();
+        assert!(code.starts_with(PREFIX));
+        assert!(!code.contains('q'));
+        assert_eq!(code.parse::<InviteCode>().unwrap(), invite);
+    }
+
+    #[test]
+    fn test_roundtrip_without_password() {
+        let invite = InviteCode::new(NetworkType::Local, "http://localhost:3030", 0, 0);
+        assert_eq!(InviteCode::decode(&invite.encode()).unwrap(), invite);
+    }
+
+    #[test]
+    fn test_max_values() {
+        let invite = InviteCode::new(NetworkType::Mainnet, "", u32::MAX, u128::MAX);
+        assert_eq!(InviteCode::decode(&invite.encode()).unwrap(), invite);
+    }
+
+    #[test]
+    fn test_case_and_whitespace_insensitive() {
+        let invite = InviteCode::new(NetworkType::Testnet, "http://localhost:3030", 7, 99);
+        let code = invite.encode().to_uppercase();
+        let spaced = format!("  {} \n", code);
+        assert_eq!(InviteCode::decode(&spaced).unwrap(), invite);
+    }
+
+    #[test]
+    fn test_typo_is_rejected() {
+        let invite = InviteCode::new(NetworkType::Testnet, "http://localhost:3030", 7, 99);
+        let mut code: Vec<char> = invite.encode().chars().collect();
+        let i = PREFIX.len() + 3;
+        code[i] = if code[i] == '0' { '1' } else { '0' };
+        let code: String = code.into_iter().collect();
+        assert!(InviteCode::decode(&code).is_err());
+    }
+
+    #[test]
+    fn test_missing_prefix_is_rejected() {
+        assert!(InviteCode::decode("abc123").is_err());
+        assert!(InviteCode::decode("poker-").is_err());
+    }
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+}
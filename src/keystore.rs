@@ -0,0 +1,340 @@
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use leo_bindings::leo_bindings_sdk::Account;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use snarkvm::prelude::{PrivateKey, TestnetV0};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+pub const KEYSTORE_FILE: &str = "keystore.toml";
+pub const PASSPHRASE_ENV: &str = "POKER_KEYSTORE_PASSPHRASE";
+
+const SCRYPT_LOG_N: u8 = 15;
+const SCRYPT_R: u32 = 8;
+const SCRYPT_P: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeystoreEntry {
+    pub name: String,
+    pub address: String,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+    log_n: u8,
+    r: u32,
+    p: u32,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Keystore {
+    #[serde(default)]
+    pub accounts: Vec<KeystoreEntry>,
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+impl Keystore {
+    pub fn default_path() -> PathBuf {
+        std::env::var("POKER_KEYSTORE")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| PathBuf::from(KEYSTORE_FILE))
+    }
+
+    pub fn open_default() -> anyhow::Result<Self> {
+        Self::open(&Self::default_path())
+    }
+
+    pub fn open(path: &Path) -> anyhow::Result<Self> {
+        let mut keystore = if path.exists() {
+            let contents = std::fs::read_to_string(path)
+                .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?;
+            toml::from_str(&contents)
+                .map_err(|e| anyhow::anyhow!("Failed to parse {}: {}", path.display(), e))?
+        } else {
+            Self::default()
+        };
+        keystore.path = path.to_path_buf();
+        Ok(keystore)
+    }
+
+    /// Writes the keystore to a temporary file that only the owner can
+    /// read, then renames it over the old one.
+    pub fn save(&self) -> anyhow::Result<()> {
+        let contents = toml::to_string_pretty(self)?;
+        let tmp = self.path.with_extension("toml.tmp");
+        // A leftover from an interrupted save may have looser permissions.
+        let _ = std::fs::remove_file(&tmp);
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        options
+            .open(&tmp)
+            .and_then(|mut file| file.write_all(contents.as_bytes()))
+            .map_err(|e| anyhow::anyhow!("Failed to write {}: {}", tmp.display(), e))?;
+        std::fs::rename(&tmp, &self.path)
+            .map_err(|e| anyhow::anyhow!("Failed to write {}: {}", self.path.display(), e))?;
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&KeystoreEntry> {
+        self.accounts.iter().find(|a| a.name == name)
+    }
+
+    pub fn import(
+        &mut self,
+        name: &str,
+        private_key: &str,
+        passphrase: &str,
+    ) -> anyhow::Result<&KeystoreEntry> {
+        if name.is_empty() {
+            anyhow::bail!("Account name must not be empty");
+        }
+        if self.get(name).is_some() {
+            anyhow::bail!("Account '{}' already exists", name);
+        }
+        let private_key = private_key.trim();
+        let account = Account::<TestnetV0>::from_str(private_key)
+            .map_err(|e| anyhow::anyhow!("Invalid private key: {}", e))?;
+        let address = account.address().to_string();
+
+        let mut salt = [0u8; 16];
+        let mut nonce = [0u8; 12];
+        rand::thread_rng().fill_bytes(&mut salt);
+        rand::thread_rng().fill_bytes(&mut nonce);
+
+        let key = derive_key(passphrase, &salt, SCRYPT_LOG_N, SCRYPT_R, SCRYPT_P)?;
+        let ciphertext = ChaCha20Poly1305::new(&key)
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: private_key.as_bytes(),
+                    aad: address.as_bytes(),
+                },
+            )
+            .map_err(|_| anyhow::anyhow!("Failed to encrypt private key"))?;
+
+        self.accounts.push(KeystoreEntry {
+            name: name.to_string(),
+            address,
+            salt: hex::encode(salt),
+            nonce: hex::encode(nonce),
+            ciphertext: hex::encode(ciphertext),
+            log_n: SCRYPT_LOG_N,
+            r: SCRYPT_R,
+            p: SCRYPT_P,
+        });
+        Ok(self.accounts.last().unwrap())
+    }
+
+    pub fn generate(&mut self, name: &str, passphrase: &str) -> anyhow::Result<&KeystoreEntry> {
+        let private_key = PrivateKey::<TestnetV0>::new(&mut rand::thread_rng())?;
+        self.import(name, &private_key.to_string(), passphrase)
+    }
+
+    pub fn export(&self, name: &str, passphrase: &str) -> anyhow::Result<String> {
+        let entry = self
+            .get(name)
+            .ok_or_else(|| anyhow::anyhow!("No account named '{}' in keystore", name))?;
+        entry.decrypt(passphrase)
+    }
+
+    pub fn remove(&mut self, name: &str) -> anyhow::Result<()> {
+        let len = self.accounts.len();
+        self.accounts.retain(|a| a.name != name);
+        if self.accounts.len() == len {
+            anyhow::bail!("No account named '{}' in keystore", name);
+        }
+        Ok(())
+    }
+}
+
+impl KeystoreEntry {
+    pub fn decrypt(&self, passphrase: &str) -> anyhow::Result<String> {
+        let salt = hex::decode(&self.salt)?;
+        let nonce = hex::decode(&self.nonce)?;
+        let ciphertext = hex::decode(&self.ciphertext)?;
+        if nonce.len() != 12 {
+            anyhow::bail!("Corrupt keystore entry '{}'", self.name);
+        }
+
+        let key = derive_key(passphrase, &salt, self.log_n, self.r, self.p)?;
+        let plaintext = ChaCha20Poly1305::new(&key)
+            .decrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: &ciphertext,
+                    aad: self.address.as_bytes(),
+                },
+            )
+            .map_err(|_| anyhow::anyhow!("Wrong passphrase for account '{}'", self.name))?;
+        Ok(String::from_utf8(plaintext)?)
+    }
+
+    pub fn account(&self, passphrase: &str) -> anyhow::Result<Account<TestnetV0>> {
+        Account::from_str(&self.decrypt(passphrase)?)
+    }
+}
+
+/// Reads the keystore passphrase from the environment, or prompts on the
+/// terminal when it is not set.
+pub fn read_passphrase(prompt: &str) -> anyhow::Result<String> {
+    match std::env::var(PASSPHRASE_ENV) {
+        Ok(passphrase) => Ok(passphrase),
+        Err(_) => Ok(rpassword::prompt_password(prompt)?),
+    }
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], log_n: u8, r: u32, p: u32) -> anyhow::Result<Key> {
+    // The parameters come from the file, so a tampered entry must not be
+    // able to make us spend unbounded memory or time.
+    if log_n > SCRYPT_LOG_N || r > SCRYPT_R || p > SCRYPT_P {
+        anyhow::bail!(
+            "scrypt parameters log_n={} r={} p={} exceed the supported maximum",
+            log_n,
+            r,
+            p
+        );
+    }
+    let params = scrypt::Params::new(log_n, r, p, 32)
+        .map_err(|e| anyhow::anyhow!("Invalid scrypt parameters: {}", e))?;
+    let mut key = Key::default();
+    scrypt::scrypt(passphrase.as_bytes(), salt, &params, &mut key)
+        .map_err(|e| anyhow::anyhow!("Key derivation failed: {}", e))?;
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(passphrase: &str, secret: &str) -> KeystoreEntry {
+        let salt = [7u8; 16];
+        let nonce = [9u8; 12];
+        let key = derive_key(passphrase, &salt, 4, 8, 1).unwrap();
+        let ciphertext = ChaCha20Poly1305::new(&key)
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: secret.as_bytes(),
+                    aad: b"aleo1test",
+                },
+            )
+            .unwrap();
+        KeystoreEntry {
+            name: "alice".to_string(),
+            address: "aleo1test".to_string(),
+            salt: hex::encode(salt),
+            nonce: hex::encode(nonce),
+            ciphertext: hex::encode(ciphertext),
+            log_n: 4,
+            r: 8,
+            p: 1,
+        }
+    }
+
+    #[test]
+    fn test_decrypt_roundtrip() {
+        let entry = entry("correct horse", "APrivateKey1secret");
+        assert_eq!(
+            entry.decrypt("correct horse").unwrap(),
+            "APrivateKey1secret"
+        );
+    }
+
+    #[test]
+    fn test_wrong_passphrase_rejected() {
+        let entry = entry("correct horse", "APrivateKey1secret");
+        assert!(entry.decrypt("battery staple").is_err());
+    }
+
+    #[test]
+    fn test_tampered_address_rejected() {
+        let mut entry = entry("correct horse", "APrivateKey1secret");
+        entry.address = "aleo1other".to_string();
+        assert!(entry.decrypt("correct horse").is_err());
+    }
+
+    #[test]
+    fn test_file_roundtrip() {
+        let mut keystore = Keystore::default();
+        keystore.accounts.push(entry("pw", "APrivateKey1secret"));
+        let text = toml::to_string_pretty(&keystore).unwrap();
+        let parsed: Keystore = toml::from_str(&text).unwrap();
+        assert_eq!(parsed.accounts, keystore.accounts);
+        assert_eq!(
+            parsed.get("alice").unwrap().decrypt("pw").unwrap(),
+            "APrivateKey1secret"
+        );
+    }
+
+    const PRIVATE_KEY: &str = "APrivateKey1zkp8CZNn3yeCseEtxuVPbDCwSyhGW6yZKUYKfgXmcpoGPWH";
+
+    #[test]
+    fn test_import_roundtrip() {
+        let mut keystore = Keystore::default();
+        let address = keystore
+            .import("bob", PRIVATE_KEY, "pw")
+            .unwrap()
+            .address
+            .clone();
+        let account = Account::<TestnetV0>::from_str(PRIVATE_KEY).unwrap();
+        assert_eq!(address, account.address().to_string());
+        assert_eq!(keystore.export("bob", "pw").unwrap(), PRIVATE_KEY);
+        assert!(keystore.import("bob", PRIVATE_KEY, "pw").is_err());
+    }
+
+    #[test]
+    fn test_import_wrong_passphrase_rejected() {
+        let mut keystore = Keystore::default();
+        keystore.import("bob", PRIVATE_KEY, "pw").unwrap();
+        assert!(keystore.export("bob", "not pw").is_err());
+    }
+
+    #[test]
+    fn test_generate_roundtrip() {
+        let mut keystore = Keystore::default();
+        let address = keystore.generate("carol", "pw").unwrap().address.clone();
+        let account = keystore.get("carol").unwrap().account("pw").unwrap();
+        assert_eq!(account.address().to_string(), address);
+        assert!(keystore.export("carol", "other").is_err());
+    }
+
+    #[test]
+    fn test_oversized_scrypt_cost_rejected() {
+        let mut entry = entry("pw", "APrivateKey1secret");
+        entry.log_n = SCRYPT_LOG_N + 1;
+        assert!(entry.decrypt("pw").is_err());
+    }
+
+    #[test]
+    fn test_save_replaces_file() {
+        let path = std::env::temp_dir().join(format!("poker-keystore-{}.toml", std::process::id()));
+        let mut keystore = Keystore::open(&path).unwrap();
+        keystore.accounts.push(entry("pw", "APrivateKey1secret"));
+        keystore.save().unwrap();
+        keystore.remove("alice").unwrap();
+        keystore.save().unwrap();
+
+        assert!(Keystore::open(&path).unwrap().accounts.is_empty());
+        assert!(!path.with_extension("toml.tmp").exists());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_remove_unknown_account() {
+        let mut keystore = Keystore::default();
+        assert!(keystore.remove("nobody").is_err());
+    }
+}
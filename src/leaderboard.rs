@@ -0,0 +1,485 @@
+use crate::events::CLAIMED_STATE;
+use crate::game::{Credits, GameState};
+use crate::game_state::NetworkType;
+use crate::poll::get_text;
+use crate::store;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+pub const LEADERBOARD_FILE: &str = "leaderboard.json";
+
+/// A game waiting for players is fetched every 2^n scans after n scans in a
+/// row found it still waiting, up to this n.
+const MAX_WAIT_BACKOFF: u32 = 6;
+
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The parts of a game's `games` and `chips` entries the leaderboard needs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameRecord {
+    pub players: [String; 3],
+    pub state: u8,
+    pub buy_in: u64,
+    pub hands_played: u8,
+    pub chips: Option<[u16; 3]>,
+}
+
+/// Read access to the on-chain mappings.
+pub trait GameSource {
+    /// Ids below this have been handed out by `create_game`.
+    fn next_game_id(&self) -> u32;
+    fn game_record(&self, game_id: u32) -> Option<GameRecord>;
+}
+
+/// Reads the mappings over a node's REST API. It needs no account or VM,
+/// so scanning works against any network without proving anything.
+pub struct RestGameSource {
+    api: String,
+}
+
+impl RestGameSource {
+    pub fn new(endpoint: &str, network: NetworkType) -> Self {
+        Self {
+            api: network.api(endpoint),
+        }
+    }
+
+    /// The endpoint with the network appended, which names the index.
+    pub fn api(&self) -> &str {
+        &self.api
+    }
+
+    fn mapping(&self, mapping: &str, key: u32) -> Option<String> {
+        let url = format!(
+            "{}/program/mental_poker.aleo/mapping/{}/{}u32",
+            self.api, mapping, key
+        );
+        let body = get_text(&url, READ_TIMEOUT)
+            .map_err(|e| log::warn!("Failed to read {}: {}", url, e))
+            .ok()?;
+        // The value comes as a JSON string, or null for a missing key.
+        serde_json::from_str::<Option<String>>(&body)
+            .map_err(|e| log::warn!("Unexpected response from {}: {}", url, e))
+            .ok()
+            .flatten()
+    }
+}
+
+impl GameSource for RestGameSource {
+    fn next_game_id(&self) -> u32 {
+        self.mapping("next_game_id", 0)
+            .and_then(|value| integer(&value))
+            .unwrap_or(0)
+    }
+
+    fn game_record(&self, game_id: u32) -> Option<GameRecord> {
+        let game = self.mapping("games", game_id)?;
+        let game = struct_fields(&game);
+        let chips = self.mapping("chips", game_id).and_then(|chips| {
+            let chips = struct_fields(&chips);
+            Some([
+                integer(chips.get("player1")?)?,
+                integer(chips.get("player2")?)?,
+                integer(chips.get("player3")?)?,
+            ])
+        });
+        Some(GameRecord {
+            players: ["player1", "player2", "player3"]
+                .map(|player| game.get(player).map_or("", |a| a).to_string()),
+            state: integer(game.get("state")?)?,
+            buy_in: integer(game.get("buy_in")?)?,
+            hands_played: integer(game.get("hands_played")?)?,
+            chips,
+        })
+    }
+}
+
+/// The fields of a flat struct literal such as `{ state: 3u8, buy_in: 5u64 }`.
+fn struct_fields(text: &str) -> BTreeMap<&str, &str> {
+    text.trim()
+        .trim_start_matches('{')
+        .trim_end_matches('}')
+        .split(',')
+        .filter_map(|field| field.split_once(':'))
+        .map(|(name, value)| (name.trim(), value.trim()))
+        .collect()
+}
+
+/// The number in a literal such as `42u16`.
+fn integer<T: std::str::FromStr>(literal: &str) -> Option<T> {
+    let literal = literal.trim();
+    let end = literal
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(literal.len());
+    literal[..end].parse().ok()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompletedGame {
+    pub game_id: u32,
+    pub players: [String; 3],
+    pub winner: String,
+    /// Microcredits paid out by `claim_prize`.
+    pub prize: u64,
+    pub buy_in: u64,
+    pub hands_played: u8,
+}
+
+/// `None` until the prize has been claimed. The winner is whoever holds
+/// every chip, which `claim_prize` checks before paying out.
+pub fn completed_game(game_id: u32, record: &GameRecord) -> Option<CompletedGame> {
+    if record.state != CLAIMED_STATE {
+        return None;
+    }
+    let chips = record.chips?;
+    let total: u16 = chips.iter().sum();
+    let winner = chips.iter().position(|&c| c > 0 && c == total)?;
+    Some(CompletedGame {
+        game_id,
+        players: record.players.clone(),
+        winner: record.players[winner].clone(),
+        prize: record.buy_in * 3,
+        buy_in: record.buy_in,
+        hands_played: record.hands_played,
+    })
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Standing {
+    pub address: String,
+    pub wins: u32,
+    pub games: u32,
+    /// Microcredits won in prizes.
+    pub won: u64,
+    /// Microcredits paid in buy-ins.
+    pub paid: u64,
+}
+
+impl Standing {
+    pub fn net_credits(&self) -> String {
+        let sign = if self.won < self.paid { "-" } else { "+" };
+        format!("{}{}", sign, Credits::from(self.won.abs_diff(self.paid)))
+    }
+}
+
+/// Completed games of one network. Games are fetched once when they appear
+/// and again only while they are still running, so a repeat scan costs one
+/// lookup per open game plus one per new id. Games nobody joins are fetched
+/// less and less often.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameIndex {
+    /// Every id below this has been fetched at least once.
+    pub scanned_to: u32,
+    /// Games that were still running, or could not be fetched, at the last
+    /// scan.
+    pub open: BTreeSet<u32>,
+    /// Open games still waiting for players, with how many scans in a row
+    /// found them waiting.
+    pub waiting: BTreeMap<u32, u32>,
+    pub scans: u32,
+    pub completed: BTreeMap<u32, CompletedGame>,
+}
+
+impl GameIndex {
+    /// Fetches new and still-running games. Returns how many games completed
+    /// since the last update.
+    pub fn update<S: GameSource + ?Sized>(&mut self, source: &S) -> usize {
+        let next = source.next_game_id();
+        self.scans += 1;
+        let due = |game_id: &u32| {
+            self.waiting
+                .get(game_id)
+                .is_none_or(|&scans| self.scans.is_multiple_of(1 << scans.min(MAX_WAIT_BACKOFF)))
+        };
+        let ids: Vec<u32> = self
+            .open
+            .iter()
+            .copied()
+            .filter(due)
+            .chain(self.scanned_to..next)
+            .collect();
+        let mut completed = 0;
+        for game_id in ids {
+            // Keep ids that failed to load open, so the next scan retries them.
+            let Some(record) = source.game_record(game_id) else {
+                self.open.insert(game_id);
+                continue;
+            };
+            match completed_game(game_id, &record) {
+                Some(game) => {
+                    self.open.remove(&game_id);
+                    self.waiting.remove(&game_id);
+                    self.completed.insert(game_id, game);
+                    completed += 1;
+                }
+                None => {
+                    self.open.insert(game_id);
+                    if matches!(
+                        GameState::from_u8(record.state),
+                        Some(GameState::P2Join | GameState::P3Join)
+                    ) {
+                        *self.waiting.entry(game_id).or_default() += 1;
+                    } else {
+                        self.waiting.remove(&game_id);
+                    }
+                }
+            }
+        }
+        self.scanned_to = self.scanned_to.max(next);
+        completed
+    }
+
+    /// Addresses ranked by wins, then by credits won.
+    pub fn standings(&self) -> Vec<Standing> {
+        let mut by_address: BTreeMap<&str, Standing> = BTreeMap::new();
+        for game in self.completed.values() {
+            for address in &game.players {
+                let standing = by_address.entry(address).or_insert_with(|| Standing {
+                    address: address.clone(),
+                    ..Standing::default()
+                });
+                standing.games += 1;
+                standing.paid += game.buy_in;
+                if *address == game.winner {
+                    standing.wins += 1;
+                    standing.won += game.prize;
+                }
+            }
+        }
+        let mut standings: Vec<Standing> = by_address.into_values().collect();
+        standings.sort_by_key(|s| (Reverse(s.wins), Reverse(s.won)));
+        standings
+    }
+}
+
+/// Cached game indexes keyed by API URL, the endpoint with its network,
+/// stored as one JSON file.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Leaderboard {
+    #[serde(default)]
+    pub networks: BTreeMap<String, GameIndex>,
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+impl Leaderboard {
+    pub fn default_path() -> PathBuf {
+        std::env::var("POKER_LEADERBOARD")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| PathBuf::from(LEADERBOARD_FILE))
+    }
+
+    pub fn open_default() -> anyhow::Result<Self> {
+        Self::open(&Self::default_path())
+    }
+
+    pub fn open(path: &Path) -> anyhow::Result<Self> {
+        let mut leaderboard: Self = store::load(path)?;
+        leaderboard.path = path.to_path_buf();
+        Ok(leaderboard)
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        store::save(&self.path, self)
+    }
+
+    pub fn index(&mut self, api: &str) -> &mut GameIndex {
+        self.networks.entry(api.to_string()).or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+
+    #[derive(Default)]
+    struct MockChain {
+        games: RefCell<Vec<GameRecord>>,
+        lookups: Cell<usize>,
+        /// A game whose lookups fail, as if the API timed out.
+        failing: Cell<Option<u32>>,
+    }
+
+    impl MockChain {
+        fn create(&self, players: [&str; 3], buy_in: u64) -> u32 {
+            let mut games = self.games.borrow_mut();
+            games.push(GameRecord {
+                players: players.map(String::from),
+                state: 2,
+                buy_in,
+                hands_played: 0,
+                chips: Some([1000, 1000, 1000]),
+            });
+            games.len() as u32 - 1
+        }
+
+        fn finish(&self, game_id: u32, winner: usize, hands_played: u8) {
+            let mut games = self.games.borrow_mut();
+            let game = &mut games[game_id as usize];
+            let mut chips = [0; 3];
+            chips[winner] = 3000;
+            game.chips = Some(chips);
+            game.state = CLAIMED_STATE;
+            game.hands_played = hands_played;
+        }
+    }
+
+    impl GameSource for MockChain {
+        fn next_game_id(&self) -> u32 {
+            self.games.borrow().len() as u32
+        }
+
+        fn game_record(&self, game_id: u32) -> Option<GameRecord> {
+            self.lookups.set(self.lookups.get() + 1);
+            if self.failing.get() == Some(game_id) {
+                return None;
+            }
+            self.games.borrow().get(game_id as usize).cloned()
+        }
+    }
+
+    #[test]
+    fn test_winner_needs_claim_and_all_chips() {
+        let mut record = GameRecord {
+            players: ["a", "b", "c"].map(String::from),
+            state: 40,
+            buy_in: 5,
+            hands_played: 9,
+            chips: Some([0, 3000, 0]),
+        };
+        assert_eq!(completed_game(0, &record), None);
+        record.state = CLAIMED_STATE;
+        let game = completed_game(0, &record).unwrap();
+        assert_eq!((game.winner.as_str(), game.prize), ("b", 15));
+        record.chips = Some([0, 0, 0]);
+        assert_eq!(completed_game(0, &record), None);
+    }
+
+    #[test]
+    fn test_incremental_scan_and_ranking() {
+        let chain = MockChain::default();
+        let first = chain.create(["alice", "bob", "carol"], 1_000_000);
+        let second = chain.create(["alice", "bob", "dave"], 1_000_000);
+        let third = chain.create(["carol", "bob", "dave"], 5_000_000);
+        chain.finish(first, 1, 12);
+        chain.finish(third, 0, 30);
+
+        let mut index = GameIndex::default();
+        assert_eq!(index.update(&chain), 2);
+        assert_eq!(chain.lookups.get(), 3);
+        assert_eq!(index.open, BTreeSet::from([second]));
+
+        // Only the running game is fetched again.
+        assert_eq!(index.update(&chain), 0);
+        assert_eq!(chain.lookups.get(), 4);
+
+        chain.finish(second, 1, 7);
+        chain.create(["erin", "frank", "gina"], 1_000_000);
+        assert_eq!(index.update(&chain), 1);
+        assert_eq!(chain.lookups.get(), 6);
+        assert_eq!(index.scanned_to, 4);
+
+        let standings = index.standings();
+        let ranked: Vec<(&str, u32, u64)> = standings
+            .iter()
+            .take(3)
+            .map(|s| (s.address.as_str(), s.wins, s.won))
+            .collect();
+        assert_eq!(
+            ranked,
+            [
+                ("bob", 2, 6_000_000),
+                ("carol", 1, 15_000_000),
+                ("alice", 0, 0)
+            ]
+        );
+        assert_eq!(standings[0].net_credits(), "-1");
+        assert_eq!(standings[1].net_credits(), "+9");
+    }
+
+    #[test]
+    fn test_failed_lookup_is_retried() {
+        let chain = MockChain::default();
+        let first = chain.create(["alice", "bob", "carol"], 1);
+        let second = chain.create(["alice", "bob", "dave"], 1);
+        chain.finish(first, 0, 3);
+        chain.finish(second, 2, 5);
+        chain.failing.set(Some(first));
+
+        let mut index = GameIndex::default();
+        assert_eq!(index.update(&chain), 1);
+        assert_eq!(index.scanned_to, 2);
+        assert_eq!(index.open, BTreeSet::from([first]));
+
+        chain.failing.set(None);
+        assert_eq!(index.update(&chain), 1);
+        assert!(index.open.is_empty());
+        assert_eq!(index.completed.len(), 2);
+    }
+
+    #[test]
+    fn test_waiting_games_back_off() {
+        let chain = MockChain::default();
+        let waiting = chain.create(["alice", "bob", "carol"], 1);
+        chain.games.borrow_mut()[waiting as usize].state = 1;
+
+        let mut index = GameIndex::default();
+        let mut fetched = Vec::new();
+        for _ in 0..8 {
+            let before = chain.lookups.get();
+            index.update(&chain);
+            fetched.push(chain.lookups.get() - before);
+        }
+        // Seen waiting on scans 1, 2, 4 and 8.
+        assert_eq!(fetched, [1, 1, 0, 1, 0, 0, 0, 1]);
+        assert_eq!(index.waiting[&waiting], 4);
+
+        // Once someone joins it is fetched every scan again.
+        chain.games.borrow_mut()[waiting as usize].state = 5;
+        for _ in 0..8 {
+            index.update(&chain);
+        }
+        assert!(index.waiting.is_empty());
+        let before = chain.lookups.get();
+        index.update(&chain);
+        assert_eq!(chain.lookups.get() - before, 1);
+    }
+
+    #[test]
+    fn test_struct_literal() {
+        let game = "{\n  player1: aleo1abc,\n  state: 34u8,\n  buy_in: 1000000u64\n}";
+        let fields = struct_fields(game);
+        assert_eq!(fields.get("player1"), Some(&"aleo1abc"));
+        assert_eq!(integer::<u8>(fields["state"]), Some(34));
+        assert_eq!(integer::<u64>(fields["buy_in"]), Some(1_000_000));
+        assert_eq!(integer::<u8>("u8"), None);
+    }
+
+    #[test]
+    fn test_file_roundtrip() {
+        let path =
+            std::env::temp_dir().join(format!("poker-leaderboard-{}.json", std::process::id()));
+        let chain = MockChain::default();
+        let game_id = chain.create(["a", "b", "c"], 1);
+        chain.finish(game_id, 2, 3);
+
+        let mut leaderboard = Leaderboard::open(&path).unwrap();
+        leaderboard.index("http://localhost:3030").update(&chain);
+        leaderboard.save().unwrap();
+
+        let mut reopened = Leaderboard::open(&path).unwrap();
+        assert_eq!(reopened.networks, leaderboard.networks);
+        assert!(
+            reopened
+                .index("https://api.explorer.provable.com/v1")
+                .completed
+                .is_empty()
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,5 +1,28 @@
+pub mod account_picker;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod bench;
 pub mod cards;
+pub mod config;
 pub mod deck;
+pub mod events;
 pub mod game;
 pub mod game_state;
+pub mod hand;
+pub mod history;
+pub mod invite;
+pub mod keystore;
+pub mod leaderboard;
+pub mod logs;
+pub mod poll;
+pub mod pots;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod rigged;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod scenario;
+pub mod server;
+pub mod showdown;
+pub mod stats;
+pub mod store;
+pub mod telemetry;
+pub mod tx;
 pub mod waksman_ctrl;
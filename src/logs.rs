@@ -0,0 +1,286 @@
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const LOG_CAPACITY: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Info,
+    Warn,
+    Error,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogCategory {
+    General,
+    Proof,
+    Bet,
+    State,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Progress {
+    Running,
+    Done,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LogEntry {
+    pub time_ms: u64,
+    pub level: LogLevel,
+    pub category: LogCategory,
+    pub game_id: Option<u32>,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress: Option<Progress>,
+}
+
+impl LogEntry {
+    pub fn time(&self) -> String {
+        let secs = self.time_ms / 1000;
+        format!(
+            "{:02}:{:02}:{:02}",
+            secs / 3600 % 24,
+            secs / 60 % 60,
+            secs % 60
+        )
+    }
+
+    pub fn text(&self) -> String {
+        match self.progress {
+            Some(Progress::Running) => format!("⏳ {}", self.message),
+            Some(Progress::Done) => format!("✓ {}", self.message),
+            None => self.message.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFilter {
+    #[default]
+    All,
+    Proofs,
+    Bets,
+    State,
+    Errors,
+}
+
+impl LogFilter {
+    pub fn matches(self, entry: &LogEntry) -> bool {
+        match self {
+            LogFilter::All => true,
+            LogFilter::Proofs => entry.category == LogCategory::Proof,
+            LogFilter::Bets => entry.category == LogCategory::Bet,
+            LogFilter::State => entry.category == LogCategory::State,
+            LogFilter::Errors => entry.level != LogLevel::Info,
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            LogFilter::All => LogFilter::Proofs,
+            LogFilter::Proofs => LogFilter::Bets,
+            LogFilter::Bets => LogFilter::State,
+            LogFilter::State => LogFilter::Errors,
+            LogFilter::Errors => LogFilter::All,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            LogFilter::All => "all",
+            LogFilter::Proofs => "proofs",
+            LogFilter::Bets => "bets",
+            LogFilter::State => "state",
+            LogFilter::Errors => "errors",
+        }
+    }
+}
+
+/// Bounded log shared by the model and its worker clones. The session file,
+/// when set, receives every entry as one JSON line at the moment it is pushed.
+#[derive(Debug, Clone, Default)]
+pub struct LogBuffer {
+    entries: VecDeque<LogEntry>,
+    session: Option<Arc<Mutex<BufWriter<File>>>>,
+}
+
+impl LogBuffer {
+    pub fn set_session_file(&mut self, path: &Path) -> anyhow::Result<()> {
+        let file = File::create(path)
+            .map_err(|e| anyhow::anyhow!("Failed to create {}: {}", path.display(), e))?;
+        self.session = Some(Arc::new(Mutex::new(BufWriter::new(file))));
+        for entry in &self.entries {
+            self.write_session(entry);
+        }
+        Ok(())
+    }
+
+    pub fn push(
+        &mut self,
+        level: LogLevel,
+        category: LogCategory,
+        game_id: Option<u32>,
+        message: String,
+        progress: Option<Progress>,
+    ) {
+        let entry = LogEntry {
+            time_ms: now_ms(),
+            level,
+            category,
+            game_id,
+            message,
+            progress,
+        };
+        self.write_session(&entry);
+        self.entries.push_back(entry);
+        if self.entries.len() > LOG_CAPACITY {
+            self.entries.pop_front();
+        }
+    }
+
+    /// Marks the most recent entry as done if it is still running.
+    pub fn complete_last(&mut self) {
+        let Some(last) = self.entries.back_mut() else {
+            return;
+        };
+        if last.progress != Some(Progress::Running) {
+            return;
+        }
+        last.progress = Some(Progress::Done);
+        last.time_ms = now_ms();
+        let last = last.clone();
+        self.write_session(&last);
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &LogEntry> {
+        self.entries.iter()
+    }
+
+    pub fn last(&self) -> Option<&LogEntry> {
+        self.entries.back()
+    }
+
+    fn write_session(&self, entry: &LogEntry) {
+        if let Some(session) = &self.session
+            && let Ok(mut writer) = session.lock()
+            && let Ok(line) = serde_json::to_string(entry)
+        {
+            let _ = writeln!(writer, "{}", line);
+            let _ = writer.flush();
+        }
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push(buffer: &mut LogBuffer, level: LogLevel, category: LogCategory, message: &str) {
+        buffer.push(level, category, Some(1), message.to_string(), None);
+    }
+
+    #[test]
+    fn test_buffer_is_bounded() {
+        let mut buffer = LogBuffer::default();
+        for i in 0..LOG_CAPACITY + 10 {
+            push(
+                &mut buffer,
+                LogLevel::Info,
+                LogCategory::General,
+                &i.to_string(),
+            );
+        }
+        assert_eq!(buffer.len(), LOG_CAPACITY);
+        assert_eq!(buffer.iter().next().unwrap().message, "10");
+    }
+
+    #[test]
+    fn test_complete_last_only_touches_running_entry() {
+        let mut buffer = LogBuffer::default();
+        buffer.push(
+            LogLevel::Info,
+            LogCategory::Proof,
+            None,
+            "Shuffling deck".to_string(),
+            Some(Progress::Running),
+        );
+        buffer.complete_last();
+        assert_eq!(buffer.last().unwrap().text(), "✓ Shuffling deck");
+
+        push(&mut buffer, LogLevel::Info, LogCategory::General, "plain");
+        buffer.complete_last();
+        assert_eq!(buffer.last().unwrap().text(), "plain");
+    }
+
+    #[test]
+    fn test_filters() {
+        let mut buffer = LogBuffer::default();
+        push(&mut buffer, LogLevel::Info, LogCategory::Proof, "proof");
+        push(&mut buffer, LogLevel::Info, LogCategory::Bet, "bet");
+        push(&mut buffer, LogLevel::Info, LogCategory::State, "state");
+        push(&mut buffer, LogLevel::Error, LogCategory::General, "error");
+
+        let matching = |filter: LogFilter| -> Vec<&str> {
+            buffer
+                .iter()
+                .filter(|e| filter.matches(e))
+                .map(|e| e.message.as_str())
+                .collect()
+        };
+        assert_eq!(matching(LogFilter::All).len(), 4);
+        assert_eq!(matching(LogFilter::Proofs), ["proof"]);
+        assert_eq!(matching(LogFilter::Bets), ["bet"]);
+        assert_eq!(matching(LogFilter::State), ["state"]);
+        assert_eq!(matching(LogFilter::Errors), ["error"]);
+    }
+
+    #[test]
+    fn test_filter_cycle_returns_to_all() {
+        let mut filter = LogFilter::All;
+        for _ in 0..5 {
+            filter = filter.next();
+        }
+        assert_eq!(filter, LogFilter::All);
+    }
+
+    #[test]
+    fn test_session_line_format() {
+        let entry = LogEntry {
+            time_ms: 3_723_000,
+            level: LogLevel::Warn,
+            category: LogCategory::Bet,
+            game_id: Some(4),
+            message: "Raising 20".to_string(),
+            progress: None,
+        };
+        assert_eq!(entry.time(), "01:02:03");
+        assert_eq!(
+            serde_json::to_string(&entry).unwrap(),
+            r#"{"time_ms":3723000,"level":"warn","category":"bet","game_id":4,"message":"Raising 20"}"#
+        );
+    }
+}
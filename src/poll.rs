@@ -0,0 +1,161 @@
+//! When to poll a table and which mapping reads a poll can skip.
+
+use crate::game::GameState;
+use std::time::Duration;
+
+/// The seat after `seat` that is still in the hand.
+fn next_active(seat: u8, inactive: u8) -> Option<u8> {
+    (1..=2)
+        .map(|offset| (seat - 1 + offset) % 3 + 1)
+        .find(|next| inactive & (1 << (next - 1)) == 0)
+}
+
+/// How much slower an abandoned game is polled, in case someone still joins.
+const ABANDONED_SLOWDOWN: u64 = 30;
+
+/// Whether players who folded sit out `state`. They still decrypt, shuffle
+/// and start the next hand; only betting and showdown skip them.
+fn folded_sit_out(state: GameState) -> bool {
+    state.is_betting_state()
+        || matches!(
+            state,
+            GameState::P1Showdown | GameState::P2Showdown | GameState::P3Showdown
+        )
+}
+
+/// Scales the profile's poll interval: half of it while the player before
+/// `player_id` is acting, four times it while `player_id` has nothing to do
+/// until the hand is over: once they are out, or have folded and the hand
+/// is in betting or showdown.
+pub fn poll_interval_ms(
+    base_ms: u64,
+    state: Option<GameState>,
+    player_id: u8,
+    players_out: u8,
+    players_folded: u8,
+) -> u64 {
+    if state == Some(GameState::Abandoned) {
+        return base_ms * ABANDONED_SLOWDOWN;
+    }
+    if !(1..=3).contains(&player_id) {
+        return base_ms;
+    }
+    let inactive = match state {
+        Some(state) if folded_sit_out(state) => players_out | players_folded,
+        _ => players_out,
+    };
+    if inactive & (1 << (player_id - 1)) != 0 {
+        return base_ms * 4;
+    }
+    let acting = state.and_then(GameState::current_player);
+    match acting {
+        Some(acting) if acting != player_id && next_active(acting, inactive) == Some(player_id) => {
+            base_ms / 2
+        }
+        _ => base_ms,
+    }
+}
+
+/// `GET {api}/block/height/latest`, where `api` is the endpoint with the
+/// network appended.
+pub fn latest_height(api: &str, timeout: Duration) -> anyhow::Result<u32> {
+    let body = get_text(&format!("{}/block/height/latest", api), timeout)?;
+    body.trim()
+        .parse()
+        .map_err(|e| anyhow::anyhow!("Unexpected block height '{}': {}", body.trim(), e))
+}
+
+pub fn get_text(url: &str, timeout: Duration) -> anyhow::Result<String> {
+    let agent: ureq::Agent = ureq::Agent::config_builder()
+        .timeout_global(Some(timeout))
+        .build()
+        .into();
+    Ok(agent.get(url).call()?.body_mut().read_to_string()?)
+}
+
+/// The last mapping reads of one game and the block height they were made
+/// at. Without a height, as on a local VM, nothing is reused.
+#[derive(Debug, Clone)]
+pub struct ReadCache<T> {
+    entry: Option<(u32, Option<u32>, T)>,
+}
+
+impl<T> Default for ReadCache<T> {
+    fn default() -> Self {
+        Self { entry: None }
+    }
+}
+
+impl<T: Clone> ReadCache<T> {
+    /// The reads of `game_id`, if they were made at block `height`.
+    pub fn at_height(&self, game_id: u32, height: Option<u32>) -> Option<T> {
+        match &self.entry {
+            Some((id, Some(cached), reads)) if *id == game_id && height == Some(*cached) => {
+                Some(reads.clone())
+            }
+            _ => None,
+        }
+    }
+
+    /// The reads of `game_id` at any height.
+    pub fn last(&self, game_id: u32) -> Option<&T> {
+        match &self.entry {
+            Some((id, _, reads)) if *id == game_id => Some(reads),
+            _ => None,
+        }
+    }
+
+    pub fn store(&mut self, game_id: u32, height: Option<u32>, reads: T) {
+        self.entry = Some((game_id, height, reads));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_poll_interval() {
+        // P2 bets preflop and P3 is next.
+        let state = Some(GameState::P2BetPre);
+        assert_eq!(poll_interval_ms(1000, state, 3, 0, 0), 500);
+        assert_eq!(poll_interval_ms(1000, state, 1, 0, 0), 1000);
+        assert_eq!(poll_interval_ms(1000, state, 2, 0, 0), 1000);
+        // With P3 folded P1 is next after P2.
+        assert_eq!(poll_interval_ms(1000, state, 1, 0, 0b100), 500);
+        assert_eq!(poll_interval_ms(1000, state, 3, 0, 0b100), 4000);
+        assert_eq!(poll_interval_ms(1000, state, 3, 0b100, 0), 4000);
+        assert_eq!(poll_interval_ms(1000, None, 0, 0, 0), 1000);
+        // Folded players still decrypt: P3 folded but is next after P2.
+        let state = Some(GameState::P2DecFlop);
+        assert_eq!(poll_interval_ms(1000, state, 3, 0, 0b100), 500);
+        assert_eq!(poll_interval_ms(1000, state, 1, 0, 0b100), 1000);
+        assert_eq!(poll_interval_ms(1000, state, 3, 0b100, 0), 4000);
+        assert_eq!(
+            poll_interval_ms(1000, Some(GameState::P1NewShuffle), 2, 0, 0b010),
+            500
+        );
+        assert_eq!(
+            poll_interval_ms(1000, Some(GameState::P1Showdown), 2, 0, 0b010),
+            4000
+        );
+        assert_eq!(
+            poll_interval_ms(1000, Some(GameState::Abandoned), 1, 0, 0),
+            30_000
+        );
+    }
+
+    #[test]
+    fn test_read_cache() {
+        let mut cache = ReadCache::default();
+        cache.store(4, Some(100), "reads");
+        assert_eq!(cache.at_height(4, Some(100)), Some("reads"));
+        assert_eq!(cache.at_height(4, Some(101)), None);
+        assert_eq!(cache.at_height(5, Some(100)), None);
+        assert_eq!(cache.last(4), Some(&"reads"));
+
+        cache.store(4, None, "local");
+        assert_eq!(cache.at_height(4, None), None);
+        assert_eq!(cache.last(4), Some(&"local"));
+    }
+}
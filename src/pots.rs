@@ -0,0 +1,143 @@
+/// One layer of the pot and the players (1-3) who can still win it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pot {
+    pub amount: u16,
+    pub eligible: Vec<u8>,
+}
+
+/// Splits per-hand bets into a main pot and side pots the same way
+/// `finalize_compare_hands` does: each distinct bet level forms a layer
+/// funded by everyone who put in at least that much, and only players still
+/// in the hand are eligible for it. Empty layers are dropped.
+pub fn side_pots(bets: [u16; 3], active: [bool; 3]) -> Vec<Pot> {
+    let mut levels = bets.to_vec();
+    levels.sort_unstable();
+    levels.dedup();
+
+    let mut pots = Vec::new();
+    let mut previous = 0u16;
+    for level in levels {
+        let contributors = bets.iter().filter(|&&bet| bet >= level).count() as u16;
+        let amount = (level - previous) * contributors;
+        previous = level;
+        if amount == 0 {
+            continue;
+        }
+        let eligible = (1..=3u8)
+            .filter(|&p| active[p as usize - 1] && bets[p as usize - 1] >= level)
+            .collect();
+        pots.push(Pot { amount, eligible });
+    }
+    pots
+}
+
+/// Chips each player takes from `pots` given their hand values (higher wins).
+/// Ties split evenly and, as on chain, any odd remainder is not paid out.
+pub fn payouts(pots: &[Pot], hand_values: [u32; 3]) -> [u16; 3] {
+    let mut shares = [0u16; 3];
+    for pot in pots {
+        let Some(best) = pot
+            .eligible
+            .iter()
+            .map(|&p| hand_values[p as usize - 1])
+            .max()
+        else {
+            continue;
+        };
+        let winners: Vec<u8> = pot
+            .eligible
+            .iter()
+            .copied()
+            .filter(|&p| hand_values[p as usize - 1] == best)
+            .collect();
+        let share = pot.amount / winners.len() as u16;
+        for winner in winners {
+            shares[winner as usize - 1] += share;
+        }
+    }
+    shares
+}
+
+pub fn active_players(players_folded: u8, players_out: u8) -> [bool; 3] {
+    let gone = players_folded | players_out;
+    [gone & 1 == 0, gone & 2 == 0, gone & 4 == 0]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_pot_when_bets_match() {
+        let pots = side_pots([50, 50, 50], [true; 3]);
+        assert_eq!(
+            pots,
+            vec![Pot {
+                amount: 150,
+                eligible: vec![1, 2, 3]
+            }]
+        );
+    }
+
+    #[test]
+    fn test_three_layers() {
+        // Same setup as test_sidepot in tests/test_combinations.rs: P1 has the
+        // best hand, then P2, then P3.
+        let pots = side_pots([10, 70, 200], [true; 3]);
+        assert_eq!(
+            pots,
+            vec![
+                Pot {
+                    amount: 30,
+                    eligible: vec![1, 2, 3]
+                },
+                Pot {
+                    amount: 120,
+                    eligible: vec![2, 3]
+                },
+                Pot {
+                    amount: 130,
+                    eligible: vec![3]
+                },
+            ]
+        );
+        assert_eq!(payouts(&pots, [300, 200, 100]), [30, 120, 130]);
+    }
+
+    #[test]
+    fn test_folded_player_funds_but_cannot_win() {
+        let pots = side_pots([40, 100, 100], active_players(0b001, 0));
+        assert_eq!(
+            pots,
+            vec![
+                Pot {
+                    amount: 120,
+                    eligible: vec![2, 3]
+                },
+                Pot {
+                    amount: 120,
+                    eligible: vec![2, 3]
+                },
+            ]
+        );
+        assert_eq!(payouts(&pots, [900, 100, 100]), [0, 120, 120]);
+    }
+
+    #[test]
+    fn test_split_drops_remainder() {
+        let pots = side_pots([15, 15, 15], [true; 3]);
+        assert_eq!(payouts(&pots, [7, 7, 1]), [22, 22, 0]);
+    }
+
+    #[test]
+    fn test_all_in_short_stack() {
+        let pots = side_pots([0, 30, 30], [true; 3]);
+        assert_eq!(
+            pots,
+            vec![Pot {
+                amount: 60,
+                eligible: vec![2, 3]
+            }]
+        );
+    }
+}
@@ -0,0 +1,179 @@
+//! Stacked decks for tests that need a specific deal to come out of the real
+//! shuffle-and-decrypt flow.
+
+use crate::cards::PlayingCard;
+use crate::waksman_ctrl::random_permutation;
+use rand::Rng;
+use rand::seq::SliceRandom;
+use std::str::FromStr;
+
+/// Deck positions the program deals from after the last shuffle.
+pub const PLAYER1_POSITIONS: [usize; 2] = [0, 3];
+pub const PLAYER2_POSITIONS: [usize; 2] = [1, 4];
+pub const PLAYER3_POSITIONS: [usize; 2] = [2, 5];
+pub const FLOP_POSITIONS: [usize; 3] = [7, 8, 9];
+pub const TURN_POSITION: usize = 11;
+pub const RIVER_POSITION: usize = 13;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Deal {
+    pub player1: [PlayingCard; 2],
+    pub player2: [PlayingCard; 2],
+    pub player3: [PlayingCard; 2],
+    pub flop: [PlayingCard; 3],
+    pub turn: PlayingCard,
+    pub river: PlayingCard,
+}
+
+impl Deal {
+    /// Every dealt card with the deck position it is dealt from.
+    pub fn positions(&self) -> [(usize, PlayingCard); 11] {
+        let positions: Vec<(usize, PlayingCard)> = PLAYER1_POSITIONS
+            .into_iter()
+            .zip(self.player1)
+            .chain(PLAYER2_POSITIONS.into_iter().zip(self.player2))
+            .chain(PLAYER3_POSITIONS.into_iter().zip(self.player3))
+            .chain(FLOP_POSITIONS.into_iter().zip(self.flop))
+            .chain([(TURN_POSITION, self.turn), (RIVER_POSITION, self.river)])
+            .collect();
+        positions.try_into().expect("eleven cards are dealt")
+    }
+
+    /// A final deck order, as card indices, that deals this hand. Cards that
+    /// are not dealt fill the other positions in random order.
+    pub fn deck_order<R: Rng + ?Sized>(&self, rng: &mut R) -> [usize; 52] {
+        let positions = self.positions();
+        let mut order = [usize::MAX; 52];
+        for (position, card) in positions {
+            order[position] = card.index() as usize;
+        }
+        let mut rest: Vec<usize> = (0..52).filter(|index| !order.contains(index)).collect();
+        rest.shuffle(rng);
+        for (slot, index) in order.iter_mut().filter(|s| **s == usize::MAX).zip(rest) {
+            *slot = index;
+        }
+        order
+    }
+}
+
+/// Parses "As Kd / 7h 7c / 2s 3s / Qh Jh Th 9c 2d": the three hands, then
+/// the board.
+impl FromStr for Deal {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let groups: Vec<Vec<PlayingCard>> = s
+            .split('/')
+            .map(|group| group.split_whitespace().map(str::parse).collect())
+            .collect::<anyhow::Result<_>>()?;
+        let [player1, player2, player3, board] = groups.as_slice() else {
+            anyhow::bail!("Expected three hands and a board separated by '/'");
+        };
+        let pair = |cards: &[PlayingCard]| -> anyhow::Result<[PlayingCard; 2]> {
+            cards
+                .try_into()
+                .map_err(|_| anyhow::anyhow!("A hand has two cards, got {}", cards.len()))
+        };
+        let [f1, f2, f3, turn, river] = board.as_slice() else {
+            anyhow::bail!("The board has five cards, got {}", board.len());
+        };
+        let deal = Deal {
+            player1: pair(player1)?,
+            player2: pair(player2)?,
+            player3: pair(player3)?,
+            flop: [*f1, *f2, *f3],
+            turn: *turn,
+            river: *river,
+        };
+        let cards = deal.positions().map(|(_, card)| card);
+        if let Some(card) = cards
+            .iter()
+            .enumerate()
+            .find_map(|(i, card)| cards[..i].contains(card).then_some(card))
+        {
+            anyhow::bail!("{} is dealt twice", card);
+        }
+        Ok(deal)
+    }
+}
+
+/// Permutations for `shufflers` players, in shuffle order, that turn the
+/// initial deck into one dealing `deal`. All but the last are random; the
+/// last undoes them and stacks the deck.
+pub fn rigged_permutations<R: Rng + ?Sized>(
+    deal: &Deal,
+    shufflers: usize,
+    rng: &mut R,
+) -> Vec<[usize; 52]> {
+    assert!(shufflers > 0, "at least one player shuffles");
+    let mut perms: Vec<[usize; 52]> = (1..shufflers).map(|_| random_permutation(rng)).collect();
+
+    // Position of each card after the random shuffles, and where it has to
+    // end up.
+    let mut current: [usize; 52] = std::array::from_fn(|card| card);
+    for perm in &perms {
+        current = current.map(|position| perm[position]);
+    }
+    let order = deal.deck_order(rng);
+    let mut target = [0usize; 52];
+    for (position, &card) in order.iter().enumerate() {
+        target[card] = position;
+    }
+
+    let mut last = [0usize; 52];
+    for card in 0..52 {
+        last[current[card]] = target[card];
+    }
+    perms.push(last);
+    perms
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::waksman_ctrl::permutation_to_waksman_bits;
+
+    fn shuffle(deck: [usize; 52], perm: &[usize; 52]) -> [usize; 52] {
+        let mut shuffled = deck;
+        for (src, &dst) in perm.iter().enumerate() {
+            shuffled[dst] = deck[src];
+        }
+        shuffled
+    }
+
+    #[test]
+    fn test_parse_deal() {
+        let deal: Deal = "As Kd / 7h 7c / 2s 3s / Qh Jh Th 9c 2d".parse().unwrap();
+        assert_eq!(deal.player2, ["7h".parse().unwrap(), "7c".parse().unwrap()]);
+        assert_eq!(deal.river.to_string(), "2d");
+        assert!(
+            "As Kd / 7h 7c / 2s 3s / Qh Jh Th 9c"
+                .parse::<Deal>()
+                .is_err()
+        );
+        assert!(
+            "As Kd / 7h As / 2s 3s / Qh Jh Th 9c 2d"
+                .parse::<Deal>()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_rigged_permutations_deal_target() {
+        let deal: Deal = "Ah Ad / Kc Qc / 9s 8s / 7s 6s 2h Jd 3c".parse().unwrap();
+        let mut rng = rand::thread_rng();
+        for shufflers in 1..=3 {
+            let perms = rigged_permutations(&deal, shufflers, &mut rng);
+            assert_eq!(perms.len(), shufflers);
+
+            let mut deck: [usize; 52] = std::array::from_fn(|card| card);
+            for perm in &perms {
+                permutation_to_waksman_bits(perm);
+                deck = shuffle(deck, perm);
+            }
+            for (position, card) in deal.positions() {
+                assert_eq!(deck[position], card.index() as usize);
+            }
+        }
+    }
+}
@@ -0,0 +1,680 @@
+//! Scripted hands for integration tests. A scenario deals chosen cards
+//! through the real create, join and shuffle transitions, plays each
+//! player's actions street by street and checks chips and state as it goes:
+//!
+//! ```ignore
+//! Scenario::new("flop bet takes it")
+//!     .deal("Ah Ad / Kc Qd / 9s 8h / 2c 7d Jh 4s 3c")
+//!     .preflop("p3 call, p1 call, p2 check")
+//!     .expect_stacks([990, 990, 990])
+//!     .flop("p1 bet 20, p2 fold, p3 fold")
+//!     .expect_stacks([1020, 990, 990])
+//!     .run()
+//!     .unwrap();
+//! ```
+//!
+//! Decryption, showdown and `compare_hands` are submitted automatically by
+//! whichever player the state is waiting on.
+
+use crate::cards::{get_other_players_cards, get_player_cards};
+use crate::deck::initialized_deck;
+use crate::game::{Chip, GameState};
+use crate::history::Street;
+use crate::rigged::{Deal, rigged_permutations};
+use crate::telemetry::{Transition, TransitionSample, timed};
+use crate::waksman_ctrl::{permute_deck, shuffle_deck};
+use credits_bindings::credits::CreditsAleo;
+use leo_bindings::leo_bindings_sdk::{Account, LocalVM, VMManager};
+use mental_poker_bindings::mental_poker::{Chips, Game, Keys, MentalPokerAleo};
+use snarkvm::prelude::{Group, Inverse, Scalar, TestRng, TestnetV0, Uniform};
+use std::collections::VecDeque;
+use std::fmt;
+use std::str::FromStr;
+
+pub const GAME_ID: u32 = 0;
+const BUY_IN: u64 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Fold,
+    Check,
+    Call,
+    /// Chips added by this action, as passed to the `bet` transition.
+    Bet(u16),
+    AllIn,
+}
+
+/// One action, written "p3 bet 60", "p1 fold" or "p2 all-in".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    pub player: u8,
+    pub action: Action,
+}
+
+impl FromStr for Step {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let words: Vec<String> = s.split_whitespace().map(str::to_lowercase).collect();
+        let words: Vec<&str> = words.iter().map(String::as_str).collect();
+        let player = match words.first() {
+            Some(&"p1") => 1,
+            Some(&"p2") => 2,
+            Some(&"p3") => 3,
+            _ => anyhow::bail!("'{}' should start with p1, p2 or p3", s),
+        };
+        let action = match &words[1..] {
+            ["fold"] => Action::Fold,
+            ["check"] => Action::Check,
+            ["call"] => Action::Call,
+            ["all-in" | "allin"] => Action::AllIn,
+            ["bet" | "raise", amount] => Action::Bet(
+                amount
+                    .parse()
+                    .map_err(|e| anyhow::anyhow!("Invalid amount in '{}': {}", s, e))?,
+            ),
+            _ => anyhow::bail!("Unknown action '{}'", s),
+        };
+        Ok(Step { player, action })
+    }
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.action {
+            Action::Fold => write!(f, "p{} fold", self.player),
+            Action::Check => write!(f, "p{} check", self.player),
+            Action::Call => write!(f, "p{} call", self.player),
+            Action::Bet(amount) => write!(f, "p{} bet {}", self.player, amount),
+            Action::AllIn => write!(f, "p{} all-in", self.player),
+        }
+    }
+}
+
+/// Checked after a step and everything it triggers have gone through.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Expect {
+    pub stacks: Option<[u16; 3]>,
+    pub bets: Option<[u16; 3]>,
+    pub folded: Option<u8>,
+    pub state: Option<GameState>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptedStep {
+    pub street: Street,
+    pub step: Step,
+    pub expect: Expect,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scenario {
+    pub name: String,
+    pub deal: Option<Deal>,
+    /// Stacks before the blinds are posted.
+    pub stacks: [u16; 3],
+    pub blinds: (u16, u16),
+    pub steps: Vec<ScriptedStep>,
+}
+
+impl Scenario {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            deal: None,
+            stacks: [1000; 3],
+            blinds: (5, 10),
+            steps: Vec::new(),
+        }
+    }
+
+    /// Panics on a malformed deal, which is a bug in the test.
+    pub fn deal(mut self, deal: &str) -> Self {
+        self.deal = Some(
+            deal.parse()
+                .unwrap_or_else(|e| panic!("{}: bad deal '{}': {}", self.name, deal, e)),
+        );
+        self
+    }
+
+    pub fn stacks(mut self, stacks: [u16; 3]) -> Self {
+        self.stacks = stacks;
+        self
+    }
+
+    pub fn blinds(mut self, small: u16, big: u16) -> Self {
+        self.blinds = (small, big);
+        self
+    }
+
+    pub fn preflop(self, actions: &str) -> Self {
+        self.street(Street::Preflop, actions)
+    }
+
+    pub fn flop(self, actions: &str) -> Self {
+        self.street(Street::Flop, actions)
+    }
+
+    pub fn turn(self, actions: &str) -> Self {
+        self.street(Street::Turn, actions)
+    }
+
+    pub fn river(self, actions: &str) -> Self {
+        self.street(Street::River, actions)
+    }
+
+    /// Comma-separated actions in the order they are taken.
+    pub fn street(mut self, street: Street, actions: &str) -> Self {
+        for action in actions.split(',') {
+            let step = action
+                .parse()
+                .unwrap_or_else(|e| panic!("{}: {}", self.name, e));
+            self.steps.push(ScriptedStep {
+                street,
+                step,
+                expect: Expect::default(),
+            });
+        }
+        self
+    }
+
+    pub fn expect_stacks(mut self, stacks: [u16; 3]) -> Self {
+        self.last_expect().stacks = Some(stacks);
+        self
+    }
+
+    pub fn expect_bets(mut self, bets: [u16; 3]) -> Self {
+        self.last_expect().bets = Some(bets);
+        self
+    }
+
+    /// Bitmask of folded players, P1=1, P2=2, P3=4.
+    pub fn expect_folded(mut self, folded: u8) -> Self {
+        self.last_expect().folded = Some(folded);
+        self
+    }
+
+    pub fn expect_state(mut self, state: GameState) -> Self {
+        self.last_expect().state = Some(state);
+        self
+    }
+
+    fn last_expect(&mut self) -> &mut Expect {
+        let name = &self.name;
+        &mut self
+            .steps
+            .last_mut()
+            .unwrap_or_else(|| panic!("{}: expectations follow an action", name))
+            .expect
+    }
+
+    /// Plays the scenario on a fresh `LocalVM`.
+    pub fn run(&self) -> anyhow::Result<Chip> {
+        self.run_on(LocalVM::new()?)
+    }
+
+    /// Plays the scenario on `vm`, which must not have any games yet.
+    /// Returns the chips at the end.
+    pub fn run_on<M: VMManager<TestnetV0>>(&self, vm: M) -> anyhow::Result<Chip> {
+        let deal = self
+            .deal
+            .ok_or_else(|| anyhow::anyhow!("{}: no cards dealt", self.name))?;
+        let mut table = Table::seat(vm, &deal)?;
+        table.set_stacks(self.stacks, self.blinds)?;
+        table.settle()?;
+
+        for (i, scripted) in self.steps.iter().enumerate() {
+            let context = |e: anyhow::Error| {
+                anyhow::anyhow!(
+                    "{}: step {} ({}: {}): {}",
+                    self.name,
+                    i + 1,
+                    scripted.street.name(),
+                    scripted.step,
+                    e
+                )
+            };
+            table.act(scripted.street, scripted.step).map_err(context)?;
+            table.settle().map_err(context)?;
+            table.check(&scripted.expect).map_err(context)?;
+        }
+        table.chips()
+    }
+}
+
+/// Three dev accounts sat at game 0, with the keys each one holds. Betting
+/// is left to the caller; everything else the state waits on is submitted
+/// by `advance`.
+pub struct Table<M: VMManager<TestnetV0>> {
+    pub poker: MentalPokerAleo<TestnetV0, M>,
+    players: [Account<TestnetV0>; 3],
+    secrets: [(Scalar<TestnetV0>, Scalar<TestnetV0>); 3],
+    keys: [Keys<TestnetV0>; 3],
+    /// Permutations for the next hand's shuffles, in shuffle order.
+    shuffles: VecDeque<[usize; 52]>,
+    /// How long each transition submitted so far took.
+    pub samples: Vec<TransitionSample>,
+}
+
+impl<M: VMManager<TestnetV0>> Table<M> {
+    /// Funds the players, creates the game and joins it, dealing `deal`.
+    pub fn seat(vm: M, deal: &Deal) -> anyhow::Result<Self> {
+        let players = [
+            Account::dev_account(0)?,
+            Account::dev_account(1)?,
+            Account::dev_account(2)?,
+        ];
+        let credits = CreditsAleo::new(&players[0], vm.clone())?;
+        for player in &players[1..] {
+            credits.transfer_public(&players[0], player.address(), 1_000_000_000)?;
+        }
+        let poker = MentalPokerAleo::new(&players[0], vm)?;
+
+        let mut rng = TestRng::default();
+        let secrets: [(Scalar<TestnetV0>, Scalar<TestnetV0>); 3] = std::array::from_fn(|_| {
+            let secret = Scalar::rand(&mut rng);
+            (secret, Inverse::inverse(&secret).unwrap())
+        });
+        let mut perms = rigged_permutations(deal, 3, &mut rand::thread_rng()).into_iter();
+        let mut samples = Vec::new();
+
+        let (_, control) = permute_deck(initialized_deck::<TestnetV0>(), &perms.next().unwrap());
+        let (secret, inverse) = secrets[0];
+        let (p1_keys, _) = timed(&mut samples, Transition::CreateGame, || {
+            poker.create_game(&players[0], BUY_IN, control, secret, inverse, 0, u8::MAX)
+        })?;
+        let mut joined = Vec::new();
+        for (seat, perm) in (1..3).zip(perms) {
+            let deck = poker
+                .get_decks(GAME_ID)
+                .ok_or_else(|| anyhow::anyhow!("Deck not found"))?;
+            let (_, control) = permute_deck(deck, &perm);
+            let (secret, inverse) = secrets[seat];
+            let (keys, _) = timed(&mut samples, Transition::JoinGame, || {
+                poker.join_game(
+                    &players[seat],
+                    GAME_ID,
+                    BUY_IN,
+                    deck,
+                    control,
+                    secret,
+                    inverse,
+                    0,
+                )
+            })?;
+            joined.push(keys);
+        }
+        let [p2_keys, p3_keys]: [Keys<TestnetV0>; 2] = joined.try_into().unwrap();
+
+        Ok(Self {
+            poker,
+            players,
+            secrets,
+            keys: [p1_keys, p2_keys, p3_keys],
+            shuffles: VecDeque::new(),
+            samples,
+        })
+    }
+
+    /// Replaces the 1000-chip stacks and 5/10 blinds the program starts
+    /// with. The blinds are posted from the new stacks around the current
+    /// dealer, all-in when a stack is short.
+    pub fn set_stacks(&self, stacks: [u16; 3], (small, big): (u16, u16)) -> anyhow::Result<()> {
+        let game = self.game()?;
+        let posted = posted_blinds(stacks, game.dealer_button, game.players_out, (small, big));
+        self.poker.set_games(
+            GAME_ID,
+            Game::new(
+                game.player1,
+                game.player2,
+                game.player3,
+                game.buy_in,
+                game.password_hash,
+                game.state,
+                game.dealer_button,
+                game.players_out,
+                game.players_folded,
+                game.last_bet,
+                small,
+                big,
+                game.blind_frequency,
+                game.remaining_hands,
+                game.hands_played,
+                game.last_raise_size,
+            ),
+        );
+        self.poker.set_chips(
+            GAME_ID,
+            Chips::new(
+                stacks[0] - posted[0],
+                stacks[1] - posted[1],
+                stacks[2] - posted[2],
+                posted[0],
+                posted[1],
+                posted[2],
+            ),
+        );
+        Ok(())
+    }
+
+    /// Gives every chip to `winner` and knocks the others out, leaving the
+    /// game waiting on the winner's `claim_prize`.
+    pub fn end_game(&self, winner: u8) -> anyhow::Result<()> {
+        let chips = self.chips()?;
+        let total = chips.player1 + chips.player2 + chips.player3 + chips.pot;
+        let mut stacks = [0; 3];
+        stacks[winner as usize - 1] = total;
+        self.poker.set_chips(
+            GAME_ID,
+            Chips::new(stacks[0], stacks[1], stacks[2], 0, 0, 0),
+        );
+        let mut game = self.game()?;
+        game.players_out = 0b111 & !(1 << (winner - 1));
+        game.state = GameState::P1Claim.to_u8() + winner - 1;
+        self.poker.set_games(GAME_ID, game);
+        Ok(())
+    }
+
+    /// Stacks the deck for the next hand. Has to be called before its
+    /// `new_hand`; hands without a deal are shuffled at random.
+    pub fn deal_next(&mut self, deal: &Deal) -> anyhow::Result<()> {
+        let shufflers = 3 - self.game()?.players_out.count_ones() as usize;
+        self.shuffles = rigged_permutations(deal, shufflers, &mut rand::thread_rng()).into();
+        Ok(())
+    }
+
+    pub fn game(&self) -> anyhow::Result<Game<TestnetV0>> {
+        self.poker
+            .get_games(GAME_ID)
+            .ok_or_else(|| anyhow::anyhow!("Game {} not found", GAME_ID))
+    }
+
+    pub fn state(&self) -> anyhow::Result<GameState> {
+        let state = self.game()?.state;
+        GameState::from_u8(state).ok_or_else(|| anyhow::anyhow!("Unknown state {}", state))
+    }
+
+    pub fn chips(&self) -> anyhow::Result<Chip> {
+        let chips = self
+            .poker
+            .get_chips(GAME_ID)
+            .ok_or_else(|| anyhow::anyhow!("Chips not found"))?;
+        Ok(Chip {
+            player1: chips.player1,
+            player2: chips.player2,
+            player3: chips.player3,
+            player1_bet: chips.player1_bet,
+            player2_bet: chips.player2_bet,
+            player3_bet: chips.player3_bet,
+            pot: chips.player1_bet + chips.player2_bet + chips.player3_bet,
+        })
+    }
+
+    /// Submits `step` after checking that it is that player's turn on
+    /// `street`. A call for more than the stack puts the player all in.
+    pub fn act(&mut self, street: Street, step: Step) -> anyhow::Result<()> {
+        let state = self.state()?;
+        if Street::of_state(state.to_u8()) != Some(street)
+            || state.current_player() != Some(step.player)
+        {
+            anyhow::bail!("not P{}'s turn in state {:?}", step.player, state);
+        }
+
+        let player = &self.players[step.player as usize - 1];
+        let poker = &self.poker;
+        if step.action == Action::Fold {
+            timed(&mut self.samples, Transition::Fold, || {
+                poker.fold(player, GAME_ID)
+            })?;
+            return Ok(());
+        }
+        let chips = self.chips()?;
+        let stack = chips.get_chips(step.player);
+        let amount = match step.action {
+            Action::Check => 0,
+            Action::Call => (chips.highest_bet() - chips.get_current_bet(step.player)).min(stack),
+            Action::Bet(amount) => amount,
+            Action::AllIn => stack,
+            Action::Fold => unreachable!(),
+        };
+        timed(&mut self.samples, Transition::Bet, || {
+            poker.bet(player, GAME_ID, amount)
+        })?;
+        Ok(())
+    }
+
+    /// Submits decryptions, showdowns and the hand comparison until the
+    /// game waits on a bet or the hand is over.
+    pub fn settle(&mut self) -> anyhow::Result<()> {
+        while !self.hand_over()? && self.advance()? {}
+        Ok(())
+    }
+
+    fn hand_over(&self) -> anyhow::Result<bool> {
+        Ok(matches!(
+            self.state()?,
+            GameState::P1NewShuffle
+                | GameState::P2NewShuffle
+                | GameState::P2Shuffle
+                | GameState::P3Shuffle
+                | GameState::P1Claim
+                | GameState::P2Claim
+                | GameState::P3Claim
+        ))
+    }
+
+    /// Submits the one transition the game is waiting on, unless that is a
+    /// bet. Returns false when it waits on a bet or the game is over.
+    pub fn advance(&mut self) -> anyhow::Result<bool> {
+        let state = self.state()?;
+        if state == GameState::Compare {
+            let dealer = self.game()?.dealer_button;
+            let player = &self.players[dealer.trailing_zeros() as usize];
+            let poker = &self.poker;
+            timed(&mut self.samples, Transition::CompareHands, || {
+                poker.compare_hands(player, GAME_ID)
+            })?;
+            return Ok(true);
+        }
+        let Some(player_id) = state.current_player() else {
+            return Ok(false);
+        };
+        let seat = player_id as usize - 1;
+        let player = &self.players[seat];
+        let keys = self.keys[seat].clone();
+        let (secret, inverse) = self.secrets[seat];
+        let poker = &self.poker;
+        let samples = &mut self.samples;
+        let cards = || {
+            poker
+                .get_cards(GAME_ID)
+                .ok_or_else(|| anyhow::anyhow!("Cards not found"))
+        };
+
+        let (keys, _) = match state {
+            GameState::P1DecHand | GameState::P2DecHand | GameState::P3DecHand => {
+                let (first, second) = get_other_players_cards(player_id, &cards()?);
+                timed(samples, Transition::DecryptHands, || {
+                    poker.decrypt_hands(player, GAME_ID, first, second, keys)
+                })?
+            }
+            GameState::P1DecFlop | GameState::P2DecFlop | GameState::P3DecFlop => {
+                let flop = cards()?.flop;
+                timed(samples, Transition::DecryptFlop, || {
+                    poker.decrypt_flop(player, GAME_ID, flop, keys)
+                })?
+            }
+            GameState::P1DecTurn | GameState::P2DecTurn | GameState::P3DecTurn => {
+                let turn = cards()?.turn;
+                timed(samples, Transition::DecryptTurnRiver, || {
+                    poker.decrypt_turn_river(player, GAME_ID, turn, keys)
+                })?
+            }
+            GameState::P1DecRiver | GameState::P2DecRiver | GameState::P3DecRiver => {
+                let river = cards()?.river;
+                timed(samples, Transition::DecryptTurnRiver, || {
+                    poker.decrypt_turn_river(player, GAME_ID, river, keys)
+                })?
+            }
+            GameState::P1Showdown | GameState::P2Showdown | GameState::P3Showdown => {
+                let hand = get_player_cards(player_id, &cards()?);
+                timed(samples, Transition::Showdown, || {
+                    poker.showdown(player, GAME_ID, hand, keys)
+                })?
+            }
+            GameState::P1NewShuffle | GameState::P2NewShuffle => {
+                let control = shuffle(&mut self.shuffles, initialized_deck::<TestnetV0>());
+                timed(samples, Transition::NewHand, || {
+                    poker.new_hand(player, GAME_ID, control, secret, inverse)
+                })?
+            }
+            GameState::P2Shuffle | GameState::P3Shuffle => {
+                let deck = poker
+                    .get_decks(GAME_ID)
+                    .ok_or_else(|| anyhow::anyhow!("Deck not found"))?;
+                let control = shuffle(&mut self.shuffles, deck);
+                timed(samples, Transition::ShuffleDeck, || {
+                    poker.shuffle_deck(player, GAME_ID, deck, control, secret, inverse)
+                })?
+            }
+            GameState::P1Claim | GameState::P2Claim | GameState::P3Claim => {
+                let game = poker
+                    .get_games(GAME_ID)
+                    .ok_or_else(|| anyhow::anyhow!("Game {} not found", GAME_ID))?;
+                timed(samples, Transition::ClaimPrize, || {
+                    poker.claim_prize(player, GAME_ID, game.buy_in * 3)
+                })?;
+                return Ok(true);
+            }
+            _ => return Ok(false),
+        };
+        self.keys[seat] = keys;
+        Ok(true)
+    }
+
+    fn check(&self, expect: &Expect) -> anyhow::Result<()> {
+        let chips = self.chips()?;
+        let stacks = [chips.player1, chips.player2, chips.player3];
+        let bets = [chips.player1_bet, chips.player2_bet, chips.player3_bet];
+        if let Some(expected) = expect.stacks
+            && expected != stacks
+        {
+            anyhow::bail!("expected stacks {:?}, got {:?}", expected, stacks);
+        }
+        if let Some(expected) = expect.bets
+            && expected != bets
+        {
+            anyhow::bail!("expected bets {:?}, got {:?}", expected, bets);
+        }
+        let game = self.game()?;
+        if let Some(expected) = expect.folded
+            && expected != game.players_folded
+        {
+            anyhow::bail!(
+                "expected folded {:03b}, got {:03b}",
+                expected,
+                game.players_folded
+            );
+        }
+        if let Some(expected) = expect.state {
+            let state = self.state()?;
+            if expected != state {
+                anyhow::bail!("expected state {:?}, got {:?}", expected, state);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The blinds each seat posts, as `new_hand` works them out: heads-up the
+/// dealer posts the small blind, otherwise the two seats after the dealer
+/// do. A short stack goes all-in for what it has.
+fn posted_blinds(stacks: [u16; 3], dealer: u8, out: u8, (small, big): (u16, u16)) -> [u16; 3] {
+    let dealer = dealer.trailing_zeros() as usize;
+    let after = |seat: usize| {
+        (1..3)
+            .map(|i| (seat + i) % 3)
+            .find(|next| out & (1 << next) == 0)
+            .unwrap_or(seat)
+    };
+    let (sb, bb) = if out != 0 {
+        (dealer, after(dealer))
+    } else {
+        ((dealer + 1) % 3, (dealer + 2) % 3)
+    };
+    let mut posted = [0; 3];
+    posted[sb] = small.min(stacks[sb]);
+    posted[bb] = big.min(stacks[bb]);
+    posted
+}
+
+fn shuffle(shuffles: &mut VecDeque<[usize; 52]>, deck: [Group<TestnetV0>; 52]) -> [bool; 249] {
+    let (_, control) = match shuffles.pop_front() {
+        Some(perm) => permute_deck(deck, &perm),
+        None => shuffle_deck(deck),
+    };
+    control
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_steps() {
+        let step: Step = "P3 bet 60".parse().unwrap();
+        assert_eq!(
+            step,
+            Step {
+                player: 3,
+                action: Action::Bet(60)
+            }
+        );
+        assert_eq!(step.to_string(), "p3 bet 60");
+        assert_eq!("p1 all-in".parse::<Step>().unwrap().action, Action::AllIn);
+        assert_eq!(
+            "p2 raise 40".parse::<Step>().unwrap().action,
+            Action::Bet(40)
+        );
+        assert!("p4 fold".parse::<Step>().is_err());
+        assert!("p1 bet".parse::<Step>().is_err());
+        assert!("p1 limp".parse::<Step>().is_err());
+    }
+
+    #[test]
+    fn test_posted_blinds() {
+        let stacks = [1000, 1000, 1000];
+        assert_eq!(posted_blinds(stacks, 0b100, 0, (5, 10)), [5, 10, 0]);
+        assert_eq!(posted_blinds(stacks, 0b001, 0, (5, 10)), [0, 5, 10]);
+        assert_eq!(posted_blinds(stacks, 0b010, 0, (5, 10)), [10, 0, 5]);
+        // Heads-up the dealer posts the small blind.
+        assert_eq!(posted_blinds(stacks, 0b100, 0b010, (5, 10)), [10, 0, 5]);
+        assert_eq!(posted_blinds(stacks, 0b001, 0b010, (5, 10)), [5, 0, 10]);
+        // Short stacks post what they have.
+        assert_eq!(posted_blinds([3, 7, 1000], 0b100, 0, (5, 10)), [3, 7, 0]);
+    }
+
+    #[test]
+    fn test_builder_attaches_expectations() {
+        let scenario = Scenario::new("builder")
+            .deal("Ah Ad / Kc Qd / 9s 8h / 2c 7d Jh 4s 3c")
+            .blinds(25, 50)
+            .preflop("p3 call, p1 call, p2 check")
+            .expect_stacks([950, 950, 950])
+            .expect_state(GameState::P1BetFlop)
+            .flop("p1 bet 100");
+
+        assert_eq!(scenario.blinds, (25, 50));
+        assert_eq!(scenario.steps.len(), 4);
+        assert_eq!(scenario.steps[0].expect, Expect::default());
+        assert_eq!(
+            scenario.steps[2].expect,
+            Expect {
+                stacks: Some([950, 950, 950]),
+                state: Some(GameState::P1BetFlop),
+                ..Expect::default()
+            }
+        );
+        assert_eq!(scenario.steps[3].street, Street::Flop);
+    }
+}
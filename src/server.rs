@@ -0,0 +1,455 @@
+use crate::cards::PlayingCard;
+use crate::events::GameEvent;
+use crate::game::{Card, Chip, Game};
+use crate::game_state::{BettingAction, describe_game_state};
+use serde::Serialize;
+use serde_json::{Value, json};
+use std::collections::{HashMap, HashSet};
+use std::io::ErrorKind;
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::Duration;
+use tungstenite::Message;
+
+const TICK: Duration = Duration::from_millis(20);
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const GAME_ERROR: i64 = -32000;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SeatState {
+    pub seat: usize,
+    pub game_id: Option<u32>,
+    pub player_id: u8,
+    pub state: Option<u8>,
+    pub description: Option<String>,
+    pub hand: Option<[PlayingCard; 2]>,
+    pub cards: Option<Card>,
+    pub chips: Option<Chip>,
+    pub betting: Option<BettingOptions>,
+    pub winner: Option<u8>,
+    pub busy: Option<String>,
+    pub invite_code: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct BettingOptions {
+    pub call_amount: u64,
+    pub min_raise: u64,
+    pub max_raise: u64,
+}
+
+impl SeatState {
+    pub fn new(seat: usize, game: &Game) -> Self {
+        let model = &game.model;
+        Self {
+            seat,
+            game_id: model.game_id,
+            player_id: model.current_player_id,
+            state: model.current_state.map(|s| s.to_u8()),
+            description: model
+                .current_state
+                .map(|s| describe_game_state(s).to_string()),
+            hand: model.decrypted_hand,
+            cards: model.card,
+            chips: model.chip,
+            betting: model.betting_ui.as_ref().map(|b| BettingOptions {
+                call_amount: b.call_amount,
+                min_raise: b.min_raise,
+                max_raise: b.max_raise,
+            }),
+            winner: model.game_winner,
+            busy: model.background_task.clone(),
+            invite_code: model.invite_code.clone(),
+        }
+    }
+}
+
+struct Seat {
+    game: Game,
+    events: Receiver<GameEvent>,
+    last_state: Option<SeatState>,
+}
+
+struct Connection {
+    outbox: Sender<String>,
+    subscriptions: HashSet<usize>,
+}
+
+enum Incoming {
+    Connected { id: u64, outbox: Sender<String> },
+    Request { id: u64, text: String },
+    Disconnected(u64),
+}
+
+#[derive(Debug)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+/// JSON-RPC 2.0 over WebSocket. Every seat is a `Game` driven by the server
+/// loop; proving stays local to this process.
+pub struct Server {
+    seats: Vec<Seat>,
+    connections: HashMap<u64, Connection>,
+    incoming: Receiver<Incoming>,
+    local_addr: SocketAddr,
+}
+
+impl Server {
+    pub fn bind(addr: impl ToSocketAddrs, games: Vec<Game>) -> anyhow::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let local_addr = listener.local_addr()?;
+        let (tx, incoming) = mpsc::channel();
+
+        thread::spawn(move || {
+            let mut next_id = 0u64;
+            for stream in listener.incoming() {
+                let Ok(stream) = stream else { continue };
+                next_id += 1;
+                let id = next_id;
+                let tx = tx.clone();
+                thread::spawn(move || {
+                    if let Err(e) = serve_connection(stream, id, &tx) {
+                        log::warn!("Connection {} closed: {}", id, e);
+                    }
+                    let _ = tx.send(Incoming::Disconnected(id));
+                });
+            }
+        });
+
+        let seats = games
+            .into_iter()
+            .map(|mut game| Seat {
+                events: game.subscribe(),
+                game,
+                last_state: None,
+            })
+            .collect();
+
+        Ok(Self {
+            seats,
+            connections: HashMap::new(),
+            incoming,
+            local_addr,
+        })
+    }
+
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    pub fn run(mut self) -> anyhow::Result<()> {
+        log::info!(
+            "Serving {} seat(s) on ws://{}",
+            self.seats.len(),
+            self.local_addr
+        );
+        loop {
+            match self.incoming.recv_timeout(TICK) {
+                Ok(incoming) => {
+                    self.handle_incoming(incoming);
+                    while let Ok(incoming) = self.incoming.try_recv() {
+                        self.handle_incoming(incoming);
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => {
+                    anyhow::bail!("Listener thread stopped")
+                }
+            }
+
+            for seat in &mut self.seats {
+                seat.game.drive();
+            }
+            self.publish_updates();
+        }
+    }
+
+    fn handle_incoming(&mut self, incoming: Incoming) {
+        match incoming {
+            Incoming::Connected { id, outbox } => {
+                self.connections.insert(
+                    id,
+                    Connection {
+                        outbox,
+                        subscriptions: HashSet::new(),
+                    },
+                );
+            }
+            Incoming::Disconnected(id) => {
+                self.connections.remove(&id);
+            }
+            Incoming::Request { id, text } => {
+                if let Some(response) = self.handle_request(id, &text)
+                    && let Some(connection) = self.connections.get(&id)
+                {
+                    let _ = connection.outbox.send(response.to_string());
+                }
+            }
+        }
+    }
+
+    fn handle_request(&mut self, connection: u64, text: &str) -> Option<Value> {
+        let request: Value = match serde_json::from_str(text) {
+            Ok(request) => request,
+            Err(e) => {
+                return Some(error_response(
+                    Value::Null,
+                    RpcError::new(PARSE_ERROR, e.to_string()),
+                ));
+            }
+        };
+        let id = request.get("id").cloned();
+        let Some(method) = request.get("method").and_then(Value::as_str) else {
+            return Some(error_response(
+                id.unwrap_or(Value::Null),
+                RpcError::new(INVALID_REQUEST, "Missing method"),
+            ));
+        };
+        let params = request.get("params").cloned().unwrap_or(Value::Null);
+
+        let result = self.call(connection, method, &params);
+        // Requests without an id are notifications and get no reply.
+        let id = id?;
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(e) => error_response(id, e),
+        })
+    }
+
+    fn call(&mut self, connection: u64, method: &str, params: &Value) -> Result<Value, RpcError> {
+        match method {
+            "seats" => Ok(json!(
+                self.seats
+                    .iter()
+                    .enumerate()
+                    .map(|(i, seat)| SeatState::new(i, &seat.game))
+                    .collect::<Vec<_>>()
+            )),
+            "state" => {
+                let seat = self.seat_param(params)?;
+                Ok(json!(SeatState::new(seat, &self.seats[seat].game)))
+            }
+            "subscribe" => {
+                let seat = self.seat_param(params)?;
+                if let Some(connection) = self.connections.get_mut(&connection) {
+                    connection.subscriptions.insert(seat);
+                    let state = SeatState::new(seat, &self.seats[seat].game);
+                    let _ = connection.outbox.send(notification("state", json!(state)));
+                }
+                Ok(json!(true))
+            }
+            "unsubscribe" => {
+                let seat = self.seat_param(params)?;
+                if let Some(connection) = self.connections.get_mut(&connection) {
+                    connection.subscriptions.remove(&seat);
+                }
+                Ok(json!(true))
+            }
+            "create" => {
+                let seat = self.seat_param(params)?;
+                let game = &mut self.seats[seat].game;
+                if game.model.game_id.is_some() {
+                    return Err(RpcError::new(GAME_ERROR, "Seat is already in a game"));
+                }
+                let buy_in = params
+                    .get("buy_in")
+                    .and_then(Value::as_f64)
+                    .unwrap_or(game.model.ui.buy_in);
+                if buy_in <= 0.0 {
+                    return Err(RpcError::new(INVALID_PARAMS, "buy_in must be positive"));
+                }
+                let blind_frequency = match params.get("blind_frequency") {
+                    None => game.model.ui.blind_frequency,
+                    Some(v) => v.as_u64().filter(|f| (1..=99).contains(f)).ok_or_else(|| {
+                        RpcError::new(INVALID_PARAMS, "blind_frequency must be 1-99")
+                    })? as u8,
+                };
+                let password = password_param(params)?;
+                game.create_game(buy_in, blind_frequency, password);
+                Ok(json!(true))
+            }
+            "join" => {
+                let seat = self.seat_param(params)?;
+                let game_id = params
+                    .get("game_id")
+                    .and_then(Value::as_u64)
+                    .and_then(|id| u32::try_from(id).ok())
+                    .ok_or_else(|| RpcError::new(INVALID_PARAMS, "Missing game_id"))?;
+                let password = password_param(params)?;
+                let game = &mut self.seats[seat].game;
+                if game.model.game_id.is_some() {
+                    return Err(RpcError::new(GAME_ERROR, "Seat is already in a game"));
+                }
+                game.join_game(game_id, password)
+                    .map_err(|e| RpcError::new(GAME_ERROR, e.to_string()))?;
+                Ok(json!(true))
+            }
+            "bet" => {
+                let seat = self.seat_param(params)?;
+                let action = match params.get("action").and_then(Value::as_str) {
+                    Some("fold") => BettingAction::Fold,
+                    Some("call") | Some("check") => BettingAction::Call,
+                    Some("raise") => BettingAction::Raise,
+                    _ => {
+                        return Err(RpcError::new(
+                            INVALID_PARAMS,
+                            "action must be fold, call or raise",
+                        ));
+                    }
+                };
+                let game = &mut self.seats[seat].game;
+                let amount = match action {
+                    BettingAction::Raise => {
+                        let betting = game.model.betting_ui.as_ref().ok_or_else(|| {
+                            RpcError::new(GAME_ERROR, "It is not your turn to bet")
+                        })?;
+                        let amount = params
+                            .get("amount")
+                            .and_then(Value::as_u64)
+                            .ok_or_else(|| RpcError::new(INVALID_PARAMS, "Missing amount"))?;
+                        if amount < betting.min_raise || amount > betting.max_raise {
+                            return Err(RpcError::new(
+                                INVALID_PARAMS,
+                                format!(
+                                    "Raise must be between {} and {}",
+                                    betting.min_raise, betting.max_raise
+                                ),
+                            ));
+                        }
+                        amount
+                    }
+                    _ => 0,
+                };
+                game.place_bet(action, amount)
+                    .map_err(|e| RpcError::new(GAME_ERROR, e.to_string()))?;
+                Ok(json!(true))
+            }
+            _ => Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                format!("Unknown method '{}'", method),
+            )),
+        }
+    }
+
+    fn seat_param(&self, params: &Value) -> Result<usize, RpcError> {
+        let seat = params.get("seat").and_then(Value::as_u64).unwrap_or(0) as usize;
+        if seat >= self.seats.len() {
+            return Err(RpcError::new(INVALID_PARAMS, format!("No seat {}", seat)));
+        }
+        Ok(seat)
+    }
+
+    fn publish_updates(&mut self) {
+        for (index, seat) in self.seats.iter_mut().enumerate() {
+            let events: Vec<GameEvent> = seat.events.try_iter().collect();
+            let state = SeatState::new(index, &seat.game);
+            let state_changed = seat.last_state.as_ref() != Some(&state);
+
+            let subscribers = self
+                .connections
+                .values()
+                .filter(|c| c.subscriptions.contains(&index));
+            for connection in subscribers {
+                for event in &events {
+                    let _ = connection.outbox.send(notification(
+                        "event",
+                        json!({ "seat": index, "event": event }),
+                    ));
+                }
+                if state_changed {
+                    let _ = connection.outbox.send(notification("state", json!(state)));
+                }
+            }
+            seat.last_state = Some(state);
+        }
+    }
+}
+
+fn password_param(params: &Value) -> Result<u128, RpcError> {
+    match params.get("password") {
+        None | Some(Value::Null) => Ok(0),
+        Some(Value::String(s)) => s
+            .parse()
+            .map_err(|_| RpcError::new(INVALID_PARAMS, "password must be a number")),
+        Some(v) => v
+            .as_u64()
+            .map(u128::from)
+            .ok_or_else(|| RpcError::new(INVALID_PARAMS, "password must be a number")),
+    }
+}
+
+fn notification(method: &str, params: Value) -> String {
+    json!({ "jsonrpc": "2.0", "method": method, "params": params }).to_string()
+}
+
+fn error_response(id: Value, error: RpcError) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": error.code, "message": error.message },
+    })
+}
+
+fn serve_connection(stream: TcpStream, id: u64, tx: &Sender<Incoming>) -> anyhow::Result<()> {
+    let mut socket = tungstenite::accept(stream)?;
+    socket.get_ref().set_read_timeout(Some(TICK))?;
+    let (outbox, outgoing) = mpsc::channel::<String>();
+    tx.send(Incoming::Connected { id, outbox })?;
+
+    loop {
+        match socket.read() {
+            Ok(Message::Text(text)) => tx.send(Incoming::Request { id, text })?,
+            Ok(Message::Close(_)) => return Ok(()),
+            Ok(_) => {}
+            Err(tungstenite::Error::Io(e))
+                if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+            Err(tungstenite::Error::ConnectionClosed) => return Ok(()),
+            Err(e) => return Err(e.into()),
+        }
+        for message in outgoing.try_iter() {
+            socket.send(Message::Text(message))?;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_password_param() {
+        assert_eq!(password_param(&json!({})).unwrap(), 0);
+        assert_eq!(password_param(&json!({ "password": 42 })).unwrap(), 42);
+        assert_eq!(
+            password_param(&json!({ "password": "340282366920938463463374607431768211455" }))
+                .unwrap(),
+            u128::MAX
+        );
+        assert!(password_param(&json!({ "password": "abc" })).is_err());
+        assert!(password_param(&json!({ "password": -1 })).is_err());
+    }
+
+    #[test]
+    fn test_error_response_shape() {
+        let response = error_response(json!(7), RpcError::new(METHOD_NOT_FOUND, "nope"));
+        assert_eq!(response["id"], 7);
+        assert_eq!(response["error"]["code"], METHOD_NOT_FOUND);
+        assert_eq!(response["error"]["message"], "nope");
+    }
+}
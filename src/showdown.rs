@@ -0,0 +1,305 @@
+use crate::cards::PlayingCard;
+use crate::events::GameSnapshot;
+use crate::game::Card;
+use crate::hand::{MadeHand, best_hand};
+use crate::pots::{active_players, side_pots};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PotResult {
+    pub amount: u16,
+    pub winners: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlayerResult {
+    pub player: u8,
+    /// Stack after the hand minus the stack before any chips went in.
+    pub change: i32,
+    pub folded: bool,
+    pub hand: Option<MadeHand>,
+}
+
+/// What happened in a finished hand, derived from the snapshots either side
+/// of `compare_hands`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HandResult {
+    pub hand_number: u8,
+    pub pots: Vec<PotResult>,
+    pub players: Vec<PlayerResult>,
+    pub uncontested: Option<u8>,
+}
+
+impl HandResult {
+    pub fn headline(&self) -> String {
+        if let Some(player) = self.uncontested {
+            return format!("P{} wins uncontested", player);
+        }
+        let Some(main) = self.pots.first().filter(|pot| !pot.winners.is_empty()) else {
+            return "Hand complete".to_string();
+        };
+        let hand = self
+            .players
+            .iter()
+            .find(|p| p.player == main.winners[0])
+            .and_then(|p| p.hand.as_ref());
+        let players: Vec<String> = main.winners.iter().map(|p| format!("P{}", p)).collect();
+        let players = players.join(" and ");
+        match (main.winners.len(), hand) {
+            (1, Some(hand)) => format!("{} wins with {}", players, hand.label()),
+            (1, None) => format!("{} wins", players),
+            (_, Some(hand)) => format!("{} split with {}", players, hand.label()),
+            (_, None) => format!("{} split the pot", players),
+        }
+    }
+
+    pub fn pot_name(index: usize) -> String {
+        if index == 0 {
+            "Main pot".to_string()
+        } else {
+            format!("Side pot {}", index)
+        }
+    }
+}
+
+/// `None` unless `next` is the first snapshot after the hand `prev` belongs
+/// to was settled. `prev` should carry the hand's final bets, as a snapshot
+/// from the showdown does; the pot winners come from the chips each player
+/// got back, so they match the contract even where hands aren't visible.
+pub fn hand_result(prev: &GameSnapshot, next: &GameSnapshot) -> Option<HandResult> {
+    if next.hands_played != prev.hands_played.wrapping_add(1) {
+        return None;
+    }
+    let (before, after) = (prev.chips?, next.chips?);
+    let active = active_players(prev.players_folded, prev.players_out);
+    let bets = [1, 2, 3].map(|p| before.get_current_bet(p));
+
+    // Depending on timing the reveal may only be visible on one side.
+    let revealed = if face_up(&next.revealed) >= face_up(&prev.revealed) {
+        next.revealed
+    } else {
+        prev.revealed
+    };
+    let board: Vec<PlayingCard> = revealed
+        .flop
+        .iter()
+        .chain([&revealed.turn, &revealed.river])
+        .flatten()
+        .copied()
+        .collect();
+
+    // Blinds for the next hand may already be posted in `next`.
+    let end = |p: u8| after.get_chips(p) + after.get_current_bet(p);
+    let players: Vec<PlayerResult> = (1..=3u8)
+        .filter(|&p| prev.players_out & (1 << (p - 1)) == 0)
+        .map(|p| {
+            let start = before.get_chips(p) + before.get_current_bet(p);
+            let folded = !active[p as usize - 1];
+            let hand = match revealed.get_cards(p) {
+                [Some(first), Some(second)] if !folded && board.len() == 5 => {
+                    let mut cards = vec![first, second];
+                    cards.extend(&board);
+                    best_hand(&cards)
+                }
+                _ => None,
+            };
+            PlayerResult {
+                player: p,
+                change: end(p) as i32 - start as i32,
+                folded,
+                hand,
+            }
+        })
+        .collect();
+
+    // Take each player's winnings apart from the top pot down. Only the
+    // main pot has three contributors, so only it can split unevenly.
+    let mut won = [1, 2, 3].map(|p| end(p).saturating_sub(before.get_chips(p)));
+    let mut pots: Vec<PotResult> = side_pots(bets, active)
+        .into_iter()
+        .rev()
+        .map(|pot| {
+            let winners: Vec<u8> = pot
+                .eligible
+                .iter()
+                .copied()
+                .filter(|&p| won[p as usize - 1] > 0)
+                .collect();
+            for &p in &winners {
+                let share = pot.amount / winners.len() as u16;
+                won[p as usize - 1] = won[p as usize - 1].saturating_sub(share);
+            }
+            PotResult {
+                amount: pot.amount,
+                winners,
+            }
+        })
+        .collect();
+    pots.reverse();
+
+    let still_in: Vec<u8> = (1..=3u8).filter(|&p| active[p as usize - 1]).collect();
+    let uncontested = match still_in.as_slice() {
+        [winner] => Some(*winner),
+        _ => None,
+    };
+
+    Some(HandResult {
+        hand_number: next.hands_played,
+        pots,
+        players,
+        uncontested,
+    })
+}
+
+fn face_up(card: &Card) -> usize {
+    card.flop
+        .iter()
+        .chain([&card.turn, &card.river])
+        .chain(&card.player1)
+        .chain(&card.player2)
+        .chain(&card.player3)
+        .filter(|c| c.is_some())
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::snapshot;
+
+    fn reveal(snapshot: &mut GameSnapshot, hands: [&str; 3], board: &str) {
+        let parse = |s: &str| -> Vec<Option<PlayingCard>> {
+            s.split_whitespace()
+                .map(|c| Some(c.parse().unwrap()))
+                .collect()
+        };
+        for (i, hand) in hands.iter().enumerate() {
+            let cards = parse(hand);
+            if cards.len() == 2 {
+                let cards = [cards[0], cards[1]];
+                match i {
+                    0 => snapshot.revealed.player1 = cards,
+                    1 => snapshot.revealed.player2 = cards,
+                    _ => snapshot.revealed.player3 = cards,
+                }
+            }
+        }
+        let board = parse(board);
+        snapshot.revealed.flop = [board[0], board[1], board[2]];
+        snapshot.revealed.turn = board[3];
+        snapshot.revealed.river = board[4];
+    }
+
+    #[test]
+    fn test_side_pots_at_showdown() {
+        // Same hands as test_sidepot in tests/test_combinations.rs.
+        let mut prev = snapshot(28, 0, [0, 0, 0], [10, 70, 200], 0);
+        reveal(&mut prev, ["As Ks", "Th 9d", "2h 3h"], "Qs 9s 7s 8c 6h");
+        let next = snapshot(28, 1, [30, 120, 130], [0, 0, 0], 0);
+
+        let result = hand_result(&prev, &next).unwrap();
+        assert_eq!(result.hand_number, 1);
+        assert_eq!(result.uncontested, None);
+        assert_eq!(result.headline(), "P1 wins with Flush, Ace high");
+        assert_eq!(
+            result.pots,
+            vec![
+                PotResult {
+                    amount: 30,
+                    winners: vec![1]
+                },
+                PotResult {
+                    amount: 120,
+                    winners: vec![2]
+                },
+                PotResult {
+                    amount: 130,
+                    winners: vec![3]
+                },
+            ]
+        );
+        let changes: Vec<i32> = result.players.iter().map(|p| p.change).collect();
+        assert_eq!(changes, [20, 50, -70]);
+        assert_eq!(
+            result.players[1].hand.as_ref().unwrap().label(),
+            "Straight, Ten high"
+        );
+    }
+
+    #[test]
+    fn test_uncontested_after_folds() {
+        let prev = snapshot(28, 2, [95, 60, 90], [5, 40, 10], 0b101);
+        let next = snapshot(28, 3, [95, 115, 90], [0, 0, 0], 0);
+
+        let result = hand_result(&prev, &next).unwrap();
+        assert_eq!(result.uncontested, Some(2));
+        assert_eq!(result.headline(), "P2 wins uncontested");
+        assert_eq!(
+            result.pots,
+            vec![
+                PotResult {
+                    amount: 15,
+                    winners: vec![2]
+                },
+                PotResult {
+                    amount: 10,
+                    winners: vec![2]
+                },
+                PotResult {
+                    amount: 30,
+                    winners: vec![2]
+                },
+            ]
+        );
+        assert!(result.players[0].folded && result.players[2].folded);
+        let changes: Vec<i32> = result.players.iter().map(|p| p.change).collect();
+        assert_eq!(changes, [-5, 15, -10]);
+    }
+
+    #[test]
+    fn test_split_pot() {
+        let mut prev = snapshot(28, 0, [50, 50, 50], [50, 50, 50], 0);
+        reveal(&mut prev, ["Ah 2c", "As 3d", "Kc Qd"], "Ad Jh 8s 5c 4h");
+        let next = snapshot(28, 1, [125, 125, 50], [0, 0, 0], 0);
+
+        let result = hand_result(&prev, &next).unwrap();
+        assert_eq!(result.pots[0].winners, [1, 2]);
+        assert_eq!(result.headline(), "P1 and P2 split with Pair of Aces");
+    }
+
+    #[test]
+    fn test_winners_from_chips_without_cards() {
+        // P1 takes the main pot, P2 the side pot P1 could not win, and
+        // nobody's cards are visible.
+        let prev = snapshot(28, 4, [50, 0, 0], [50, 100, 100], 0);
+        let next = snapshot(28, 5, [200, 100, 0], [0, 0, 0], 0);
+
+        let result = hand_result(&prev, &next).unwrap();
+        let winners: Vec<Vec<u8>> = result.pots.iter().map(|p| p.winners.clone()).collect();
+        assert_eq!(winners, [vec![1], vec![2]]);
+        assert_eq!(result.headline(), "P1 wins");
+    }
+
+    #[test]
+    fn test_uneven_split() {
+        let prev = snapshot(28, 0, [75, 75, 75], [25, 25, 25], 0);
+        let next = snapshot(28, 1, [113, 112, 75], [0, 0, 0], 0);
+
+        let result = hand_result(&prev, &next).unwrap();
+        assert_eq!(result.pots[0].winners, [1, 2]);
+        assert_eq!(result.headline(), "P1 and P2 split the pot");
+    }
+
+    #[test]
+    fn test_no_result_across_missed_hands() {
+        let prev = snapshot(28, 0, [90, 90, 90], [10, 10, 10], 0);
+        let next = snapshot(28, 2, [80, 100, 90], [0, 0, 0], 0);
+        assert_eq!(hand_result(&prev, &next), None);
+    }
+
+    #[test]
+    fn test_no_result_mid_hand() {
+        let prev = snapshot(28, 0, [90, 90, 90], [10, 10, 10], 0);
+        let next = snapshot(28, 0, [80, 90, 90], [20, 10, 10], 0);
+        assert_eq!(hand_result(&prev, &next), None);
+    }
+}
@@ -0,0 +1,374 @@
+use crate::game::Credits;
+use crate::history::{ActionHistory, ActionKind, Street};
+use crate::showdown::HandResult;
+use redb::{Database, ReadableTable, TableDefinition};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+pub const STATS_FILE: &str = "stats.redb";
+
+/// Counters for one player. Ratios are derived on display so hands from
+/// different sessions add up exactly.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlayerStats {
+    pub hands: u32,
+    /// Hands where the player put chips in preflop beyond the blinds.
+    pub vpip: u32,
+    /// Hands where the player bet or raised preflop.
+    pub pfr: u32,
+    pub bets_raises: u32,
+    pub calls: u32,
+    pub showdowns: u32,
+    pub showdowns_won: u32,
+    pub tournaments: u32,
+    pub tournaments_won: u32,
+    /// Microcredits won minus buy-ins paid.
+    pub net_microcredits: i64,
+}
+
+impl PlayerStats {
+    pub fn add(&mut self, other: &PlayerStats) {
+        self.hands += other.hands;
+        self.vpip += other.vpip;
+        self.pfr += other.pfr;
+        self.bets_raises += other.bets_raises;
+        self.calls += other.calls;
+        self.showdowns += other.showdowns;
+        self.showdowns_won += other.showdowns_won;
+        self.tournaments += other.tournaments;
+        self.tournaments_won += other.tournaments_won;
+        self.net_microcredits += other.net_microcredits;
+    }
+
+    pub fn vpip_pct(&self) -> u32 {
+        percent(self.vpip, self.hands)
+    }
+
+    pub fn pfr_pct(&self) -> u32 {
+        percent(self.pfr, self.hands)
+    }
+
+    /// Bets and raises per call.
+    pub fn aggression(&self) -> f64 {
+        self.bets_raises as f64 / self.calls.max(1) as f64
+    }
+
+    pub fn showdown_win_pct(&self) -> u32 {
+        percent(self.showdowns_won, self.showdowns)
+    }
+
+    pub fn net_credits(&self) -> String {
+        let sign = if self.net_microcredits < 0 { "-" } else { "+" };
+        format!(
+            "{}{}",
+            sign,
+            Credits::from(self.net_microcredits.unsigned_abs())
+        )
+    }
+
+    /// One-line read on a player, short enough for a widget border.
+    pub fn tendencies(&self) -> String {
+        format!(
+            "VPIP {} PFR {} AF {:.1} · {}h",
+            self.vpip_pct(),
+            self.pfr_pct(),
+            self.aggression(),
+            self.hands
+        )
+    }
+}
+
+fn percent(part: u32, whole: u32) -> u32 {
+    (part * 100).checked_div(whole).unwrap_or(0)
+}
+
+/// What `player` did in one finished hand, or `None` if they were not in it.
+pub fn hand_stats(player: u8, history: &ActionHistory, result: &HandResult) -> Option<PlayerStats> {
+    let outcome = result.players.iter().find(|p| p.player == player)?;
+    let mut stats = PlayerStats {
+        hands: 1,
+        ..PlayerStats::default()
+    };
+
+    for (street, actions) in history.streets() {
+        let mut voluntary = false;
+        let mut raised = false;
+        for action in actions.iter().filter(|a| a.player == player) {
+            match action.kind {
+                ActionKind::Call(_) => {
+                    stats.calls += 1;
+                    voluntary = true;
+                }
+                ActionKind::Bet(_) | ActionKind::Raise(_) | ActionKind::AllIn(_) => {
+                    stats.bets_raises += 1;
+                    voluntary = true;
+                    raised = true;
+                }
+                _ => {}
+            }
+        }
+        if *street == Street::Preflop {
+            stats.vpip = voluntary as u32;
+            stats.pfr = raised as u32;
+        }
+    }
+
+    if result.uncontested.is_none() && !outcome.folded {
+        stats.showdowns = 1;
+        stats.showdowns_won = (outcome.change > 0) as u32;
+    }
+    Some(stats)
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AccountStats {
+    pub own: PlayerStats,
+    /// Keyed by opponent address.
+    pub opponents: BTreeMap<String, PlayerStats>,
+}
+
+/// One row per (account, player) pair. The account's own stats are the row
+/// whose player is the account itself.
+const STATS: TableDefinition<(&str, &str), &[u8]> = TableDefinition::new("stats");
+
+/// Stats of every account that has played on this machine, kept in an
+/// embedded database next to the keystore. Rows are mirrored in memory for
+/// rendering, and each hand writes only the rows it changes.
+pub struct StatsDb {
+    db: Database,
+    accounts: BTreeMap<String, AccountStats>,
+}
+
+impl StatsDb {
+    pub fn default_path() -> PathBuf {
+        std::env::var("POKER_STATS")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| PathBuf::from(STATS_FILE))
+    }
+
+    pub fn open_default() -> anyhow::Result<Self> {
+        Self::open(&Self::default_path())
+    }
+
+    pub fn open(path: &Path) -> anyhow::Result<Self> {
+        let db = Database::create(path)
+            .map_err(|e| anyhow::anyhow!("Failed to open {}: {}", path.display(), e))?;
+        Self::load(db)
+    }
+
+    #[cfg(test)]
+    fn in_memory() -> anyhow::Result<Self> {
+        Self::load(Database::builder().create_with_backend(redb::backends::InMemoryBackend::new())?)
+    }
+
+    fn load(db: Database) -> anyhow::Result<Self> {
+        let txn = db.begin_write()?;
+        txn.open_table(STATS)?;
+        txn.commit()?;
+
+        let mut accounts: BTreeMap<String, AccountStats> = BTreeMap::new();
+        let txn = db.begin_read()?;
+        for row in txn.open_table(STATS)?.iter()? {
+            let (key, value) = row?;
+            let (account, player) = key.value();
+            let stats: PlayerStats = serde_json::from_slice(value.value())?;
+            let entry = accounts.entry(account.to_string()).or_default();
+            if player == account {
+                entry.own = stats;
+            } else {
+                entry.opponents.insert(player.to_string(), stats);
+            }
+        }
+        Ok(Self { db, accounts })
+    }
+
+    pub fn account(&self, address: &str) -> Option<&AccountStats> {
+        self.accounts.get(address)
+    }
+
+    pub fn opponent(&self, account: &str, opponent: &str) -> Option<&PlayerStats> {
+        self.account(account)?.opponents.get(opponent)
+    }
+
+    /// Adds a finished hand to `account`'s own stats and to its record of
+    /// each opponent at the table. `seats` holds the addresses of P1-P3.
+    pub fn record_hand(
+        &mut self,
+        account: &str,
+        seats: &[String; 3],
+        history: &ActionHistory,
+        result: &HandResult,
+    ) -> anyhow::Result<()> {
+        let rows = (1..=3u8)
+            .zip(seats)
+            .filter_map(|(player, address)| Some((address, hand_stats(player, history, result)?)));
+        self.add(account, rows)
+    }
+
+    /// Counts a finished tournament for every seat. The winner takes all
+    /// three buy-ins.
+    pub fn record_tournament(
+        &mut self,
+        account: &str,
+        seats: &[String; 3],
+        winner: u8,
+        buy_in: u64,
+    ) -> anyhow::Result<()> {
+        let rows = (1..=3u8).zip(seats).map(|(player, address)| {
+            let won = player == winner;
+            let stats = PlayerStats {
+                tournaments: 1,
+                tournaments_won: won as u32,
+                net_microcredits: if won {
+                    2 * buy_in as i64
+                } else {
+                    -(buy_in as i64)
+                },
+                ..PlayerStats::default()
+            };
+            (address, stats)
+        });
+        self.add(account, rows)
+    }
+
+    /// Adds each player's stats to `account`'s rows in one transaction, and
+    /// to the in-memory copy once it has committed.
+    fn add<'a>(
+        &mut self,
+        account: &str,
+        rows: impl Iterator<Item = (&'a String, PlayerStats)>,
+    ) -> anyhow::Result<()> {
+        let mut updated = Vec::new();
+        let txn = self.db.begin_write()?;
+        {
+            let mut table = txn.open_table(STATS)?;
+            for (address, stats) in rows {
+                let mut total = self.player(account, address);
+                total.add(&stats);
+                table.insert(
+                    (account, address.as_str()),
+                    serde_json::to_vec(&total)?.as_slice(),
+                )?;
+                updated.push((address, total));
+            }
+        }
+        txn.commit()?;
+
+        let entry = self.accounts.entry(account.to_string()).or_default();
+        for (address, total) in updated {
+            if address == account {
+                entry.own = total;
+            } else {
+                entry.opponents.insert(address.clone(), total);
+            }
+        }
+        Ok(())
+    }
+
+    fn player(&self, account: &str, address: &str) -> PlayerStats {
+        if address == account {
+            self.account(account).map(|a| a.own).unwrap_or_default()
+        } else {
+            self.opponent(account, address).copied().unwrap_or_default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{GameSnapshot, diff, snapshot};
+    use crate::showdown::hand_result;
+
+    /// P1 raises, P2 folds, P3 calls, P3 bets the flop and P1 folds.
+    fn played_hand() -> (ActionHistory, HandResult) {
+        let snapshots = [
+            snapshot(33, 0, [100, 100, 100], [0, 0, 0], 0),
+            snapshot(2, 0, [100, 95, 90], [0, 5, 10], 0),
+            snapshot(5, 0, [100, 95, 90], [0, 5, 10], 0),
+            snapshot(6, 0, [60, 95, 90], [40, 5, 10], 0),
+            snapshot(7, 0, [60, 95, 90], [40, 5, 10], 0b010),
+            snapshot(8, 0, [60, 95, 60], [40, 5, 40], 0b010),
+            snapshot(11, 0, [60, 95, 60], [40, 5, 40], 0b010),
+            snapshot(13, 0, [60, 95, 60], [40, 5, 40], 0b010),
+            snapshot(11, 0, [60, 95, 40], [40, 5, 60], 0b010),
+            snapshot(11, 0, [60, 95, 40], [40, 5, 60], 0b011),
+        ];
+        let mut history = ActionHistory::default();
+        let mut prev: Option<&GameSnapshot> = None;
+        for next in &snapshots {
+            history.record(prev, next, &diff(prev, next));
+            prev = Some(next);
+        }
+        let last = snapshots.last().unwrap();
+        let settled = snapshot(32, 1, [60, 95, 145], [0, 0, 0], 0);
+        (history, hand_result(last, &settled).unwrap())
+    }
+
+    fn seats() -> [String; 3] {
+        ["aleo1me", "aleo1bob", "aleo1carol"].map(String::from)
+    }
+
+    #[test]
+    fn test_hand_stats() {
+        let (history, result) = played_hand();
+        let p1 = hand_stats(1, &history, &result).unwrap();
+        assert_eq!((p1.vpip, p1.pfr, p1.bets_raises, p1.calls), (1, 1, 1, 0));
+        let p2 = hand_stats(2, &history, &result).unwrap();
+        assert_eq!((p2.vpip, p2.pfr), (0, 0));
+        let p3 = hand_stats(3, &history, &result).unwrap();
+        assert_eq!((p3.vpip, p3.pfr, p3.bets_raises, p3.calls), (1, 0, 1, 1));
+        assert_eq!(p3.showdowns, 0);
+    }
+
+    #[test]
+    fn test_record_splits_own_and_opponents() {
+        let (history, result) = played_hand();
+        let mut db = StatsDb::in_memory().unwrap();
+        db.record_hand("aleo1me", &seats(), &history, &result)
+            .unwrap();
+        db.record_hand("aleo1me", &seats(), &history, &result)
+            .unwrap();
+        db.record_tournament("aleo1me", &seats(), 3, 1_000_000)
+            .unwrap();
+
+        let account = db.account("aleo1me").unwrap();
+        assert_eq!(account.own.hands, 2);
+        assert_eq!(account.own.pfr_pct(), 100);
+        assert_eq!(account.own.net_credits(), "-1");
+        let carol = db.opponent("aleo1me", "aleo1carol").unwrap();
+        assert_eq!(carol.aggression(), 1.0);
+        assert_eq!(carol.tournaments_won, 1);
+        assert_eq!(carol.net_credits(), "+2");
+        assert_eq!(carol.tendencies(), "VPIP 100 PFR 0 AF 1.0 · 2h");
+        assert!(db.opponent("aleo1bob", "aleo1me").is_none());
+    }
+
+    #[test]
+    fn test_file_roundtrip() {
+        let path = std::env::temp_dir().join(format!("poker-stats-{}.redb", std::process::id()));
+        let (history, result) = played_hand();
+        let mut db = StatsDb::open(&path).unwrap();
+        db.record_hand("aleo1me", &seats(), &history, &result)
+            .unwrap();
+        db.record_tournament("aleo1me", &seats(), 1, 1_000_000)
+            .unwrap();
+        let accounts = db.accounts.clone();
+        drop(db);
+
+        let reopened = StatsDb::open(&path).unwrap();
+        assert_eq!(reopened.accounts, accounts);
+        assert_eq!(reopened.account("aleo1me").unwrap().own.hands, 1);
+        assert_eq!(
+            reopened
+                .opponent("aleo1me", "aleo1bob")
+                .unwrap()
+                .tournaments,
+            1
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,54 @@
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::path::Path;
+
+/// Reads the JSON file at `path`, or the default value if there is none yet.
+pub fn load<T: DeserializeOwned + Default>(path: &Path) -> anyhow::Result<T> {
+    if !path.exists() {
+        return Ok(T::default());
+    }
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?;
+    serde_json::from_str(&contents)
+        .map_err(|e| anyhow::anyhow!("Failed to parse {}: {}", path.display(), e))
+}
+
+/// Writes `value` to a temporary file first and renames it over `path`, so a
+/// crash never leaves a truncated file behind.
+pub fn save<T: Serialize>(path: &Path, value: &T) -> anyhow::Result<()> {
+    let contents = serde_json::to_string_pretty(value)?;
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, contents)
+        .map_err(|e| anyhow::anyhow!("Failed to write {}: {}", tmp.display(), e))?;
+    std::fs::rename(&tmp, path)
+        .map_err(|e| anyhow::anyhow!("Failed to write {}: {}", path.display(), e))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_missing_file_is_default() {
+        let path = std::env::temp_dir().join(format!("poker-none-{}.json", std::process::id()));
+        let value: BTreeMap<String, u32> = load(&path).unwrap();
+        assert!(value.is_empty());
+    }
+
+    #[test]
+    fn test_save_replaces_file() {
+        let path = std::env::temp_dir().join(format!("poker-store-{}.json", std::process::id()));
+        save(&path, &BTreeMap::from([("a", 1)])).unwrap();
+        save(&path, &BTreeMap::from([("b", 2)])).unwrap();
+
+        let value: BTreeMap<String, u32> = load(&path).unwrap();
+        assert_eq!(value, BTreeMap::from([("b".to_string(), 2)]));
+        assert!(!path.with_extension("json.tmp").exists());
+
+        std::fs::write(&path, "{").unwrap();
+        assert!(load::<BTreeMap<String, u32>>(&path).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
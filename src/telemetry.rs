@@ -0,0 +1,248 @@
+use crate::store;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+pub const TRANSITION_TIMES_FILE: &str = "transition_times.json";
+
+/// Weight of the newest sample in `Timing::recent_ms`.
+const RECENT_WEIGHT: f64 = 0.3;
+
+/// Program functions that generate a proof.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Transition {
+    CreateGame,
+    JoinGame,
+    DecryptHands,
+    Bet,
+    Fold,
+    DecryptFlop,
+    DecryptTurnRiver,
+    Showdown,
+    CompareHands,
+    NewHand,
+    ShuffleDeck,
+    ClaimPrize,
+}
+
+impl Transition {
+    pub fn all() -> [Self; 12] {
+        [
+            Self::CreateGame,
+            Self::JoinGame,
+            Self::DecryptHands,
+            Self::Bet,
+            Self::Fold,
+            Self::DecryptFlop,
+            Self::DecryptTurnRiver,
+            Self::Showdown,
+            Self::CompareHands,
+            Self::NewHand,
+            Self::ShuffleDeck,
+            Self::ClaimPrize,
+        ]
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::CreateGame => "create_game",
+            Self::JoinGame => "join_game",
+            Self::DecryptHands => "decrypt_hands",
+            Self::Bet => "bet",
+            Self::Fold => "fold",
+            Self::DecryptFlop => "decrypt_flop",
+            Self::DecryptTurnRiver => "decrypt_turn_river",
+            Self::Showdown => "showdown",
+            Self::CompareHands => "compare_hands",
+            Self::NewHand => "new_hand",
+            Self::ShuffleDeck => "shuffle_deck",
+            Self::ClaimPrize => "claim_prize",
+        }
+    }
+
+    /// Whether the call returns new keys, which are lost if the call fails
+    /// after its transaction was sent.
+    pub fn returns_record(self) -> bool {
+        !matches!(
+            self,
+            Self::Bet | Self::Fold | Self::CompareHands | Self::ClaimPrize
+        )
+    }
+}
+
+/// One call to a transition. The bindings prove, broadcast and wait for the
+/// transaction in one call and report no split, so on a network this is
+/// proving and confirmation together. Only `LocalVM` samples are proving
+/// time alone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransitionSample {
+    pub transition: Transition,
+    pub elapsed: Duration,
+}
+
+/// Runs `call` and records how long it took if it succeeded.
+pub fn timed<T>(
+    samples: &mut Vec<TransitionSample>,
+    transition: Transition,
+    call: impl FnOnce() -> anyhow::Result<T>,
+) -> anyhow::Result<T> {
+    let started = Instant::now();
+    let result = call()?;
+    samples.push(TransitionSample {
+        transition,
+        elapsed: started.elapsed(),
+    });
+    Ok(result)
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Timing {
+    pub count: u32,
+    pub total_ms: u64,
+    pub min_ms: u64,
+    pub max_ms: u64,
+    /// Moving average that follows a machine getting faster or slower.
+    pub recent_ms: u64,
+}
+
+impl Timing {
+    pub fn record(&mut self, elapsed: Duration) {
+        let ms = elapsed.as_millis() as u64;
+        self.recent_ms = if self.count == 0 {
+            ms
+        } else {
+            (RECENT_WEIGHT * ms as f64 + (1.0 - RECENT_WEIGHT) * self.recent_ms as f64).round()
+                as u64
+        };
+        self.min_ms = if self.count == 0 {
+            ms
+        } else {
+            self.min_ms.min(ms)
+        };
+        self.max_ms = self.max_ms.max(ms);
+        self.total_ms += ms;
+        self.count += 1;
+    }
+
+    pub fn mean_ms(&self) -> u64 {
+        self.total_ms / self.count.max(1) as u64
+    }
+}
+
+/// Progress towards an estimated duration, held below 100% until the
+/// transition actually finishes.
+pub fn progress(elapsed: Duration, estimate: Duration) -> (u8, Duration) {
+    if estimate.is_zero() {
+        return (99, Duration::ZERO);
+    }
+    let ratio = elapsed.as_secs_f64() / estimate.as_secs_f64();
+    let percent = (ratio * 100.0).min(99.0) as u8;
+    (percent, estimate.saturating_sub(elapsed))
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let ms = duration.as_millis() as u64;
+    match ms {
+        0..1_000 => format!("{}ms", ms),
+        1_000..60_000 => format!("{:.1}s", ms as f64 / 1000.0),
+        _ => format!("{}m{:02}s", ms / 60_000, ms / 1000 % 60),
+    }
+}
+
+/// Call timings per network and transition, stored as one JSON file. See
+/// `TransitionSample` for what a network timing covers.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TransitionTimes {
+    #[serde(default)]
+    pub networks: BTreeMap<String, BTreeMap<Transition, Timing>>,
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+impl TransitionTimes {
+    pub fn default_path() -> PathBuf {
+        std::env::var("POKER_TRANSITION_TIMES")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| PathBuf::from(TRANSITION_TIMES_FILE))
+    }
+
+    pub fn open_default() -> anyhow::Result<Self> {
+        Self::open(&Self::default_path())
+    }
+
+    pub fn open(path: &Path) -> anyhow::Result<Self> {
+        let mut times: Self = store::load(path)?;
+        times.path = path.to_path_buf();
+        Ok(times)
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        store::save(&self.path, self)
+    }
+
+    pub fn record(&mut self, network: &str, sample: TransitionSample) {
+        self.networks
+            .entry(network.to_string())
+            .or_default()
+            .entry(sample.transition)
+            .or_default()
+            .record(sample.elapsed);
+    }
+
+    pub fn timing(&self, network: &str, transition: Transition) -> Option<&Timing> {
+        self.networks.get(network)?.get(&transition)
+    }
+
+    /// How long the next `transition` on `network` is expected to take.
+    pub fn estimate(&self, network: &str, transition: Transition) -> Option<Duration> {
+        self.timing(network, transition)
+            .map(|timing| Duration::from_millis(timing.recent_ms))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timing_and_progress() {
+        let mut times = TransitionTimes::default();
+        for secs in [10, 20, 30] {
+            times.record(
+                "Testnet",
+                TransitionSample {
+                    transition: Transition::Showdown,
+                    elapsed: Duration::from_secs(secs),
+                },
+            );
+        }
+        let timing = times.timing("Testnet", Transition::Showdown).unwrap();
+        assert_eq!(
+            (timing.count, timing.min_ms, timing.max_ms, timing.mean_ms()),
+            (3, 10_000, 30_000, 20_000)
+        );
+        // 10s, then 0.3 * 20 + 0.7 * 10, then 0.3 * 30 + 0.7 * 13.
+        assert_eq!(timing.recent_ms, 18_100);
+        assert_eq!(times.estimate("Local", Transition::Showdown), None);
+
+        let estimate = times.estimate("Testnet", Transition::Showdown).unwrap();
+        assert_eq!(
+            progress(Duration::from_secs(9), estimate),
+            (49, Duration::from_millis(9_100))
+        );
+        assert_eq!(
+            progress(Duration::from_secs(40), estimate),
+            (99, Duration::ZERO)
+        );
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_millis(850)), "850ms");
+        assert_eq!(format_duration(Duration::from_millis(12_340)), "12.3s");
+        assert_eq!(format_duration(Duration::from_secs(125)), "2m05s");
+    }
+}
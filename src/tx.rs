@@ -0,0 +1,688 @@
+//! Following a submitted transaction until it is on chain, and telling a
+//! failure that left the table untouched from one that may still land.
+
+use crate::poll::get_text;
+use crate::store;
+use crate::telemetry::Transition;
+use serde::{Deserialize, Serialize};
+use snarkvm::prelude::{Network, Transaction};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+pub const TX_HISTORY_FILE: &str = "transactions.json";
+
+/// Records kept per account and network; older ones are dropped.
+const MAX_RECORDS: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TxStatus {
+    Proving,
+    /// Sent with a known id, not yet seen in a block.
+    Broadcast,
+    /// The call failed, but the transaction may still be in a block.
+    Pending,
+    Accepted,
+    /// In a block, but its finalize failed and nothing changed.
+    Rejected,
+    /// The call failed and the table did not move, so it can be proven
+    /// again.
+    Failed,
+}
+
+impl TxStatus {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Proving => "proving",
+            Self::Broadcast => "broadcast",
+            Self::Pending => "pending",
+            Self::Accepted => "accepted",
+            Self::Rejected => "rejected",
+            Self::Failed => "failed",
+        }
+    }
+
+    /// Whether proving the same action again cannot apply it twice.
+    pub fn can_retry(self) -> bool {
+        matches!(self, Self::Rejected | Self::Failed)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxRecord {
+    pub transition: Transition,
+    pub game_id: u32,
+    /// The public inputs worth telling apart, e.g. `amount 20`.
+    pub inputs: String,
+    pub id: Option<String>,
+    /// In microcredits.
+    pub fee: Option<u64>,
+    pub status: TxStatus,
+    /// The block the transaction is in, looked up by its id. Unknown for a
+    /// call that failed but landed anyway, since it returned no id.
+    pub block_height: Option<u32>,
+    pub error: Option<String>,
+}
+
+impl TxRecord {
+    pub fn new(transition: Transition, game_id: u32, inputs: String) -> Self {
+        Self {
+            transition,
+            game_id,
+            inputs,
+            id: None,
+            fee: None,
+            status: TxStatus::Proving,
+            block_height: None,
+            error: None,
+        }
+    }
+
+    /// One line for the log once the record has settled.
+    pub fn describe(&self) -> String {
+        let name = self.transition.name();
+        let id = self.id.as_deref().unwrap_or("without an id");
+        match self.status {
+            TxStatus::Proving | TxStatus::Broadcast => {
+                format!("{} {} is {}", name, id, self.status.name())
+            }
+            TxStatus::Accepted => format!("{} {} was accepted", name, id),
+            TxStatus::Rejected => format!(
+                "{} {} was rejected on chain; nothing changed, so it is safe to prove it again",
+                name, id
+            ),
+            TxStatus::Pending => format!(
+                "{} may still land; the table could not be read, so wait before proving it again",
+                name
+            ),
+            TxStatus::Failed => format!(
+                "{} did not reach the chain and the table has not moved; it is safe to prove it again",
+                name
+            ),
+        }
+    }
+}
+
+/// Where `export` writes a game's transactions by default.
+pub fn export_path(game_id: u32) -> PathBuf {
+    let dir = std::env::var("POKER_TX_EXPORT_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_default();
+    dir.join(format!("transactions-{}.json", game_id))
+}
+
+/// Writes the records of `game_id` as a JSON array and returns how many
+/// there were.
+pub fn export(records: &[TxRecord], game_id: u32, path: &Path) -> anyhow::Result<usize> {
+    let records: Vec<_> = records
+        .iter()
+        .filter(|record| record.game_id == game_id)
+        .collect();
+    let json = serde_json::to_string_pretty(&records)?;
+    std::fs::write(path, json)
+        .map_err(|e| anyhow::anyhow!("Failed to write {}: {}", path.display(), e))?;
+    Ok(records.len())
+}
+
+/// The transaction a binding call returned, alone or with a new record.
+pub trait Submitted<N: Network> {
+    fn transaction(&self) -> &Transaction<N>;
+}
+
+impl<N: Network> Submitted<N> for Transaction<N> {
+    fn transaction(&self) -> &Transaction<N> {
+        self
+    }
+}
+
+impl<N: Network, K> Submitted<N> for (K, Transaction<N>) {
+    fn transaction(&self) -> &Transaction<N> {
+        &self.1
+    }
+}
+
+/// Errors worth retrying: a transport failure, or an HTTP status that says
+/// the node is busy or behind a failing proxy. The request may never have
+/// reached the node.
+pub fn is_transient(error: &str) -> bool {
+    const TRANSPORT: [&str; 9] = [
+        "timed out",
+        "timeout",
+        "connection refused",
+        "connection reset",
+        "connection aborted",
+        "connection closed",
+        "broken pipe",
+        "dns",
+        "temporarily unavailable",
+    ];
+    const STATUS: [&str; 4] = ["429", "502", "503", "504"];
+    let error = error.to_lowercase();
+    if TRANSPORT.iter().any(|pattern| error.contains(pattern)) {
+        return true;
+    }
+    // A bare number only counts next to an HTTP status, not as an amount.
+    (error.contains("http") || error.contains("status"))
+        && error
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .any(|word| STATUS.contains(&word))
+}
+
+/// This account's place in a game: whether it is seated, and the game's
+/// state and hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub seated: bool,
+    pub state: u8,
+    pub hands_played: u8,
+}
+
+/// Whether the table went from `before` to `now` in a way only our own
+/// `transition` causes: we took a seat, or a game we sit at moved on while
+/// it waited for us. A game that could not be read before only counts for
+/// `create_game`, the one call that makes it exist.
+pub fn moved_by_us(
+    transition: Transition,
+    before: Option<Position>,
+    now: Option<Position>,
+) -> bool {
+    let Some(now) = now.filter(|now| now.seated) else {
+        return false;
+    };
+    match before {
+        Some(before) => !before.seated || before != now,
+        None => transition == Transition::CreateGame,
+    }
+}
+
+/// How many times `submit` sends a call that failed on the way to the node.
+const SUBMIT_ATTEMPTS: u32 = 3;
+
+/// What `submit` asks about the game a transaction acts on.
+pub trait Chain {
+    /// `None` while the game cannot be read, or does not exist yet.
+    fn position(&self) -> Option<Position>;
+    /// `None` while the transaction is not in a block.
+    fn confirmed(&self, id: &str) -> Option<TxStatus>;
+    /// The height of the block that holds transaction `id`.
+    fn block_height(&self, id: &str) -> Option<u32>;
+    /// How long to wait for a transaction or the table to move.
+    fn backoff(&self) -> Backoff {
+        Backoff::CONFIRM
+    }
+}
+
+/// A transaction the bindings returned, with its id and fee.
+pub struct Sent<T> {
+    pub value: T,
+    pub id: String,
+    pub fee: Option<u64>,
+}
+
+/// Sends `call` and records its transaction in `records`, looking up once
+/// whether the block accepted it. When the call fails the table is read
+/// again, waiting a while for transient errors, so a transaction that landed anyway is never sent
+/// twice. A transient failure that left the table untouched is sent again,
+/// up to `SUBMIT_ATTEMPTS` times in all, each attempt with its own record.
+pub fn submit<T>(
+    chain: &impl Chain,
+    records: &mut Vec<TxRecord>,
+    record: TxRecord,
+    mut call: impl FnMut() -> anyhow::Result<Sent<T>>,
+) -> anyhow::Result<T> {
+    let before = chain.position();
+    let backoff = chain.backoff();
+    let mut attempt = 0;
+    loop {
+        records.push(record.clone());
+        let result = call();
+        let record = records.last_mut().unwrap();
+        let e = match result {
+            Ok(sent) => {
+                record.id = Some(sent.id.clone());
+                record.fee = sent.fee;
+                // The call only returns once the transaction is in a block,
+                // so one lookup tells whether it was accepted.
+                record.status = chain.confirmed(&sent.id).unwrap_or(TxStatus::Broadcast);
+                if record.status != TxStatus::Broadcast {
+                    record.block_height = chain.block_height(&sent.id);
+                }
+                if record.status == TxStatus::Rejected {
+                    anyhow::bail!("{}", record.describe());
+                }
+                return Ok(sent.value);
+            }
+            Err(e) => e,
+        };
+
+        let error = e.to_string();
+        let transient = is_transient(&error);
+        let transition = record.transition;
+        let moved = || moved_by_us(transition, before, chain.position()).then_some(());
+        let landed = if transient {
+            backoff.poll(moved)
+        } else {
+            moved()
+        };
+        record.status = if landed.is_some() {
+            TxStatus::Accepted
+        } else if before.is_some() && chain.position().is_none() {
+            TxStatus::Pending
+        } else {
+            TxStatus::Failed
+        };
+        record.error = Some(error);
+
+        attempt += 1;
+        if record.status != TxStatus::Failed || !transient || attempt == SUBMIT_ATTEMPTS {
+            return Err(e);
+        }
+        log::warn!(
+            "{} failed, sending it again: {}",
+            record.transition.name(),
+            e
+        );
+        std::thread::sleep(backoff.delay(attempt));
+    }
+}
+
+/// Exponential backoff: `initial`, doubling up to `max`, for `attempts`
+/// tries in all.
+#[derive(Debug, Clone, Copy)]
+pub struct Backoff {
+    pub initial: Duration,
+    pub max: Duration,
+    pub attempts: u32,
+}
+
+impl Backoff {
+    /// Covers a few blocks, which is how long a broadcast transaction
+    /// normally takes to be included.
+    pub const CONFIRM: Self = Self {
+        initial: Duration::from_secs(1),
+        max: Duration::from_secs(8),
+        attempts: 6,
+    };
+
+    pub fn delay(&self, attempt: u32) -> Duration {
+        self.initial
+            .saturating_mul(1 << attempt.min(16))
+            .min(self.max)
+    }
+
+    /// Calls `check` until it returns `Some` or the attempts run out,
+    /// sleeping between tries.
+    pub fn poll<T>(&self, mut check: impl FnMut() -> Option<T>) -> Option<T> {
+        for attempt in 0..self.attempts {
+            if let Some(value) = check() {
+                return Some(value);
+            }
+            if attempt + 1 < self.attempts {
+                std::thread::sleep(self.delay(attempt));
+            }
+        }
+        None
+    }
+}
+
+/// Looks up `{api}/transaction/confirmed/{id}`. `None` while it is not in
+/// a block or the node cannot be reached.
+pub fn confirmed_status(api: &str, id: &str, timeout: Duration) -> Option<TxStatus> {
+    let url = format!("{}/transaction/confirmed/{}", api, id);
+    parse_confirmed(&get_text(&url, timeout).ok()?)
+}
+
+/// Looks up `{api}/find/blockHash/{id}`, then `{api}/height/{hash}`.
+pub fn inclusion_height(api: &str, id: &str, timeout: Duration) -> anyhow::Result<u32> {
+    let body = get_text(&format!("{}/find/blockHash/{}", api, id), timeout)?;
+    let hash: Option<String> = serde_json::from_str(&body)
+        .map_err(|e| anyhow::anyhow!("Unexpected block hash '{}': {}", body.trim(), e))?;
+    let hash = hash.ok_or_else(|| anyhow::anyhow!("Transaction {} is not in a block", id))?;
+    let body = get_text(&format!("{}/height/{}", api, hash), timeout)?;
+    body.trim()
+        .parse()
+        .map_err(|e| anyhow::anyhow!("Unexpected block height '{}': {}", body.trim(), e))
+}
+
+/// Every account's transactions per network, stored as one JSON file so the
+/// history outlives the session.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TxHistory {
+    #[serde(default)]
+    pub networks: BTreeMap<String, BTreeMap<String, Vec<TxRecord>>>,
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+impl TxHistory {
+    pub fn default_path() -> PathBuf {
+        std::env::var("POKER_TRANSACTIONS")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| PathBuf::from(TX_HISTORY_FILE))
+    }
+
+    pub fn open_default() -> anyhow::Result<Self> {
+        Self::open(&Self::default_path())
+    }
+
+    pub fn open(path: &Path) -> anyhow::Result<Self> {
+        let mut history: Self = store::load(path)?;
+        history.path = path.to_path_buf();
+        Ok(history)
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        store::save(&self.path, self)
+    }
+
+    pub fn records(&self, network: &str, address: &str) -> &[TxRecord] {
+        self.networks
+            .get(network)
+            .and_then(|accounts| accounts.get(address))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Replaces the records of `address` on `network`, keeping the newest
+    /// `MAX_RECORDS`.
+    pub fn set_records(&mut self, network: &str, address: &str, records: &[TxRecord]) {
+        let kept = &records[records.len().saturating_sub(MAX_RECORDS)..];
+        self.networks
+            .entry(network.to_string())
+            .or_default()
+            .insert(address.to_string(), kept.to_vec());
+    }
+}
+
+fn parse_confirmed(body: &str) -> Option<TxStatus> {
+    let confirmed: serde_json::Value = serde_json::from_str(body).ok()?;
+    match confirmed.get("status")?.as_str()? {
+        "accepted" => Some(TxStatus::Accepted),
+        "rejected" => Some(TxStatus::Rejected),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_transient() {
+        assert!(is_transient(
+            "Failed to broadcast transaction: Connection refused"
+        ));
+        assert!(is_transient("HTTP 503 Service Unavailable"));
+        assert!(is_transient("http status: 429"));
+        assert!(is_transient("io: operation timed out"));
+        assert!(!is_transient("Failed to evaluate instruction: assert.eq"));
+        assert!(!is_transient(
+            "Failed to broadcast transaction: insufficient balance for fee"
+        ));
+        assert!(!is_transient("Invalid connection string in endpoint"));
+        assert!(!is_transient("bet of 503 exceeds the stack"));
+    }
+
+    const SEATED: Position = Position {
+        seated: true,
+        state: 5,
+        hands_played: 0,
+    };
+
+    #[test]
+    fn test_moved_by_us() {
+        let moved = Position { state: 6, ..SEATED };
+        let bet = Transition::Bet;
+        assert!(moved_by_us(bet, Some(SEATED), Some(moved)));
+        assert!(!moved_by_us(bet, Some(SEATED), Some(SEATED)));
+        assert!(!moved_by_us(bet, Some(SEATED), None));
+        // An unreadable table before is not a move, except for create_game.
+        assert!(!moved_by_us(bet, None, Some(moved)));
+        assert!(moved_by_us(Transition::CreateGame, None, Some(SEATED)));
+        // Someone else created the game we meant to.
+        let stranger = Position {
+            seated: false,
+            ..SEATED
+        };
+        assert!(!moved_by_us(Transition::CreateGame, None, Some(stranger)));
+        assert!(moved_by_us(
+            Transition::JoinGame,
+            Some(stranger),
+            Some(SEATED)
+        ));
+    }
+
+    /// A chain whose table reads come from a script, one per call, repeating
+    /// the last.
+    struct Script {
+        positions: std::cell::RefCell<Vec<Option<Position>>>,
+        status: Option<TxStatus>,
+        lookups: std::cell::Cell<usize>,
+    }
+
+    impl Script {
+        fn new(positions: &[Option<Position>], status: Option<TxStatus>) -> Self {
+            Self {
+                positions: std::cell::RefCell::new(positions.iter().rev().copied().collect()),
+                status,
+                lookups: std::cell::Cell::new(0),
+            }
+        }
+    }
+
+    impl Chain for Script {
+        fn position(&self) -> Option<Position> {
+            let mut positions = self.positions.borrow_mut();
+            if positions.len() > 1 {
+                positions.pop().unwrap()
+            } else {
+                positions[0]
+            }
+        }
+
+        fn confirmed(&self, _id: &str) -> Option<TxStatus> {
+            self.lookups.set(self.lookups.get() + 1);
+            self.status
+        }
+
+        fn block_height(&self, _id: &str) -> Option<u32> {
+            Some(90)
+        }
+
+        fn backoff(&self) -> Backoff {
+            Backoff {
+                initial: Duration::from_millis(1),
+                max: Duration::from_millis(1),
+                attempts: 2,
+            }
+        }
+    }
+
+    fn sent(id: &str) -> anyhow::Result<Sent<u8>> {
+        Ok(Sent {
+            value: 7,
+            id: id.to_string(),
+            fee: Some(1_000),
+        })
+    }
+
+    fn bet() -> TxRecord {
+        TxRecord::new(Transition::Bet, 4, "amount 20".to_string())
+    }
+
+    #[test]
+    fn test_submit_confirms() {
+        let mut records = Vec::new();
+        let chain = Script::new(&[Some(SEATED)], Some(TxStatus::Accepted));
+        assert_eq!(
+            submit(&chain, &mut records, bet(), || sent("at1")).unwrap(),
+            7
+        );
+        assert_eq!(records.len(), 1);
+        assert_eq!(
+            (records[0].status, records[0].block_height, records[0].fee),
+            (TxStatus::Accepted, Some(90), Some(1_000))
+        );
+
+        let chain = Script::new(&[Some(SEATED)], Some(TxStatus::Rejected));
+        assert!(submit(&chain, &mut records, bet(), || sent("at2")).is_err());
+        assert_eq!(records[1].status, TxStatus::Rejected);
+
+        let chain = Script::new(&[Some(SEATED)], None);
+        submit(&chain, &mut records, bet(), || sent("at3")).unwrap();
+        assert_eq!(
+            (records[2].status, records[2].block_height),
+            (TxStatus::Broadcast, None)
+        );
+        // A successful call is looked up once, not polled.
+        assert_eq!(chain.lookups.get(), 1);
+    }
+
+    #[test]
+    fn test_submit_resends_transient_failures() {
+        let mut records = Vec::new();
+        let chain = Script::new(&[Some(SEATED)], Some(TxStatus::Accepted));
+        let mut calls = 0;
+        let value = submit(&chain, &mut records, bet(), || {
+            calls += 1;
+            match calls {
+                1 => Err(anyhow::anyhow!("Connection refused")),
+                _ => sent("at1"),
+            }
+        });
+        assert_eq!(value.unwrap(), 7);
+        assert_eq!(
+            records.iter().map(|r| r.status).collect::<Vec<_>>(),
+            [TxStatus::Failed, TxStatus::Accepted]
+        );
+
+        // Gives up after SUBMIT_ATTEMPTS, and never resends other errors.
+        records.clear();
+        let result = submit(&chain, &mut records, bet(), || {
+            Err::<Sent<u8>, _>(anyhow::anyhow!("HTTP 503"))
+        });
+        assert!(result.is_err());
+        assert_eq!(records.len(), SUBMIT_ATTEMPTS as usize);
+        records.clear();
+        let result = submit(&chain, &mut records, bet(), || {
+            Err::<Sent<u8>, _>(anyhow::anyhow!("assert.eq failed"))
+        });
+        assert!(result.is_err());
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].status, TxStatus::Failed);
+    }
+
+    #[test]
+    fn test_submit_failure_that_landed() {
+        // The table moves on after the error, so the bet is not sent again.
+        let mut records = Vec::new();
+        let moved = Position { state: 6, ..SEATED };
+        let chain = Script::new(&[Some(SEATED), Some(SEATED), Some(moved)], None);
+        let mut calls = 0;
+        let result = submit(&chain, &mut records, bet(), || {
+            calls += 1;
+            Err::<Sent<u8>, _>(anyhow::anyhow!("request timed out"))
+        });
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+        assert_eq!(
+            (records[0].status, records[0].block_height),
+            (TxStatus::Accepted, None)
+        );
+
+        // The table cannot be read any more: the bet may still land.
+        records.clear();
+        let chain = Script::new(&[Some(SEATED), None], None);
+        let result = submit(&chain, &mut records, bet(), || {
+            Err::<Sent<u8>, _>(anyhow::anyhow!("request timed out"))
+        });
+        assert!(result.is_err());
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].status, TxStatus::Pending);
+    }
+
+    #[test]
+    fn test_backoff() {
+        let backoff = Backoff {
+            initial: Duration::from_millis(1),
+            max: Duration::from_millis(4),
+            attempts: 5,
+        };
+        assert_eq!(
+            (0..5)
+                .map(|n| backoff.delay(n).as_millis())
+                .collect::<Vec<_>>(),
+            [1, 2, 4, 4, 4]
+        );
+        let mut tries = 0;
+        assert_eq!(
+            backoff.poll(|| {
+                tries += 1;
+                (tries == 3).then_some(tries)
+            }),
+            Some(3)
+        );
+        assert_eq!(backoff.poll(|| None::<()>), None);
+    }
+
+    #[test]
+    fn test_export() {
+        let mut bet = TxRecord::new(Transition::Bet, 4, "amount 20".to_string());
+        bet.id = Some("at1bet".to_string());
+        bet.fee = Some(1_500);
+        bet.status = TxStatus::Accepted;
+        bet.block_height = Some(812);
+        let other = TxRecord::new(Transition::CreateGame, 5, "buy_in 100".to_string());
+
+        let path = std::env::temp_dir().join(format!("poker-tx-{}.json", std::process::id()));
+        assert_eq!(export(&[bet, other], 4, &path).unwrap(), 1);
+        let exported: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            exported,
+            serde_json::json!([{
+                "transition": "bet",
+                "game_id": 4,
+                "inputs": "amount 20",
+                "id": "at1bet",
+                "fee": 1500,
+                "status": "accepted",
+                "block_height": 812,
+                "error": null,
+            }])
+        );
+    }
+
+    #[test]
+    fn test_history_round_trip() {
+        let path = std::env::temp_dir().join(format!("poker-txs-{}.json", std::process::id()));
+        let mut history = TxHistory::open(&path).unwrap();
+        assert!(history.records("testnet", "aleo1me").is_empty());
+
+        let mut bet = TxRecord::new(Transition::Bet, 4, "amount 20".to_string());
+        bet.status = TxStatus::Accepted;
+        bet.block_height = Some(812);
+        let records = vec![bet; MAX_RECORDS + 1];
+        history.set_records("testnet", "aleo1me", &records);
+        history.save().unwrap();
+
+        let reopened = TxHistory::open(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(reopened.records("testnet", "aleo1me"), &records[1..]);
+        assert!(reopened.records("local", "aleo1me").is_empty());
+    }
+
+    #[test]
+    fn test_parse_confirmed() {
+        assert_eq!(
+            parse_confirmed(r#"{"status":"accepted","type":"execute","index":0}"#),
+            Some(TxStatus::Accepted)
+        );
+        assert_eq!(
+            parse_confirmed(r#"{"status":"rejected","type":"fee"}"#),
+            Some(TxStatus::Rejected)
+        );
+        assert_eq!(parse_confirmed("null"), None);
+    }
+}
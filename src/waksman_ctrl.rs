@@ -1,21 +1,41 @@
+use rand::Rng;
 use rand::seq::SliceRandom;
 use snarkvm::prelude::{Group, Network};
 
 pub fn shuffle_deck<N: Network>(deck: [Group<N>; 52]) -> ([Group<N>; 52], [bool; 249]) {
-    let mut rng = rand::thread_rng();
+    permute_deck(deck, &random_permutation(&mut rand::thread_rng()))
+}
+
+#[cfg(any(test, feature = "test-utils"))]
+pub fn shuffle_deck_with<N: Network, R: Rng + ?Sized>(
+    deck: [Group<N>; 52],
+    rng: &mut R,
+) -> ([Group<N>; 52], [bool; 249]) {
+    permute_deck(deck, &random_permutation(rng))
+}
+
+/// Moves the card at position `src` to `perm[src]`.
+pub fn permute_deck<N: Network>(
+    deck: [Group<N>; 52],
+    perm: &[usize; 52],
+) -> ([Group<N>; 52], [bool; 249]) {
+    let control = permutation_to_waksman_bits(perm);
+    let mut shuffled = deck;
+    for (src, &dst) in perm.iter().enumerate() {
+        shuffled[dst] = deck[src];
+    }
+    (shuffled, control)
+}
 
+pub(crate) fn random_permutation<R: Rng + ?Sized>(rng: &mut R) -> [usize; 52] {
     let mut indices: Vec<usize> = (0..52).collect();
-    indices.shuffle(&mut rng);
+    indices.shuffle(rng);
 
     let mut perm = [0usize; 52];
     for (dst, &src) in indices.iter().enumerate() {
         perm[src] = dst;
     }
-
-    let control = permutation_to_waksman_bits(&perm);
-    let shuffled = std::array::from_fn(|i| deck[indices[i]]);
-
-    (shuffled, control)
+    perm
 }
 
 pub fn permutation_to_waksman_bits(perm: &[usize; 52]) -> [bool; 249] {
@@ -163,7 +183,6 @@ fn is_valid_permutation(perm: &[usize]) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use rand::seq::SliceRandom;
 
     fn simulate(deck: &[usize], ctrl: &[bool], offset: &mut usize) -> Vec<usize> {
         let n = deck.len();
@@ -271,15 +290,7 @@ mod tests {
     fn test_random_permutations() {
         let mut rng = rand::thread_rng();
         for _ in 0..200 {
-            let mut indices: Vec<usize> = (0..52).collect();
-            indices.shuffle(&mut rng);
-
-            let mut perm = [0usize; 52];
-            for (dst, &src) in indices.iter().enumerate() {
-                perm[src] = dst;
-            }
-
-            check_perm(&perm);
+            check_perm(&random_permutation(&mut rng));
         }
     }
 
@@ -1,5 +1,8 @@
 use leo_bindings::leo_bindings_sdk::snapshot_store;
 use mental_poker_bindings::mental_poker::*;
+use poker::cards::PlayingCard;
+use poker::hand::evaluate_hand;
+use poker::pots::{active_players, payouts, side_pots};
 use snarkvm::prelude::*;
 use std::str::FromStr;
 
@@ -13,37 +16,8 @@ snapshot_store!(SETUP, |store| {
     store.save("deployed");
 });
 
-fn card(s: &str) -> u8 {
-    let s = s.trim().replace(" ", "").to_uppercase();
-    let suit_char = s.chars().next().expect("Empty card string");
-    let value_str = &s[1..];
-
-    let suit_offset = match suit_char {
-        'S' => 0,
-        'C' => 13,
-        'H' => 26,
-        'D' => 39,
-        _ => panic!("Invalid suit: {}", suit_char),
-    };
-
-    let value_index = match value_str {
-        "2" => 0,
-        "3" => 1,
-        "4" => 2,
-        "5" => 3,
-        "6" => 4,
-        "7" => 5,
-        "8" => 6,
-        "9" => 7,
-        "10" => 8,
-        "J" => 9,
-        "Q" => 10,
-        "K" => 11,
-        "A" => 12,
-        _ => panic!("Invalid value: {}", value_str),
-    };
-
-    suit_offset + value_index
+fn card(s: &str) -> PlayingCard {
+    s.parse().unwrap()
 }
 
 struct GameSetup {
@@ -55,12 +29,12 @@ struct GameSetup {
 }
 
 struct Cards {
-    p1_cards: [u8; 2],
-    p2_cards: [u8; 2],
-    p3_cards: [u8; 2],
-    flop: [u8; 3],
-    turn: u8,
-    river: u8,
+    p1_cards: [PlayingCard; 2],
+    p2_cards: [PlayingCard; 2],
+    p3_cards: [PlayingCard; 2],
+    flop: [PlayingCard; 3],
+    turn: PlayingCard,
+    river: PlayingCard,
 }
 
 struct Expectation {
@@ -106,12 +80,12 @@ fn run_test(setup: GameSetup, cards: Cards, expectation: Expectation) {
     poker.set_chips(setup.game_id, chips);
 
     let revealed = RevealedCards::new(
-        cards.p1_cards,
-        cards.p2_cards,
-        cards.p3_cards,
-        cards.flop,
-        cards.turn,
-        cards.river,
+        cards.p1_cards.map(u8::from),
+        cards.p2_cards.map(u8::from),
+        cards.p3_cards.map(u8::from),
+        cards.flop.map(u8::from),
+        cards.turn.into(),
+        cards.river.into(),
     );
     poker.set_revealed_cards(setup.game_id, revealed);
 
@@ -119,6 +93,40 @@ fn run_test(setup: GameSetup, cards: Cards, expectation: Expectation) {
 
     let final_chips = poker.get_chips(setup.game_id).unwrap();
 
+    // The client's port of evaluate_hand must split the pots the same way.
+    let board = [
+        cards.flop[0],
+        cards.flop[1],
+        cards.flop[2],
+        cards.turn,
+        cards.river,
+    ];
+    let values = [cards.p1_cards, cards.p2_cards, cards.p3_cards].map(|[first, second]| {
+        evaluate_hand(&[
+            first, second, board[0], board[1], board[2], board[3], board[4],
+        ])
+    });
+    let bets = [
+        setup.initial_bets.0,
+        setup.initial_bets.1,
+        setup.initial_bets.2,
+    ];
+    let active = active_players(setup.players_folded, setup.players_out);
+    let shares = payouts(&side_pots(bets, active), values);
+    assert_eq!(
+        [
+            setup.initial_chips.0 + shares[0],
+            setup.initial_chips.1 + shares[1],
+            setup.initial_chips.2 + shares[2],
+        ],
+        [
+            final_chips.player1,
+            final_chips.player2,
+            final_chips.player3
+        ],
+        "Client hand values disagree with compare_hands"
+    );
+
     if let Some(expected_chips) = expectation.winner_chips {
         assert_eq!(
             final_chips.player1,
@@ -167,12 +175,12 @@ fn test_straight_flush_vs_flush() {
             initial_bets: (100, 100, 100),
         },
         Cards {
-            p1_cards: [card("S9"), card("S6")],
-            p2_cards: [card("SQ"), card("SK")],
-            p3_cards: [card("SA"), card("S2")],
-            flop: [card("S8"), card("S7"), card("S5")],
-            turn: card("S3"),
-            river: card("H10"),
+            p1_cards: [card("9s"), card("6s")],
+            p2_cards: [card("Qs"), card("Ks")],
+            p3_cards: [card("As"), card("2s")],
+            flop: [card("8s"), card("7s"), card("5s")],
+            turn: card("3s"),
+            river: card("Th"),
         },
         Expectation {
             winner_chips: Some((300, 0, 0)),
@@ -191,12 +199,12 @@ fn test_four_of_a_kind_vs_full_house() {
             initial_bets: (100, 100, 100),
         },
         Cards {
-            p1_cards: [card("SA"), card("HA")],
-            p2_cards: [card("SK"), card("HK")],
-            p3_cards: [card("H2"), card("H3")],
-            flop: [card("DA"), card("CA"), card("SQ")],
-            turn: card("HQ"),
-            river: card("DQ"),
+            p1_cards: [card("As"), card("Ah")],
+            p2_cards: [card("Ks"), card("Kh")],
+            p3_cards: [card("2h"), card("3h")],
+            flop: [card("Ad"), card("Ac"), card("Qs")],
+            turn: card("Qh"),
+            river: card("Qd"),
         },
         Expectation {
             winner_chips: Some((300, 0, 0)),
@@ -215,12 +223,12 @@ fn test_flush_vs_straight() {
             initial_bets: (100, 100, 100),
         },
         Cards {
-            p1_cards: [card("SA"), card("SK")],
-            p2_cards: [card("H10"), card("D9")],
-            p3_cards: [card("H2"), card("H3")],
-            flop: [card("SQ"), card("S9"), card("S7")],
-            turn: card("C8"),
-            river: card("H6"),
+            p1_cards: [card("As"), card("Ks")],
+            p2_cards: [card("Th"), card("9d")],
+            p3_cards: [card("2h"), card("3h")],
+            flop: [card("Qs"), card("9s"), card("7s")],
+            turn: card("8c"),
+            river: card("6h"),
         },
         Expectation {
             winner_chips: Some((300, 0, 0)),
@@ -239,12 +247,12 @@ fn test_three_of_a_kind_vs_two_pair() {
             initial_bets: (100, 100, 100),
         },
         Cards {
-            p1_cards: [card("SK"), card("HK")],
-            p2_cards: [card("SQ"), card("HA")],
-            p3_cards: [card("H2"), card("H3")],
-            flop: [card("DK"), card("SA"), card("HQ")],
-            turn: card("S10"),
-            river: card("S9"),
+            p1_cards: [card("Ks"), card("Kh")],
+            p2_cards: [card("Qs"), card("Ah")],
+            p3_cards: [card("2h"), card("3h")],
+            flop: [card("Kd"), card("As"), card("Qh")],
+            turn: card("Ts"),
+            river: card("9s"),
         },
         Expectation {
             winner_chips: Some((300, 0, 0)),
@@ -263,12 +271,12 @@ fn test_pair_vs_high_card() {
             initial_bets: (100, 100, 100),
         },
         Cards {
-            p1_cards: [card("SK"), card("HK")],
-            p2_cards: [card("DA"), card("DQ")],
-            p3_cards: [card("H2"), card("H3")],
-            flop: [card("C10"), card("H9"), card("D7")],
-            turn: card("C5"),
-            river: card("C4"),
+            p1_cards: [card("Ks"), card("Kh")],
+            p2_cards: [card("Ad"), card("Qd")],
+            p3_cards: [card("2h"), card("3h")],
+            flop: [card("Tc"), card("9h"), card("7d")],
+            turn: card("5c"),
+            river: card("4c"),
         },
         Expectation {
             winner_chips: Some((300, 0, 0)),
@@ -287,12 +295,12 @@ fn test_same_pair_higher_kicker() {
             initial_bets: (100, 100, 100),
         },
         Cards {
-            p1_cards: [card("S10"), card("SA")],
-            p2_cards: [card("D10"), card("SK")],
-            p3_cards: [card("C10"), card("HK")],
-            flop: [card("H10"), card("S9"), card("D7")],
-            turn: card("S5"),
-            river: card("D4"),
+            p1_cards: [card("Ts"), card("As")],
+            p2_cards: [card("Td"), card("Ks")],
+            p3_cards: [card("Tc"), card("Kh")],
+            flop: [card("Th"), card("9s"), card("7d")],
+            turn: card("5s"),
+            river: card("4d"),
         },
         Expectation {
             winner_chips: Some((300, 0, 0)),
@@ -311,12 +319,12 @@ fn test_three_of_a_kind_kicker_comparison() {
             initial_bets: (100, 100, 0),
         },
         Cards {
-            p1_cards: [card("SK"), card("HQ")],
-            p2_cards: [card("DK"), card("HJ")],
-            p3_cards: [card("H2"), card("H3")],
-            flop: [card("CK"), card("SA"), card("D9")],
-            turn: card("C7"),
-            river: card("C4"),
+            p1_cards: [card("Ks"), card("Qh")],
+            p2_cards: [card("Kd"), card("Jh")],
+            p3_cards: [card("2h"), card("3h")],
+            flop: [card("Kc"), card("As"), card("9d")],
+            turn: card("7c"),
+            river: card("4c"),
         },
         Expectation {
             winner_chips: Some((200, 0, 100)),
@@ -335,12 +343,12 @@ fn test_split_pot_playing_board() {
             initial_bets: (100, 100, 100),
         },
         Cards {
-            p1_cards: [card("H2"), card("H3")],
-            p2_cards: [card("D4"), card("D5")],
-            p3_cards: [card("C2"), card("C3")],
-            flop: [card("SA"), card("SK"), card("SQ")],
-            turn: card("SJ"),
-            river: card("S10"),
+            p1_cards: [card("2h"), card("3h")],
+            p2_cards: [card("4d"), card("5d")],
+            p3_cards: [card("2c"), card("3c")],
+            flop: [card("As"), card("Ks"), card("Qs")],
+            turn: card("Js"),
+            river: card("Ts"),
         },
         Expectation {
             winner_chips: Some((100, 100, 100)),
@@ -359,12 +367,12 @@ fn test_full_house_higher_trips() {
             initial_bets: (100, 100, 100),
         },
         Cards {
-            p1_cards: [card("SA"), card("HA")],
-            p2_cards: [card("D3"), card("C3")],
-            p3_cards: [card("H2"), card("H3")],
-            flop: [card("SK"), card("HK"), card("DA")],
-            turn: card("S10"),
-            river: card("S9"),
+            p1_cards: [card("As"), card("Ah")],
+            p2_cards: [card("3d"), card("3c")],
+            p3_cards: [card("2h"), card("3h")],
+            flop: [card("Ks"), card("Kh"), card("Ad")],
+            turn: card("Ts"),
+            river: card("9s"),
         },
         Expectation {
             winner_chips: Some((300, 0, 0)),
@@ -383,12 +391,12 @@ fn test_straight_higher_top_card() {
             initial_bets: (100, 100, 100),
         },
         Cards {
-            p1_cards: [card("S9"), card("H8")],
-            p2_cards: [card("D4"), card("H4")],
-            p3_cards: [card("H2"), card("D3")],
-            flop: [card("D7"), card("C6"), card("S5")],
-            turn: card("C2"),
-            river: card("CA"),
+            p1_cards: [card("9s"), card("8h")],
+            p2_cards: [card("4d"), card("4h")],
+            p3_cards: [card("2h"), card("3d")],
+            flop: [card("7d"), card("6c"), card("5s")],
+            turn: card("2c"),
+            river: card("Ac"),
         },
         Expectation {
             winner_chips: Some((300, 0, 0)),
@@ -407,12 +415,12 @@ fn test_multiple_straights_board() {
             initial_bets: (100, 100, 100),
         },
         Cards {
-            p1_cards: [card("SJ"), card("HQ")],
-            p2_cards: [card("D5"), card("H4")],
-            p3_cards: [card("H2"), card("C2")],
-            flop: [card("D6"), card("C7"), card("S8")],
-            turn: card("H9"),
-            river: card("C10"),
+            p1_cards: [card("Js"), card("Qh")],
+            p2_cards: [card("5d"), card("4h")],
+            p3_cards: [card("2h"), card("2c")],
+            flop: [card("6d"), card("7c"), card("8s")],
+            turn: card("9h"),
+            river: card("Tc"),
         },
         Expectation {
             winner_chips: Some((300, 0, 0)),
@@ -431,12 +439,12 @@ fn test_full_house_higher_pair() {
             initial_bets: (100, 100, 100),
         },
         Cards {
-            p1_cards: [card("SK"), card("HK")],
-            p2_cards: [card("SQ"), card("HQ")],
-            p3_cards: [card("H2"), card("H3")],
-            flop: [card("SA"), card("HA"), card("DA")],
-            turn: card("C9"),
-            river: card("C8"),
+            p1_cards: [card("Ks"), card("Kh")],
+            p2_cards: [card("Qs"), card("Qh")],
+            p3_cards: [card("2h"), card("3h")],
+            flop: [card("As"), card("Ah"), card("Ad")],
+            turn: card("9c"),
+            river: card("8c"),
         },
         Expectation {
             winner_chips: Some((300, 0, 0)),
@@ -455,12 +463,12 @@ fn test_wheel_vs_six_high_straight() {
             initial_bets: (100, 100, 100),
         },
         Cards {
-            p1_cards: [card("SA"), card("H10")],
-            p2_cards: [card("D6"), card("H7")],
-            p3_cards: [card("C10"), card("CJ")],
-            flop: [card("D3"), card("C4"), card("S5")],
-            turn: card("H2"),
-            river: card("C8"),
+            p1_cards: [card("As"), card("Th")],
+            p2_cards: [card("6d"), card("7h")],
+            p3_cards: [card("Tc"), card("Jc")],
+            flop: [card("3d"), card("4c"), card("5s")],
+            turn: card("2h"),
+            river: card("8c"),
         },
         Expectation {
             winner_chips: Some((0, 300, 0)),
@@ -479,12 +487,12 @@ fn test_royal_flush_vs_lower_straight_flush() {
             initial_bets: (100, 100, 100),
         },
         Cards {
-            p1_cards: [card("SA"), card("SK")],
-            p2_cards: [card("HK"), card("H9")],
-            p3_cards: [card("C2"), card("C3")],
-            flop: [card("SQ"), card("SJ"), card("S10")],
-            turn: card("HQ"),
-            river: card("HJ"),
+            p1_cards: [card("As"), card("Ks")],
+            p2_cards: [card("Kh"), card("9h")],
+            p3_cards: [card("2c"), card("3c")],
+            flop: [card("Qs"), card("Js"), card("Ts")],
+            turn: card("Qh"),
+            river: card("Jh"),
         },
         Expectation {
             winner_chips: Some((300, 0, 0)),
@@ -503,12 +511,12 @@ fn test_straight_flush_wheel() {
             initial_bets: (100, 100, 100),
         },
         Cards {
-            p1_cards: [card("SA"), card("S2")],
-            p2_cards: [card("H6"), card("D6")],
-            p3_cards: [card("H7"), card("H8")],
-            flop: [card("S3"), card("S4"), card("S5")],
-            turn: card("C6"),
-            river: card("S6"),
+            p1_cards: [card("As"), card("2s")],
+            p2_cards: [card("6h"), card("6d")],
+            p3_cards: [card("7h"), card("8h")],
+            flop: [card("3s"), card("4s"), card("5s")],
+            turn: card("6c"),
+            river: card("6s"),
         },
         Expectation {
             winner_chips: Some((300, 0, 0)),
@@ -527,12 +535,12 @@ fn test_board_straight_different_pairs() {
             initial_bets: (100, 100, 50),
         },
         Cards {
-            p1_cards: [card("HA"), card("DA")],
-            p2_cards: [card("HK"), card("DK")],
-            p3_cards: [card("H2"), card("H3")],
-            flop: [card("C7"), card("D8"), card("S9")],
-            turn: card("H10"),
-            river: card("CJ"),
+            p1_cards: [card("Ah"), card("Ad")],
+            p2_cards: [card("Kh"), card("Kd")],
+            p3_cards: [card("2h"), card("3h")],
+            flop: [card("7c"), card("8d"), card("9s")],
+            turn: card("Th"),
+            river: card("Jc"),
         },
         Expectation {
             winner_chips: Some((125, 125, 50)),
@@ -551,12 +559,12 @@ fn test_two_pair_kicker() {
             initial_bets: (100, 100, 100),
         },
         Cards {
-            p1_cards: [card("SA"), card("H3")],
-            p2_cards: [card("DJ"), card("H2")],
-            p3_cards: [card("C2"), card("C3")],
-            flop: [card("SK"), card("HK"), card("DQ")],
-            turn: card("CQ"),
-            river: card("H5"),
+            p1_cards: [card("As"), card("3h")],
+            p2_cards: [card("Jd"), card("2h")],
+            p3_cards: [card("2c"), card("3c")],
+            flop: [card("Ks"), card("Kh"), card("Qd")],
+            turn: card("Qc"),
+            river: card("5h"),
         },
         Expectation {
             winner_chips: Some((300, 0, 0)),
@@ -574,12 +582,12 @@ fn test_flush_tie_same_top_five() {
             initial_bets: (100, 100, 100),
         },
         Cards {
-            p1_cards: [card("S8"), card("S7")],
-            p2_cards: [card("S6"), card("S5")],
-            p3_cards: [card("S2"), card("S3")],
-            flop: [card("SA"), card("SK"), card("SQ")],
-            turn: card("SJ"),
-            river: card("S9"),
+            p1_cards: [card("8s"), card("7s")],
+            p2_cards: [card("6s"), card("5s")],
+            p3_cards: [card("2s"), card("3s")],
+            flop: [card("As"), card("Ks"), card("Qs")],
+            turn: card("Js"),
+            river: card("9s"),
         },
         Expectation {
             winner_chips: Some((100, 100, 100)),
@@ -598,15 +606,40 @@ fn test_sidepot() {
             initial_bets: (10, 70, 200),
         },
         Cards {
-            p1_cards: [card("SA"), card("SK")],
-            p2_cards: [card("H10"), card("D9")],
-            p3_cards: [card("H2"), card("H3")],
-            flop: [card("SQ"), card("S9"), card("S7")],
-            turn: card("C8"),
-            river: card("H6"),
+            p1_cards: [card("As"), card("Ks")],
+            p2_cards: [card("Th"), card("9d")],
+            p3_cards: [card("2h"), card("3h")],
+            flop: [card("Qs"), card("9s"), card("7s")],
+            turn: card("8c"),
+            river: card("6h"),
         },
         Expectation {
             winner_chips: Some((30, 120, 130)),
         },
     );
 }
+
+#[test]
+fn test_split_pot_drops_odd_chip() {
+    run_test(
+        GameSetup {
+            game_id: 21,
+            players_out: 0,
+            players_folded: 0,
+            initial_chips: (0, 0, 0),
+            initial_bets: (15, 15, 15),
+        },
+        Cards {
+            p1_cards: [card("Qc"), card("Jd")],
+            p2_cards: [card("9d"), card("7h")],
+            p3_cards: [card("Qh"), card("Jc")],
+            flop: [card("Ah"), card("Kd"), card("8c")],
+            turn: card("5s"),
+            river: card("2h"),
+        },
+        Expectation {
+            // compare_hands floors the split, so the 45th chip is not paid out.
+            winner_chips: Some((22, 0, 22)),
+        },
+    );
+}
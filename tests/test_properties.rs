@@ -0,0 +1,288 @@
+//! Random legal action sequences over several hands, checking after every
+//! transition that chips are conserved (bar the odd chips of split pots),
+//! `players_out` only grows, the state moves along a legal edge, blinds
+//! rotate and revealed cards are unique.
+
+use leo_bindings::leo_bindings_sdk::{Account, VMManager, snapshot_store};
+use mental_poker_bindings::mental_poker::MentalPokerAleo;
+use poker::cards::PlayingCard;
+use poker::game::GameState;
+use poker::history::Street;
+use poker::rigged::Deal;
+use poker::scenario::{Action, GAME_ID, Step, Table};
+use proptest::prelude::*;
+use proptest::test_runner::TestCaseError;
+use snarkvm::prelude::TestnetV0;
+
+const HANDS: u8 = 4;
+const BLINDS: (u16, u16) = (5, 10);
+
+snapshot_store!(SETUP, |store| {
+    let account = Account::dev_account(0).unwrap();
+    MentalPokerAleo::new(&account, store.vm().clone()).unwrap();
+    store.save("deployed");
+});
+
+/// Random deals, ties included.
+fn deal() -> impl Strategy<Value = Deal> {
+    Just((0..52u8).collect::<Vec<u8>>())
+        .prop_shuffle()
+        .prop_map(|cards| {
+            let card = |i: usize| PlayingCard::from_index(cards[i]).unwrap();
+            Deal {
+                player1: [card(0), card(1)],
+                player2: [card(2), card(3)],
+                player3: [card(4), card(5)],
+                flop: [card(6), card(7), card(8)],
+                turn: card(9),
+                river: card(10),
+            }
+        })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    Join,
+    /// Decryptions before betting on street 0 (preflop) to 3 (river).
+    Decrypt(u8),
+    Bet(u8),
+    Showdown,
+    Compare,
+    NewHand,
+    Shuffle,
+    Claim,
+    Completed,
+}
+
+fn phase(state: u8) -> Option<Phase> {
+    Some(match state {
+        0..=1 => Phase::Join,
+        2..=25 if (state - 2) % 6 < 3 => Phase::Decrypt((state - 2) / 6),
+        2..=25 => Phase::Bet((state - 2) / 6),
+        26..=28 => Phase::Showdown,
+        29 => Phase::Compare,
+        30..=31 => Phase::NewHand,
+        32..=33 => Phase::Shuffle,
+        34..=36 => Phase::Claim,
+        255 => Phase::Completed,
+        _ => return None,
+    })
+}
+
+fn legal_edge(from: u8, to: u8) -> bool {
+    let (Some(from_phase), Some(to_phase)) = (phase(from), phase(to)) else {
+        return false;
+    };
+    from != to
+        && match (from_phase, to_phase) {
+            (Phase::Decrypt(a), Phase::Decrypt(b)) => b == a || (a > 0 && b == a + 1),
+            (Phase::Decrypt(a), Phase::Bet(b)) => a == b,
+            (Phase::Bet(a), Phase::Bet(b)) => a == b,
+            (Phase::Bet(a), Phase::Decrypt(b)) => b == a + 1,
+            (Phase::Decrypt(3) | Phase::Bet(3), Phase::Showdown) => true,
+            (Phase::Bet(_), Phase::NewHand) => true,
+            (Phase::Showdown, Phase::Showdown | Phase::Compare) => true,
+            (Phase::Compare, Phase::NewHand | Phase::Claim) => true,
+            (Phase::NewHand, Phase::Shuffle) => true,
+            (Phase::Shuffle, Phase::Shuffle | Phase::Decrypt(0)) => true,
+            (Phase::Claim, Phase::Completed) => true,
+            _ => false,
+        }
+}
+
+/// The seat after `seat` (a player bitmap) that is still in the game.
+fn next_seat(seat: u8, out: u8) -> u8 {
+    let index = seat.trailing_zeros();
+    (1..=3)
+        .map(|offset| 1u8 << ((index + offset) % 3))
+        .find(|bit| out & bit == 0)
+        .unwrap_or(seat)
+}
+
+/// The chips and game fields the invariants compare across transitions.
+#[derive(Debug, Clone, Copy)]
+struct Observed {
+    state: u8,
+    players_out: u8,
+    dealer_button: u8,
+    blinds: (u16, u16),
+    stacks: [u16; 3],
+    total: u16,
+}
+
+impl Observed {
+    fn new<M: VMManager<TestnetV0>>(table: &Table<M>) -> Result<Self, TestCaseError> {
+        let game = table.game().map_err(fail)?;
+        let chips = table.chips().map_err(fail)?;
+        let stacks = [chips.player1, chips.player2, chips.player3];
+        Ok(Self {
+            state: game.state,
+            players_out: game.players_out,
+            dealer_button: game.dealer_button,
+            blinds: (game.sb, game.bb),
+            stacks,
+            total: stacks.iter().sum::<u16>() + chips.pot,
+        })
+    }
+
+    /// Checks the transition from `self` to the table's current state.
+    fn check<M: VMManager<TestnetV0>>(&self, table: &Table<M>) -> Result<Self, TestCaseError> {
+        let next = Observed::new(table)?;
+        prop_assert!(next.total <= self.total, "chips created");
+        // `compare_hands` floors split pots and drops the odd chips: at most
+        // two from a three-way main pot and one from a two-way side pot.
+        let dropped = self.total - next.total;
+        prop_assert!(
+            dropped == 0 || (phase(self.state) == Some(Phase::Compare) && dropped <= 3),
+            "{} chips destroyed leaving state {}",
+            dropped,
+            self.state
+        );
+        prop_assert_eq!(
+            self.players_out & !next.players_out,
+            0,
+            "players_out shrank from {:03b} to {:03b}",
+            self.players_out,
+            next.players_out
+        );
+        prop_assert!(
+            legal_edge(self.state, next.state),
+            "illegal transition {} -> {}",
+            self.state,
+            next.state
+        );
+        if let Some(player) = GameState::from_u8(next.state).and_then(|s| s.current_player()) {
+            prop_assert_eq!(
+                next.players_out & (1 << (player - 1)),
+                0,
+                "eliminated P{} is asked to act",
+                player
+            );
+        }
+
+        if phase(self.state) == Some(Phase::NewHand) {
+            let dealer = next_seat(self.dealer_button, self.players_out);
+            let small = if self.players_out != 0 {
+                dealer
+            } else {
+                next_seat(dealer, self.players_out)
+            };
+            let big = next_seat(small, self.players_out);
+            let chips = table.chips().map_err(fail)?;
+            let bets = [chips.player1_bet, chips.player2_bet, chips.player3_bet];
+            let expected: [u16; 3] = std::array::from_fn(|i| {
+                let blind = match 1u8 << i {
+                    bit if bit == small => self.blinds.0,
+                    bit if bit == big => self.blinds.1,
+                    _ => 0,
+                };
+                blind.min(self.stacks[i])
+            });
+            prop_assert_eq!(next.dealer_button, dealer, "dealer button did not rotate");
+            prop_assert_eq!(bets, expected, "wrong blinds posted");
+        }
+
+        let revealed = table
+            .poker
+            .get_revealed_cards(GAME_ID)
+            .ok_or_else(|| TestCaseError::fail("revealed cards not found"))?;
+        let mut cards: Vec<u8> = [revealed.player1, revealed.player2, revealed.player3]
+            .concat()
+            .into_iter()
+            .chain(revealed.flop)
+            .chain([revealed.turn, revealed.river])
+            .filter(|&card| card != u8::MAX)
+            .collect();
+        let shown = cards.len();
+        cards.sort_unstable();
+        cards.dedup();
+        prop_assert_eq!(cards.len(), shown, "a card is revealed twice");
+        Ok(next)
+    }
+}
+
+fn fail(e: anyhow::Error) -> TestCaseError {
+    TestCaseError::fail(e.to_string())
+}
+
+/// The legal actions for `player`, simplest first so that shrinking
+/// `choice` towards zero turns actions into calls.
+fn choose<M: VMManager<TestnetV0>>(
+    table: &Table<M>,
+    player: u8,
+    choice: u8,
+) -> anyhow::Result<Step> {
+    let game = table.game()?;
+    let chips = table.chips()?;
+    let stack = chips.get_chips(player);
+    let to_call = chips.highest_bet() - chips.get_current_bet(player);
+    let raise_size = if game.last_raise_size == 0 {
+        game.bb
+    } else {
+        game.last_raise_size
+    };
+
+    let mut actions = vec![Action::Call, Action::Fold];
+    if to_call + raise_size < stack {
+        actions.push(Action::Bet(to_call + raise_size));
+    }
+    if stack > to_call {
+        actions.push(Action::AllIn);
+    }
+    Ok(Step {
+        player,
+        action: actions[choice as usize % actions.len()],
+    })
+}
+
+fn play(stacks: [u16; 3], deals: &[Deal], choices: &[u8]) -> Result<(), TestCaseError> {
+    let mut table = Table::seat(SETUP.restore("deployed"), &deals[0]).map_err(fail)?;
+    table.set_stacks(stacks, BLINDS).map_err(fail)?;
+    let mut observed = Observed::new(&table)?;
+    let mut deals = deals[1..].iter();
+    let mut choices = choices.iter().copied();
+    let mut steps = Vec::new();
+
+    loop {
+        let game = table.game().map_err(fail)?;
+        let state = GameState::from_u8(game.state)
+            .ok_or_else(|| TestCaseError::fail(format!("unknown state {}", game.state)))?;
+        if state.is_terminal() || game.hands_played >= HANDS {
+            return Ok(());
+        }
+        if matches!(state, GameState::P1NewShuffle | GameState::P2NewShuffle)
+            && let Some(deal) = deals.next()
+        {
+            table.deal_next(deal).map_err(fail)?;
+        }
+
+        if state.is_betting_state() {
+            let player = state.current_player().unwrap();
+            let street = Street::of_state(game.state).unwrap();
+            let step = choose(&table, player, choices.next().unwrap_or(0)).map_err(fail)?;
+            steps.push(step.to_string());
+            table
+                .act(street, step)
+                .map_err(|e| TestCaseError::fail(format!("{} after {:?}: {}", step, steps, e)))?;
+        } else if !table.advance().map_err(fail)? {
+            return Err(TestCaseError::fail(format!("stuck in state {:?}", state)));
+        }
+        observed = observed
+            .check(&table)
+            .map_err(|e| TestCaseError::fail(format!("{} after {:?}", e, steps)))?;
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(16))]
+
+    #[test]
+    fn test_random_hands_keep_invariants(
+        stacks in proptest::array::uniform3(30u16..=150),
+        deals in proptest::collection::vec(deal(), HANDS as usize),
+        choices in proptest::collection::vec(any::<u8>(), 0..60),
+    ) {
+        leo_bindings::utils::init_test_logger();
+        play(stacks, &deals, &choices)?;
+    }
+}
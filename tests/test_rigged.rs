@@ -0,0 +1,168 @@
+use credits_bindings::credits::*;
+use leo_bindings::leo_bindings_sdk::{Account, LocalVM};
+use mental_poker_bindings::mental_poker::*;
+use poker::cards::{compute_card_hashes_from_deck, decrypt_hand_local, get_player_cards};
+use poker::deck::initialized_deck;
+use poker::rigged::{Deal, rigged_permutations};
+use poker::waksman_ctrl::permute_deck;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use snarkvm::console::network::TestnetV0;
+use snarkvm::prelude::{Inverse, Scalar, TestRng, Uniform};
+use std::str::FromStr;
+
+const PRIVATE_KEY: &str = "APrivateKey1zkp8CZNn3yeCseEtxuVPbDCwSyhGW6yZKUYKfgXmcpoGPWH";
+
+/// Deals a chosen hand through create, join and decrypt, then plays it to
+/// showdown.
+#[test]
+fn test_rigged_deal_to_showdown() {
+    leo_bindings::utils::init_test_logger();
+    let alice = Account::from_str(PRIVATE_KEY).unwrap();
+    let bob = Account::dev_account(1).unwrap();
+    let charlie = Account::dev_account(2).unwrap();
+    let vm = LocalVM::new().unwrap();
+    let poker = MentalPokerAleo::new(&alice, vm.clone()).unwrap();
+    let credits = CreditsAleo::new(&alice, vm).unwrap();
+    credits
+        .transfer_public(&alice, bob.address(), 1_000_000_000_000)
+        .unwrap();
+    credits
+        .transfer_public(&alice, charlie.address(), 1_000_000_000_000)
+        .unwrap();
+
+    // POKER_TEST_SEED replays the shuffles of an earlier run.
+    let seed = std::env::var("POKER_TEST_SEED")
+        .ok()
+        .and_then(|seed| seed.parse().ok())
+        .unwrap_or_else(rand::random::<u64>);
+    println!("POKER_TEST_SEED={}", seed);
+    let deal: Deal = "Ah Ad / Kc Qd / 9s 8h / 2c 7d Jh 4s 3c".parse().unwrap();
+    let perms = rigged_permutations(&deal, 3, &mut StdRng::seed_from_u64(seed));
+
+    let mut rng = TestRng::default();
+    let secrets: [Scalar<TestnetV0>; 3] = std::array::from_fn(|_| Scalar::rand(&mut rng));
+    let inverses = secrets.map(|s| Inverse::inverse(&s).unwrap());
+
+    let (_, control) = permute_deck(initialized_deck::<TestnetV0>(), &perms[0]);
+    let (alice_keys, _) = poker
+        .create_game(&alice, 1, control, secrets[0], inverses[0], 0u128, 3u8)
+        .unwrap();
+
+    let deck = poker.get_decks(0).unwrap();
+    let (_, control) = permute_deck(deck, &perms[1]);
+    let (bob_keys, _) = poker
+        .join_game(&bob, 0, 1u64, deck, control, secrets[1], inverses[1], 0u128)
+        .unwrap();
+
+    let deck = poker.get_decks(0).unwrap();
+    let (_, control) = permute_deck(deck, &perms[2]);
+    let (charlie_keys, _) = poker
+        .join_game(
+            &charlie,
+            0,
+            1u64,
+            deck,
+            control,
+            secrets[2],
+            inverses[2],
+            0u128,
+        )
+        .unwrap();
+
+    let cards = poker.get_cards(0).unwrap();
+    let (alice_keys, _) = poker
+        .decrypt_hands(&alice, 0, cards.player2, cards.player3, alice_keys)
+        .unwrap();
+    let cards = poker.get_cards(0).unwrap();
+    let (bob_keys, _) = poker
+        .decrypt_hands(&bob, 0, cards.player3, cards.player1, bob_keys)
+        .unwrap();
+    let cards = poker.get_cards(0).unwrap();
+    let (charlie_keys, _) = poker
+        .decrypt_hands(&charlie, 0, cards.player1, cards.player2, charlie_keys)
+        .unwrap();
+
+    // Each hand is now only encrypted under its owner's secret.
+    let card_hashes = compute_card_hashes_from_deck(initialized_deck::<TestnetV0>());
+    let cards = poker.get_cards(0).unwrap();
+    for (player, expected) in [(1, deal.player1), (2, deal.player2), (3, deal.player3)] {
+        let hand = decrypt_hand_local(
+            get_player_cards(player, &cards),
+            inverses[player as usize - 1],
+            &card_hashes,
+        );
+        assert_eq!(hand, Some(expected), "P{} hand", player);
+    }
+
+    poker.bet(&charlie, 0, 10).unwrap();
+    poker.bet(&alice, 0, 5).unwrap();
+    poker.bet(&bob, 0, 0).unwrap();
+
+    let (alice_keys, _) = poker
+        .decrypt_flop(&alice, 0, poker.get_cards(0).unwrap().flop, alice_keys)
+        .unwrap();
+    let (bob_keys, _) = poker
+        .decrypt_flop(&bob, 0, poker.get_cards(0).unwrap().flop, bob_keys)
+        .unwrap();
+    let (charlie_keys, _) = poker
+        .decrypt_flop(&charlie, 0, poker.get_cards(0).unwrap().flop, charlie_keys)
+        .unwrap();
+    assert_eq!(
+        poker.get_revealed_cards(0).unwrap().flop,
+        deal.flop.map(u8::from)
+    );
+
+    let mut keys = [alice_keys, bob_keys, charlie_keys];
+    let players = [&alice, &bob, &charlie];
+    for street in ["turn", "river"] {
+        for player in players {
+            poker.bet(player, 0, 0).unwrap();
+        }
+        for (player, key) in players.iter().zip(keys.iter_mut()) {
+            let cards = poker.get_cards(0).unwrap();
+            let card = if street == "turn" {
+                cards.turn
+            } else {
+                cards.river
+            };
+            let (next, _) = poker
+                .decrypt_turn_river(player, 0, card, key.clone())
+                .unwrap();
+            *key = next;
+        }
+    }
+    for player in players {
+        poker.bet(player, 0, 0).unwrap();
+    }
+
+    let [alice_keys, bob_keys, charlie_keys] = keys;
+    poker
+        .showdown(&alice, 0, poker.get_cards(0).unwrap().player1, alice_keys)
+        .unwrap();
+    poker
+        .showdown(&bob, 0, poker.get_cards(0).unwrap().player2, bob_keys)
+        .unwrap();
+    poker
+        .showdown(
+            &charlie,
+            0,
+            poker.get_cards(0).unwrap().player3,
+            charlie_keys,
+        )
+        .unwrap();
+
+    let revealed = poker.get_revealed_cards(0).unwrap();
+    assert_eq!(revealed.player1, deal.player1.map(u8::from));
+    assert_eq!(revealed.player2, deal.player2.map(u8::from));
+    assert_eq!(revealed.player3, deal.player3.map(u8::from));
+    assert_eq!(revealed.turn, u8::from(deal.turn));
+    assert_eq!(revealed.river, u8::from(deal.river));
+
+    poker.compare_hands(&charlie, 0).unwrap();
+
+    // Pair of aces takes the pot.
+    let chips = poker.get_chips(0).unwrap();
+    assert!(chips.player1 > chips.player2 && chips.player1 > chips.player3);
+    assert_eq!(chips.player2, chips.player3);
+}
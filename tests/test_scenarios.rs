@@ -0,0 +1,90 @@
+use leo_bindings::leo_bindings_sdk::{Account, snapshot_store};
+use mental_poker_bindings::mental_poker::MentalPokerAleo;
+use poker::game::GameState;
+use poker::scenario::Scenario;
+use snarkvm::prelude::TestnetV0;
+
+const DEAL: &str = "Ah Ad / Kc Qd / 9s 8h / 2c 7d Jh 4s 3c";
+
+snapshot_store!(SETUP, |store| {
+    let account = Account::dev_account(0).unwrap();
+    MentalPokerAleo::new(&account, store.vm().clone()).unwrap();
+    store.save("deployed");
+});
+
+fn run(scenario: Scenario) {
+    leo_bindings::utils::init_test_logger();
+    scenario.run_on(SETUP.restore("deployed")).unwrap();
+}
+
+#[test]
+fn test_checked_down_to_showdown() {
+    run(Scenario::new("checked down")
+        .deal(DEAL)
+        .preflop("p3 call, p1 call, p2 check")
+        .expect_stacks([990, 990, 990])
+        .expect_bets([10, 10, 10])
+        .expect_state(GameState::P1BetFlop)
+        .flop("p1 check, p2 check, p3 check")
+        .expect_state(GameState::P1BetTurn)
+        .turn("p1 check, p2 check, p3 check")
+        .river("p1 check, p2 check, p3 check")
+        .expect_stacks([1020, 990, 990])
+        .expect_bets([0, 0, 0])
+        .expect_state(GameState::P1NewShuffle));
+}
+
+#[test]
+fn test_blinds_stolen_preflop() {
+    run(Scenario::new("steal")
+        .deal(DEAL)
+        .preflop("p3 bet 30, p1 fold")
+        .expect_state(GameState::P2BetPre)
+        .expect_stacks([995, 990, 970])
+        .preflop("p2 fold")
+        .expect_stacks([995, 990, 1015])
+        .expect_folded(0b011)
+        .expect_state(GameState::P1NewShuffle));
+}
+
+#[test]
+fn test_flop_bet_and_turn_fold() {
+    run(Scenario::new("turn fold")
+        .deal(DEAL)
+        .preflop("p3 call, p1 call, p2 check")
+        .flop("p1 bet 20, p2 call, p3 fold")
+        .expect_stacks([970, 970, 990])
+        .expect_bets([30, 30, 10])
+        .expect_folded(0b100)
+        .expect_state(GameState::P1BetTurn)
+        .turn("p1 check, p2 bet 50")
+        .expect_state(GameState::P1BetTurn)
+        .turn("p1 fold")
+        .expect_stacks([970, 1040, 990])
+        .expect_state(GameState::P1NewShuffle));
+}
+
+#[test]
+fn test_custom_stacks_and_blinds() {
+    run(Scenario::new("deep blinds")
+        .deal(DEAL)
+        .stacks([500, 800, 1200])
+        .blinds(25, 50)
+        .preflop("p3 bet 150, p1 fold, p2 call")
+        .expect_stacks([475, 650, 1050])
+        .expect_bets([25, 150, 150])
+        .expect_state(GameState::P2BetFlop)
+        .flop("p2 check, p3 bet 200, p2 fold")
+        .expect_stacks([475, 650, 1375]));
+}
+
+#[test]
+fn test_out_of_turn_action_fails() {
+    leo_bindings::utils::init_test_logger();
+    let err = Scenario::new("out of turn")
+        .deal(DEAL)
+        .preflop("p1 call")
+        .run_on(SETUP.restore("deployed"))
+        .unwrap_err();
+    assert!(err.to_string().contains("not P1's turn"), "{}", err);
+}
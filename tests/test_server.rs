@@ -0,0 +1,147 @@
+use poker::config::Profile;
+use poker::game::{Game, new_local_game};
+use poker::game_state::NetworkType;
+use poker::server::Server;
+use serde_json::{Value, json};
+use std::collections::VecDeque;
+use std::net::TcpStream;
+use std::thread;
+use std::time::{Duration, Instant};
+use tungstenite::stream::MaybeTlsStream;
+use tungstenite::{Message, WebSocket};
+
+const TIMEOUT: Duration = Duration::from_secs(1800);
+
+struct RpcClient {
+    socket: WebSocket<MaybeTlsStream<TcpStream>>,
+    next_id: u64,
+    notifications: VecDeque<Value>,
+}
+
+impl RpcClient {
+    fn connect(url: &str) -> Self {
+        let (socket, _) = tungstenite::connect(url).unwrap();
+        Self {
+            socket,
+            next_id: 0,
+            notifications: VecDeque::new(),
+        }
+    }
+
+    /// Reads the next JSON message, failing once `deadline` passes rather
+    /// than blocking on a server that went quiet.
+    fn read(&mut self, deadline: Instant) -> Value {
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            assert!(!remaining.is_zero(), "Timed out waiting for the server");
+            if let MaybeTlsStream::Plain(stream) = self.socket.get_mut() {
+                stream.set_read_timeout(Some(remaining)).unwrap();
+            }
+            match self.socket.read() {
+                Ok(Message::Text(text)) => return serde_json::from_str(&text).unwrap(),
+                Ok(_) => {}
+                Err(tungstenite::Error::Io(e))
+                    if matches!(
+                        e.kind(),
+                        std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+                    ) => {}
+                Err(e) => panic!("WebSocket read failed: {}", e),
+            }
+        }
+    }
+
+    fn call(&mut self, method: &str, params: Value) -> Result<Value, Value> {
+        self.next_id += 1;
+        let id = self.next_id;
+        let request = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+        self.socket
+            .send(Message::Text(request.to_string()))
+            .unwrap();
+        let deadline = Instant::now() + TIMEOUT;
+        loop {
+            let message = self.read(deadline);
+            if message["id"] == id {
+                return match message.get("error") {
+                    Some(error) => Err(error.clone()),
+                    None => Ok(message["result"].clone()),
+                };
+            }
+            self.notifications.push_back(message);
+        }
+    }
+
+    fn wait_for_state(&mut self, predicate: impl Fn(&Value) -> bool) -> Value {
+        let deadline = Instant::now() + TIMEOUT;
+        loop {
+            let message = match self.notifications.pop_front() {
+                Some(message) => message,
+                None => self.read(deadline),
+            };
+            if message["method"] == "state" && predicate(&message["params"]) {
+                return message["params"].clone();
+            }
+        }
+    }
+}
+
+#[test]
+fn server_local() {
+    leo_bindings::utils::init_test_logger();
+    let profile = Profile::builtin(NetworkType::Local);
+    let games = new_local_game()
+        .unwrap()
+        .into_iter()
+        .map(|handle| Game::new(handle, &profile))
+        .collect();
+    let server = Server::bind("127.0.0.1:0", games).unwrap();
+    let url = format!("ws://{}", server.local_addr());
+    thread::spawn(move || server.run());
+
+    let mut client = RpcClient::connect(&url);
+    assert_eq!(
+        client
+            .call("seats", json!({}))
+            .unwrap()
+            .as_array()
+            .unwrap()
+            .len(),
+        3
+    );
+    assert!(
+        client
+            .call("bet", json!({ "seat": 0, "action": "call" }))
+            .is_err()
+    );
+    assert!(client.call("state", json!({ "seat": 3 })).is_err());
+    assert!(client.call("shuffle", json!({})).is_err());
+
+    for seat in 0..3 {
+        client.call("subscribe", json!({ "seat": seat })).unwrap();
+    }
+
+    client
+        .call(
+            "create",
+            json!({ "seat": 0, "buy_in": 1.0, "blind_frequency": 3 }),
+        )
+        .unwrap();
+    let state = client.wait_for_state(|s| s["seat"] == 0 && s["game_id"].is_u64());
+    let game_id = state["game_id"].as_u64().unwrap();
+
+    client
+        .call("join", json!({ "seat": 1, "game_id": game_id }))
+        .unwrap();
+    client.wait_for_state(|s| s["seat"] == 1 && s["player_id"] == 2);
+    client
+        .call("join", json!({ "seat": 2, "game_id": game_id }))
+        .unwrap();
+    client.wait_for_state(|s| s["seat"] == 2 && s["player_id"] == 3);
+
+    let state = client.wait_for_state(|s| s["betting"].is_object());
+    let seat = state["seat"].as_u64().unwrap();
+    let before = state["state"].clone();
+    client
+        .call("bet", json!({ "seat": seat, "action": "call" }))
+        .unwrap();
+    client.wait_for_state(|s| s["seat"] == seat && s["state"] != before);
+}